@@ -25,6 +25,17 @@ pub const CALLER_SAVED_REGISTERS: [&str; 11] = [
 /// Callee-saved регистры (должны быть сохранены вызываемой функцией)
 pub const CALLEE_SAVED_REGISTERS: [&str; 7] = ["rbx", "rsp", "rbp", "r12", "r13", "r14", "r15"];
 
+/// Где передается аргумент вызова
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgLocation {
+    /// Целочисленный регистр (rdi, rsi, ...)
+    Integer(&'static str),
+    /// Векторный регистр (xmm0-xmm7)
+    Float(&'static str),
+    /// Слот в стеке: номер 8-байтовой ячейки над адресом возврата
+    Stack(usize),
+}
+
 /// Соглашение о вызовах
 #[derive(Debug, Clone, Copy)]
 pub struct CallingConvention;
@@ -40,6 +51,33 @@ impl CallingConvention {
         FLOAT_ARG_REGISTERS.get(index).copied()
     }
 
+    /// Распределяет аргументы по регистрам и стеку (System V)
+    ///
+    /// Целые и вещественные аргументы занимают регистры своего класса
+    /// независимо друг от друга: `f(int, float, int)` передается в rdi, xmm0, rsi.
+    /// Аргументы, которым не хватило регистров, идут в стек в исходном порядке.
+    pub fn classify_args(types: &[crate::ir::IRType]) -> Vec<ArgLocation> {
+        let mut int_idx = 0;
+        let mut float_idx = 0;
+        let mut stack_idx = 0;
+        types
+            .iter()
+            .map(|typ| {
+                let reg = if *typ == crate::ir::IRType::Float {
+                    float_idx += 1;
+                    Self::float_arg_register(float_idx - 1).map(ArgLocation::Float)
+                } else {
+                    int_idx += 1;
+                    Self::integer_arg_register(int_idx - 1).map(ArgLocation::Integer)
+                };
+                reg.unwrap_or_else(|| {
+                    stack_idx += 1;
+                    ArgLocation::Stack(stack_idx - 1)
+                })
+            })
+            .collect()
+    }
+
     /// Возвращает регистр для возвращаемого значения
     pub fn return_register() -> &'static str {
         "rax"
@@ -188,13 +226,14 @@ impl ABI {
     /// Возвращает размер типа в байтах
     pub fn type_size(typ: &crate::ir::IRType) -> usize {
         match typ {
-            crate::ir::IRType::Int => 4,
+            crate::ir::IRType::Int | crate::ir::IRType::UInt => 4,
+            crate::ir::IRType::Long | crate::ir::IRType::ULong => 8,
             crate::ir::IRType::Float => 8,
             crate::ir::IRType::Bool => 1,
             crate::ir::IRType::Void => 0,
             crate::ir::IRType::String => 8,
             crate::ir::IRType::Char => 1,
-            crate::ir::IRType::Struct(_) => 8,
+            crate::ir::IRType::Struct(_, size) => *size,
             crate::ir::IRType::Pointer(_) => 8,
             crate::ir::IRType::Unknown => 0,
             crate::ir::IRType::Array(_, _) => 0,
@@ -204,13 +243,14 @@ impl ABI {
     /// Возвращает выравнивание типа
     pub fn type_alignment(typ: &crate::ir::IRType) -> usize {
         match typ {
-            crate::ir::IRType::Int => 4,
+            crate::ir::IRType::Int | crate::ir::IRType::UInt => 4,
+            crate::ir::IRType::Long | crate::ir::IRType::ULong => 8,
             crate::ir::IRType::Float => 8,
             crate::ir::IRType::Bool => 1,
             crate::ir::IRType::Void => 0,
             crate::ir::IRType::String => 8,
             crate::ir::IRType::Char => 1,
-            crate::ir::IRType::Struct(_) => 8,
+            crate::ir::IRType::Struct(_, _) => 8,
             crate::ir::IRType::Pointer(_) => 8,
             crate::ir::IRType::Unknown => 0,
             crate::ir::IRType::Array(_, _) => 0,
@@ -219,7 +259,7 @@ impl ABI {
 
     /// Проверяет, является ли тип агрегатным (структура)
     pub fn is_aggregate_type(typ: &crate::ir::IRType) -> bool {
-        matches!(typ, crate::ir::IRType::Struct(_))
+        matches!(typ, crate::ir::IRType::Struct(_, _))
     }
 }
 
@@ -236,6 +276,35 @@ mod tests {
         assert_eq!(CallingConvention::return_register(), "rax");
     }
 
+    #[test]
+    fn test_classify_mixed_args() {
+        use crate::ir::IRType;
+
+        let locations = CallingConvention::classify_args(&[
+            IRType::Int,
+            IRType::Float,
+            IRType::Int,
+            IRType::Float,
+        ]);
+        assert_eq!(
+            locations,
+            vec![
+                ArgLocation::Integer("rdi"),
+                ArgLocation::Float("xmm0"),
+                ArgLocation::Integer("rsi"),
+                ArgLocation::Float("xmm1"),
+            ]
+        );
+
+        let mut types = vec![IRType::Float; 9];
+        types.extend(vec![IRType::Int; 7]);
+        let locations = CallingConvention::classify_args(&types);
+        assert_eq!(locations[7], ArgLocation::Float("xmm7"));
+        assert_eq!(locations[8], ArgLocation::Stack(0));
+        assert_eq!(locations[14], ArgLocation::Integer("r9"));
+        assert_eq!(locations[15], ArgLocation::Stack(1));
+    }
+
     #[test]
     fn test_register_info() {
         let rax = RegisterInfo::for_name("rax").unwrap();
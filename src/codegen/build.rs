@@ -0,0 +1,159 @@
+//! Сборка исполняемого файла: ассемблирование и компоновка
+//!
+//! Сгенерированный ассемблер передается внешним инструментам:
+//! `nasm -f elf64` для получения объектного файла и `gcc` для компоновки.
+
+use super::{generate_assembly, generate_debug_assembly};
+use crate::ir::ProgramIR;
+use crate::preprocessor::IncludeOptions;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use thiserror::Error;
+
+/// Параметры сборки исполняемого файла
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    /// Применить оптимизации IR и кодогенерации
+    pub optimize: bool,
+    /// Применить инлайнинг функций
+    pub inline: bool,
+    /// Библиотеки для компоновки (`-l<имя>`)
+    pub libs: Vec<String>,
+    /// Статическая компоновка
+    pub static_link: bool,
+    /// Сохранить промежуточные `.asm` и `.o` файлы рядом с `output`
+    pub keep_temps: bool,
+    /// Исходный файл для отладочной информации DWARF (`-g`)
+    pub debug_file: Option<String>,
+    /// Ассемблер
+    pub assembler: String,
+    /// Компоновщик
+    pub linker: String,
+    /// Поиск файлов `#include`
+    pub includes: IncludeOptions,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            optimize: false,
+            inline: false,
+            libs: Vec::new(),
+            static_link: false,
+            keep_temps: false,
+            debug_file: None,
+            assembler: "nasm".to_string(),
+            linker: "gcc".to_string(),
+            includes: IncludeOptions::default(),
+        }
+    }
+}
+
+/// Ошибки сборки исполняемого файла
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("Ошибка компиляции: {0}")]
+    Compile(String),
+
+    #[error("Не найден {role} '{tool}': убедитесь, что он установлен и доступен через PATH")]
+    ToolNotFound { role: &'static str, tool: String },
+
+    #[error("{tool} завершился с ошибкой:\n{stderr}")]
+    ToolFailed { tool: String, stderr: String },
+
+    #[error("Ошибка ввода-вывода: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Пути промежуточных файлов сборки и временный каталог для них.
+///
+/// Сохраняемые файлы кладутся рядом с `output`, остальные - в отдельный
+/// каталог, чтобы не затереть одноименные файлы пользователя.
+fn temp_paths(output: &Path, keep: bool) -> io::Result<(PathBuf, PathBuf, Option<PathBuf>)> {
+    if keep {
+        return Ok((output.with_extension("asm"), output.with_extension("o"), None));
+    }
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "minic-build-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir)?;
+    Ok((dir.join("program.asm"), dir.join("program.o"), Some(dir)))
+}
+
+/// Запускает внешний инструмент и проверяет код завершения
+fn run_tool(role: &'static str, tool: &str, args: &[&std::ffi::OsStr]) -> Result<(), BuildError> {
+    let result = Command::new(tool).args(args).output();
+    let out = match result {
+        Ok(out) => out,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(BuildError::ToolNotFound {
+                role,
+                tool: tool.to_string(),
+            });
+        }
+        Err(e) => return Err(BuildError::Io(e)),
+    };
+
+    if !out.status.success() {
+        return Err(BuildError::ToolFailed {
+            tool: tool.to_string(),
+            stderr: String::from_utf8_lossy(&out.stderr).trim_end().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Генерирует ассемблер, ассемблирует и компонует программу в `output`
+pub fn build_executable(
+    program: &ProgramIR,
+    output: &Path,
+    options: &BuildOptions,
+) -> Result<(), BuildError> {
+    let (asm_path, obj_path, temp_dir) = temp_paths(output, options.keep_temps)?;
+    let result = match &options.debug_file {
+        Some(file) => generate_debug_assembly(program, options.optimize, file),
+        None => generate_assembly(program, options.optimize),
+    };
+
+    let status = std::fs::write(&asm_path, &result.assembly)
+        .map_err(BuildError::from)
+        .and_then(|_| assemble_and_link(&asm_path, &obj_path, output, options));
+
+    if let Some(dir) = temp_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    status
+}
+
+fn assemble_and_link(
+    asm_path: &Path,
+    obj_path: &Path,
+    output: &Path,
+    options: &BuildOptions,
+) -> Result<(), BuildError> {
+    let mut asm_args: Vec<&std::ffi::OsStr> = vec!["-f".as_ref(), "elf64".as_ref()];
+    if options.debug_file.is_some() {
+        asm_args.extend(["-g", "-F", "dwarf"].map(std::ffi::OsStr::new));
+    }
+    asm_args.extend([asm_path.as_os_str(), "-o".as_ref(), obj_path.as_os_str()]);
+    run_tool("ассемблер", &options.assembler, &asm_args)?;
+
+    let lib_args: Vec<String> = options.libs.iter().map(|l| format!("-l{}", l)).collect();
+    let mut link_args: Vec<&std::ffi::OsStr> = vec!["-no-pie".as_ref()];
+    if options.static_link {
+        link_args.push("-static".as_ref());
+    }
+    link_args.push(obj_path.as_os_str());
+    link_args.push("-o".as_ref());
+    link_args.push(output.as_os_str());
+    link_args.extend(lib_args.iter().map(std::ffi::OsStr::new));
+
+    run_tool("компоновщик", &options.linker, &link_args)
+}
@@ -4,6 +4,7 @@
 //! в ассемблер x86-64 с соблюдением System V AMD64 ABI.
 
 mod abi;
+mod build;
 pub mod control_flow_generator;
 pub mod expression_generator;
 pub mod label_manager;
@@ -12,9 +13,10 @@ mod stack_frame;
 mod x86_generator;
 
 pub use abi::{
-    ABI, CALLEE_SAVED_REGISTERS, CALLER_SAVED_REGISTERS, CallingConvention, FLOAT_ARG_REGISTERS,
+    ABI, ArgLocation, CALLEE_SAVED_REGISTERS, CALLER_SAVED_REGISTERS, CallingConvention, FLOAT_ARG_REGISTERS,
     INTEGER_ARG_REGISTERS, RETURN_REGISTERS, RegisterInfo, RegisterPurpose,
 };
+pub use build::{BuildError, BuildOptions, build_executable};
 pub use control_flow_generator::{ControlFlowType, LabelManager as ControlFlowLabelManager};
 pub use expression_generator::ExpressionPriority;
 pub use label_manager::LabelManager;
@@ -42,8 +44,27 @@ impl fmt::Display for CodegenResult {
     }
 }
 
+/// Генерирует ассемблер для программы; при `optimize` перед генерацией
+/// к копии IR применяется peephole-оптимизатор
 pub fn generate_assembly(program: &ProgramIR, optimize: bool) -> CodegenResult {
-    let mut generator = X86Generator::new();
+    generate_with(X86Generator::new(), program, optimize)
+}
+
+/// Генерирует ассемблер с директивами `%line` для исходного файла
+/// `source_file` (отладочная информация `-g`)
+pub fn generate_debug_assembly(
+    program: &ProgramIR,
+    optimize: bool,
+    source_file: &str,
+) -> CodegenResult {
+    generate_with(X86Generator::new().with_debug_info(source_file), program, optimize)
+}
+
+fn generate_with(
+    mut generator: X86Generator,
+    program: &ProgramIR,
+    optimize: bool,
+) -> CodegenResult {
     if optimize {
         let mut program_copy = program.clone();
         let _ = crate::ir::PeepholeOptimizer::optimize(&mut program_copy);
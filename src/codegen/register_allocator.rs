@@ -4,7 +4,8 @@
 //! под переменные и временные значения вместо хранения в стеке.
 //! Поддерживает spill при нехватке регистров.
 
-use std::collections::HashMap;
+use crate::ir::{build_cfg, compute_liveness, FunctionIR};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 /// Регистры x86-64, доступные для аллокации
@@ -100,6 +101,8 @@ pub struct AdvancedRegisterAllocator {
     spill_counter: i32,
     stats: RegisterStatistics,
     min_spill_offset: i32,
+    /// Переменные, чей адрес берется `ADDR_OF`: они всегда живут на стеке
+    addressed: HashSet<String>,
 }
 
 impl AdvancedRegisterAllocator {
@@ -112,6 +115,7 @@ impl AdvancedRegisterAllocator {
             spill_counter: 0,
             stats: RegisterStatistics::default(),
             min_spill_offset: 0,
+            addressed: HashSet::new(),
         }
     }
 
@@ -119,77 +123,73 @@ impl AdvancedRegisterAllocator {
         self.min_spill_offset = offset;
     }
 
-    pub fn analyze_live_ranges(&mut self, instructions: &[crate::ir::IRInstruction]) {
+    /// Строит интервалы жизни по блокам функции в порядке `block_order`
+    ///
+    /// Позиции нумеруются по инструкциям в порядке размещения блоков.
+    /// Интервал покрывает все определения и использования переменной, а также
+    /// каждый блок, на входе или выходе которого она жива по результатам
+    /// анализа потока данных. Так переменная, используемая в заголовке цикла,
+    /// не освобождает регистр до конца последнего блока тела.
+    pub fn analyze_live_ranges(&mut self, func: &FunctionIR, block_order: &[String]) {
         self.intervals.clear();
         self.allocation.clear();
         self.active.clear();
+        self.addressed.clear();
         self.spill_counter = 0;
 
-        let mut first_def: HashMap<String, usize> = HashMap::new();
-        let mut last_use: HashMap<String, usize> = HashMap::new();
-
-        for (idx, instr) in instructions.iter().enumerate() {
-            let dests: Vec<String> = match instr {
-                crate::ir::IRInstruction::Add(d, _, _)
-                | crate::ir::IRInstruction::Sub(d, _, _)
-                | crate::ir::IRInstruction::Mul(d, _, _)
-                | crate::ir::IRInstruction::Div(d, _, _)
-                | crate::ir::IRInstruction::Mod(d, _, _)
-                | crate::ir::IRInstruction::And(d, _, _)
-                | crate::ir::IRInstruction::Or(d, _, _)
-                | crate::ir::IRInstruction::Xor(d, _, _)
-                | crate::ir::IRInstruction::Not(d, _)
-                | crate::ir::IRInstruction::Neg(d, _)
-                | crate::ir::IRInstruction::Move(d, _)
-                | crate::ir::IRInstruction::Load(d, _)
-                | crate::ir::IRInstruction::Call(d, _, _)
-                | crate::ir::IRInstruction::IntToFloat(d, _)
-                | crate::ir::IRInstruction::FloatToInt(d, _)
-                | crate::ir::IRInstruction::Alloca(d, _)
-                | crate::ir::IRInstruction::ArrayLoad(d, _, _)
-                | crate::ir::IRInstruction::CmpEq(d, _, _)
-                | crate::ir::IRInstruction::CmpNe(d, _, _)
-                | crate::ir::IRInstruction::CmpLt(d, _, _)
-                | crate::ir::IRInstruction::CmpLe(d, _, _)
-                | crate::ir::IRInstruction::CmpGt(d, _, _)
-                | crate::ir::IRInstruction::CmpGe(d, _, _)
-                | crate::ir::IRInstruction::CmpEqF(d, _, _)
-                | crate::ir::IRInstruction::CmpNeF(d, _, _)
-                | crate::ir::IRInstruction::CmpLtF(d, _, _)
-                | crate::ir::IRInstruction::CmpLeF(d, _, _)
-                | crate::ir::IRInstruction::CmpGtF(d, _, _)
-                | crate::ir::IRInstruction::CmpGeF(d, _, _)
-                | crate::ir::IRInstruction::CmpLtU(d, _, _)
-                | crate::ir::IRInstruction::CmpLeU(d, _, _)
-                | crate::ir::IRInstruction::CmpGtU(d, _, _)
-                | crate::ir::IRInstruction::CmpGeU(d, _, _) => {
-                    vec![Self::operand_name_to_string(d)]
-                }
-                _ => vec![],
+        let mut cfg_func = func.clone();
+        build_cfg(&mut cfg_func);
+        let liveness = compute_liveness(&cfg_func);
+
+        let mut ranges: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut touch = |name: &str, pos: usize| {
+            let range = ranges.entry(name.to_string()).or_insert((pos, pos));
+            range.0 = range.0.min(pos);
+            range.1 = range.1.max(pos);
+        };
+
+        let mut idx = 0;
+        for label in block_order {
+            let block = match cfg_func.blocks.get(label) {
+                Some(block) if !block.instructions.is_empty() => block,
+                _ => continue,
             };
+            let block_start = idx;
+            let block_end = idx + block.instructions.len() - 1;
 
-            for name in dests {
-                if !name.is_empty() {
-                    first_def.entry(name.clone()).or_insert(idx);
-                    last_use.insert(name.clone(), idx);
+            for instr in &block.instructions {
+                if let crate::ir::IRInstruction::AddrOf(_, src) = instr {
+                    self.addressed.insert(Self::operand_name_to_string(src));
+                }
+                if let Some(dest) = instr.dest() {
+                    let name = Self::operand_name_to_string(dest);
+                    if !name.is_empty() {
+                        touch(&name, idx);
+                    }
+                }
+                for op in instr.operands() {
+                    let name = Self::operand_name_to_string(op);
+                    if !name.is_empty() {
+                        touch(&name, idx);
+                    }
                 }
+                idx += 1;
             }
 
-            for op in instr.operands() {
-                let name = Self::operand_name_to_string(op);
-                if !name.is_empty() {
-                    first_def.entry(name.clone()).or_insert(idx);
-                    last_use.insert(name.clone(), idx);
-                }
+            for name in liveness.live_in(label).into_iter().flatten() {
+                touch(name, block_start);
+            }
+            for name in liveness.live_out(label).into_iter().flatten() {
+                touch(name, block_end);
             }
         }
 
-        for (name, start) in first_def {
-            let end = last_use.get(&name).copied().unwrap_or(start);
+        for (name, (start, end)) in ranges {
             self.intervals.push(LiveInterval::new(name, start, end));
         }
 
-        self.intervals.sort_by_key(|i| i.start);
+        self.intervals
+            .sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.var_name.cmp(&b.var_name)));
     }
 
     fn operand_name_to_string(op: &crate::ir::Operand) -> String {
@@ -216,7 +216,9 @@ impl AdvancedRegisterAllocator {
         for interval in self.intervals.clone() {
             self.expire_old_intervals(interval.start);
 
-            if self.free_registers.is_empty() {
+            if self.addressed.contains(&interval.var_name) {
+                self.spill_current(&interval);
+            } else if self.free_registers.is_empty() {
                 self.spill_at_interval(&interval);
                 if self.free_registers.is_empty() {
                     self.spill_current(&interval);
@@ -312,6 +314,7 @@ impl AdvancedRegisterAllocator {
         self.allocation.clear();
         self.active.clear();
         self.intervals.clear();
+        self.addressed.clear();
         self.spill_counter = 0;
         self.stats = RegisterStatistics::default();
     }
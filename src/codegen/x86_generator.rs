@@ -4,14 +4,16 @@
 //! переменных в регистрах вместо стека, генерирует прямые условные
 //! переходы (je/jg/jl/jb/ja) и поддерживает глобальные переменные.
 
+use super::abi::{ArgLocation, CallingConvention};
 use super::register_allocator::{AdvancedRegisterAllocator, Allocation};
-use crate::ir::{FunctionIR, IRInstruction, Operand, ProgramIR};
+use crate::ir::{reverse_postorder, FunctionIR, IRInstruction, IRType, Operand, ProgramIR};
+use crate::preprocessor::SourceMap;
 use std::collections::{HashMap, HashSet};
 
 pub struct X86Generator {
     string_counter: usize,
     string_literals: Vec<(String, String)>,
-    global_vars: Vec<(String, String)>,
+    global_vars: Vec<(String, IRType)>,
     stack_size: usize,
     current_function: Option<String>,
     allocator: AdvancedRegisterAllocator,
@@ -20,6 +22,18 @@ pub struct X86Generator {
     spill_total: i32,
     used_callee_saved: Vec<super::register_allocator::Register>,
     alloca_vars: HashSet<String>,
+    value_types: HashMap<String, IRType>,
+    signatures: HashMap<String, Vec<IRType>>,
+    /// Глобальные переменные уже 8 байт, чей адрес берется в программе
+    narrow_globals: HashSet<String>,
+    /// Переменные уже 8 байт, чей адрес берется: запись через указатель
+    /// меняет только младшие байты их 8-байтовой ячейки
+    narrow_addressed: Vec<(String, IRType)>,
+    return_type: IRType,
+    debug_file: Option<String>,
+    /// Последняя выданная директива `%line`: файл и строка
+    debug_line: Option<(String, usize)>,
+    source_map: Option<SourceMap>,
 }
 
 impl X86Generator {
@@ -36,21 +50,74 @@ impl X86Generator {
             spill_total: 0,
             used_callee_saved: Vec::new(),
             alloca_vars: HashSet::new(),
+            value_types: HashMap::new(),
+            signatures: HashMap::new(),
+            narrow_globals: HashSet::new(),
+            narrow_addressed: Vec::new(),
+            return_type: IRType::Void,
+            debug_file: None,
+            debug_line: None,
+            source_map: None,
         }
     }
 
+    /// Включает вывод директив `%line`, связывающих код со строками `file`
+    pub fn with_debug_info(mut self, file: impl Into<String>) -> Self {
+        self.debug_file = Some(file.into());
+        self
+    }
+
+    /// Директива `%line` при смене строки исходного кода. Строка IR
+    /// относится к результату препроцессора и переводится через карту
+    /// в строку основного или включенного файла.
+    fn line_directive(&mut self, line: Option<usize>) -> String {
+        let (Some(root), Some(line)) = (&self.debug_file, line) else {
+            return String::new();
+        };
+        let (file, line) = match self.source_map.as_ref().and_then(|m| m.locate_line(line)) {
+            Some((file, line)) => (file.map_or_else(|| root.clone(), |f| f.to_string()), line),
+            None => (root.clone(), line),
+        };
+        if self.debug_line.as_ref() == Some(&(file.clone(), line)) {
+            return String::new();
+        }
+        let directive = format!("%line {}+0 {}\n", line, file);
+        self.debug_line = Some((file, line));
+        directive
+    }
+
     pub fn generate(&mut self, program: &ProgramIR) -> super::CodegenResult {
         let mut output = String::new();
         let mut data_section = String::new();
         self.string_counter = 0;
         self.string_literals.clear();
         self.global_vars.clear();
+        self.source_map = program.source_map.clone();
 
-        for (name, _typ) in &program.globals {
-            self.global_vars.push((name.clone(), _typ.clone()));
+        for (name, typ) in &program.globals {
+            self.global_vars.push((name.clone(), typ.clone()));
         }
-
-        let mut has_data = !self.global_vars.is_empty();
+        self.narrow_globals = program
+            .functions
+            .iter()
+            .flat_map(|f| f.blocks.values())
+            .flat_map(|b| &b.instructions)
+            .filter_map(|instr| match instr {
+                IRInstruction::AddrOf(_, Operand::Variable(n)) => Some(n.clone()),
+                _ => None,
+            })
+            .filter(|n| {
+                self.global_vars
+                    .iter()
+                    .any(|(name, t)| name == n && Self::is_narrow_scalar(t))
+            })
+            .collect();
+        self.signatures = program
+            .functions
+            .iter()
+            .chain(program.externs.iter())
+            .map(|f| (f.name.clone(), f.parameters.iter().map(|(_, t)| t.clone()).collect()))
+            .collect();
 
         let mut text_output = String::new();
         text_output.push_str("section .text\n");
@@ -74,29 +141,69 @@ impl X86Generator {
             }
         }
 
-        if has_data {
-            data_section.push_str("section .data\n");
-            for (name, _typ) in &self.global_vars {
-                data_section.push_str(&format!("    {}: dq 0\n", name));
+        let mut bss_section = String::new();
+        for (name, typ) in &self.global_vars.clone() {
+            let slots = Self::global_slots(typ);
+            // Элементы массива идут подряд с шагом своего размера, массив
+            // дополняется до 8 байт; скаляр занимает 8-байтовый слот
+            let (data, reserve, slots) = match typ {
+                IRType::Array(..) => match Self::slot_size(&Self::innermost(typ)) {
+                    1 => ("db", "resb", slots.div_ceil(8) * 8),
+                    4 => ("dd", "resd", slots.div_ceil(2) * 2),
+                    _ => ("dq", "resq", slots),
+                },
+                _ => ("dq", "resq", slots),
+            };
+            // Структуры и массивы структур резервируются побайтно
+            let struct_size = match Self::innermost(typ) {
+                IRType::Struct(..) => Some(typ.size().max(1).div_ceil(8) * 8),
+                _ => None,
+            };
+            let values = program
+                .global_inits
+                .get(name)
+                .filter(|values| !values.iter().all(Self::is_zero_literal));
+            match (values, struct_size) {
+                (Some(values), None) => {
+                    let items: Vec<String> = values.iter().map(|v| self.data_item(v)).collect();
+                    let items = items.join(", ");
+                    data_section.push_str(&format!("    {}: {} {}\n", name, data, items));
+                    if slots > values.len() {
+                        data_section.push_str(&format!(
+                            "    times {} {} 0\n",
+                            slots - values.len(),
+                            data
+                        ));
+                    }
+                }
+                (_, Some(size)) => {
+                    bss_section.push_str(&format!("    {}: resb {}\n", name, size));
+                }
+                (None, None) => {
+                    let count = program.global_inits.get(name).map_or(0, |v| v.len());
+                    bss_section
+                        .push_str(&format!("    {}: {} {}\n", name, reserve, slots.max(count)));
+                }
             }
         }
-        if !self.string_literals.is_empty() {
-            if !has_data {
-                data_section.push_str("section .data\n");
-                has_data = true;
-            }
-            for (label, string) in &self.string_literals {
-                if label.ends_with(':') {
-                    data_section.push_str(&format!("{} {}\n", label, string));
-                } else {
-                    data_section.push_str(&format!("{}: {}\n", label, string));
-                }
+
+        let mut rodata_section = String::new();
+        for (label, value) in &self.string_literals {
+            if label.ends_with(':') {
+                rodata_section.push_str(&format!("{} {}\n", label, value));
+            } else {
+                rodata_section.push_str(&format!("{}: {}\n", label, value));
             }
         }
 
-        output.push_str(&data_section);
-        if has_data {
-            output.push_str("\n");
+        for (section, body, align) in [
+            (".data", &data_section, "align 8"),
+            (".bss", &bss_section, "alignb 8"),
+            (".rodata", &rodata_section, "align 8"),
+        ] {
+            if !body.is_empty() {
+                output.push_str(&format!("section {}\n    {}\n{}\n", section, align, body));
+            }
         }
         text_output.push_str("section .note.GNU-stack progbits\n");
         output.push_str(&text_output);
@@ -131,6 +238,274 @@ impl X86Generator {
         }
     }
 
+    /// Число скалярных элементов глобальной переменной (`i32[4]` -> 4)
+    fn global_slots(typ: &IRType) -> usize {
+        match typ {
+            IRType::Array(inner, count) => Self::global_slots(inner) * count,
+            _ => 1,
+        }
+        .max(1)
+    }
+
+    fn is_global_array(&self, op: &Operand) -> bool {
+        match op {
+            Operand::Variable(n) => self
+                .global_vars
+                .iter()
+                .any(|(name, typ)| name == n && matches!(typ, IRType::Array(..))),
+            _ => false,
+        }
+    }
+
+    /// Тип операнда по типизированному IR
+    fn operand_type(&self, op: &Operand) -> IRType {
+        match op {
+            Operand::IntLiteral(v) if i32::try_from(*v).is_ok() => IRType::Int,
+            Operand::IntLiteral(_) => IRType::Long,
+            Operand::FloatLiteral(_) => IRType::Float,
+            Operand::BoolLiteral(_) => IRType::Bool,
+            Operand::StringLiteral(_) => IRType::String,
+            Operand::Variable(n) | Operand::Temporary(n) => self
+                .value_types
+                .get(n)
+                .or_else(|| {
+                    self.global_vars
+                        .iter()
+                        .find(|(name, _)| name == n)
+                        .map(|(_, t)| t)
+                })
+                .cloned()
+                .unwrap_or(IRType::Unknown),
+            _ => IRType::Unknown,
+        }
+    }
+
+    /// Тип объекта уже 8 байт под указателем (`char*`, `bool*`, `int*`)
+    fn narrow_pointee(&self, op: &Operand) -> Option<IRType> {
+        match self.operand_type(op) {
+            IRType::Pointer(inner) if matches!(inner.size(), 1 | 4) => Some(*inner),
+            _ => None,
+        }
+    }
+
+    /// Скалярный элемент массива или указателя, по которому индексирует
+    /// `ArrayLoad`/`ArrayStore`: для многомерного массива - элемент
+    /// самого внутреннего измерения
+    fn indexed_element(&self, base: &Operand) -> IRType {
+        match self.operand_type(base) {
+            IRType::Array(inner, _) | IRType::Pointer(inner) => Self::innermost(&inner),
+            _ => IRType::Long,
+        }
+    }
+
+    /// Тип элемента самого внутреннего измерения массива
+    fn innermost(typ: &IRType) -> IRType {
+        match typ {
+            IRType::Array(inner, _) => Self::innermost(inner),
+            other => other.clone(),
+        }
+    }
+
+    /// Шаг элемента массива - его размер (`sizeof`)
+    fn slot_size(elem: &IRType) -> usize {
+        match elem.size() {
+            0 => 8,
+            size => size,
+        }
+    }
+
+    /// Адрес элемента `base[index]` с шагом `stride` байт в rax.
+    /// Локальный и глобальный массив адресуются своим размещением,
+    /// указатель - хранимым значением
+    fn element_addr(&mut self, base: &Operand, index: &Operand, stride: usize) -> String {
+        let global_array = self.is_global_array(base);
+        let bs = if global_array { self.addr_of(base) } else { self.op(base) };
+        let istr = self.op(index);
+        let mut code = Self::load_rax(&istr);
+        if stride != 1 {
+            code.push_str(&format!("    imul rax, {}\n", stride));
+        }
+        if global_array || self.alloca_vars.contains(self.get_operand_name(base)) {
+            code.push_str(&format!("    lea rdx, [{}]\n    add rax, rdx\n", bs));
+        } else {
+            code.push_str(&format!("    add rax, {}\n", bs));
+        }
+        code
+    }
+
+    /// Скаляр уже 8 байт: `int`, `unsigned int`, `char`, `bool`
+    fn is_narrow_scalar(typ: &IRType) -> bool {
+        matches!(typ, IRType::Int | IRType::UInt | IRType::Char | IRType::Bool)
+    }
+
+    /// Может ли инструкция записать узкое значение через указатель: запись
+    /// `int`/`char` по указателю и вызов функции, получившей адрес
+    fn writes_narrow_memory(&self, instr: &IRInstruction) -> bool {
+        if self.narrow_addressed.is_empty() {
+            return false;
+        }
+        match instr {
+            IRInstruction::Store(a, _) => self.narrow_pointee(a).is_some(),
+            IRInstruction::ArrayStore(base, _, _) => self.indexed_element(base).size() < 8,
+            IRInstruction::Call(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Заново расширяет значения узких переменных, чей адрес взят, до
+    /// 8-байтовой ячейки после записи в их младшие байты через указатель
+    fn renormalize_addressed(&mut self) -> String {
+        let mut code = String::new();
+        for (name, typ) in self.narrow_addressed.clone() {
+            let mem = format!("[{}]", self.addr_of(&Operand::Variable(name)));
+            code.push_str(&Self::sized_load(&typ, &mem));
+            code.push_str(&format!("    mov qword {}, rax\n", mem));
+        }
+        code
+    }
+
+    /// Загрузка 64-битного операнда в rax
+    fn load_rax(s: &str) -> String {
+        if s.starts_with('[') {
+            format!("    mov rax, qword {}\n", s)
+        } else {
+            format!("    mov rax, {}\n", s)
+        }
+    }
+
+    /// Загрузка значения для записи в память в rcx
+    fn load_rcx(&mut self, value: &Operand) -> String {
+        let vs = self.op(value);
+        if vs.starts_with("L_str") {
+            format!("    lea rcx, [rel {}]\n", vs)
+        } else if vs.starts_with("rbp-") || vs.starts_with("rbp+") {
+            format!("    lea rcx, [{}]\n", vs)
+        } else if vs.starts_with('[') {
+            format!("    mov rcx, qword {}\n", vs)
+        } else {
+            format!("    mov rcx, {}\n", vs)
+        }
+    }
+
+    /// Чтение значения типа `typ` из `mem` в rax: байты расширяются
+    /// `movsx` для `char` и `movzx` для `bool`, `int` - `movsxd`
+    fn sized_load(typ: &IRType, mem: &str) -> String {
+        match (typ, typ.size()) {
+            (IRType::Char, 1) => format!("    movsx rax, byte {}\n", mem),
+            (_, 1) => format!("    movzx eax, byte {}\n", mem),
+            (IRType::UInt, 4) => format!("    mov eax, dword {}\n", mem),
+            (_, 4) => format!("    movsxd rax, dword {}\n", mem),
+            _ => format!("    mov rax, qword {}\n", mem),
+        }
+    }
+
+    /// Запись младшей части rcx размера `typ` в `mem`
+    fn sized_store(typ: &IRType, mem: &str) -> String {
+        match typ.size() {
+            1 => format!("    mov byte {}, cl\n", mem),
+            4 => format!("    mov dword {}, ecx\n", mem),
+            _ => format!("    mov qword {}, rcx\n", mem),
+        }
+    }
+
+    /// Имена 32- и 8-битной частей 64-битного регистра
+    fn sub_registers(reg: &str) -> (String, String) {
+        match reg {
+            "rax" | "rbx" | "rcx" | "rdx" => {
+                (format!("e{}", &reg[1..]), format!("{}l", &reg[1..2]))
+            }
+            "rsi" | "rdi" => (format!("e{}", &reg[1..]), format!("{}l", &reg[1..])),
+            _ => (format!("{}d", reg), format!("{}b", reg)),
+        }
+    }
+
+    /// Приведение 64-битного регистра к типу `typ`: `int` расширяется знаком
+    /// из младших 32 бит, `unsigned int` - нулями, `char` - знаком из байта.
+    /// Значения шире 32 бит хранятся как есть
+    fn narrow(typ: &IRType, reg: &str) -> String {
+        let (dword, byte) = Self::sub_registers(reg);
+        match typ {
+            IRType::Int => format!("    movsxd {}, {}\n", reg, dword),
+            IRType::UInt => format!("    mov {}, {}\n", dword, dword),
+            IRType::Char => format!("    movsx {}, {}\n", reg, byte),
+            _ => String::new(),
+        }
+    }
+
+    /// Приведение результата целой операции к типу `d`; операции над
+    /// адресами и значениями неизвестного типа не затрагиваются
+    fn narrow_result(&self, d: &Operand, operands: &[&Operand], reg: &str) -> String {
+        if operands.iter().all(|op| self.operand_type(op).is_integer()) {
+            Self::narrow(&self.operand_type(d), reg)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Целая константа, приведенная к типу `typ` на этапе компиляции
+    fn truncate_literal(typ: &IRType, v: i64) -> i64 {
+        match typ {
+            IRType::Int => v as i32 as i64,
+            IRType::UInt => v as u32 as i64,
+            IRType::Char => v as i8 as i64,
+            _ => v,
+        }
+    }
+
+    fn is_float(&self, op: &Operand) -> bool {
+        self.operand_type(op) == IRType::Float
+    }
+
+    /// Загрузка операнда в xmm-регистр; целый операнд преобразуется в `f64`
+    fn load_xmm(&mut self, xmm: &str, op: &Operand) -> String {
+        let typ = self.operand_type(op);
+        let integer = typ.is_integer() || typ == IRType::Bool;
+        let s = self.op(op);
+        let mem = if s.starts_with("qword [") {
+            s.clone()
+        } else if s.starts_with('[') {
+            format!("qword {}", s)
+        } else {
+            String::new()
+        };
+        match (integer, mem.is_empty()) {
+            (true, false) => format!("    cvtsi2sd {}, {}\n", xmm, mem),
+            (true, true) => format!("    mov rax, {}\n    cvtsi2sd {}, rax\n", s, xmm),
+            (false, false) => format!("    movsd {}, {}\n", xmm, mem),
+            (false, true) => format!("    movq {}, {}\n", xmm, s),
+        }
+    }
+
+    fn is_zero_literal(op: &Operand) -> bool {
+        match op {
+            Operand::IntLiteral(v) => *v == 0,
+            Operand::BoolLiteral(v) => !*v,
+            Operand::FloatLiteral(v) => v.to_bits() == 0,
+            _ => false,
+        }
+    }
+
+    /// Значение ячейки в секции данных
+    fn data_item(&mut self, op: &Operand) -> String {
+        match op {
+            Operand::IntLiteral(v) => v.to_string(),
+            Operand::BoolLiteral(v) => (*v as i32).to_string(),
+            Operand::FloatLiteral(v) => Self::float_data(*v),
+            Operand::StringLiteral(_) => self.op(op),
+            _ => "0".to_string(),
+        }
+    }
+
+    /// Вещественная константа для `dq`: десятичная запись с точкой или битовый образ
+    fn float_data(v: f64) -> String {
+        let text = format!("{:?}", v);
+        if v.is_finite() && text.contains('.') && !text.contains('e') {
+            text
+        } else {
+            format!("0x{:016X}", v.to_bits())
+        }
+    }
+
     fn generate_function(&mut self, func: &FunctionIR) -> String {
         let mut output = String::new();
         self.current_function = Some(func.name.clone());
@@ -139,17 +514,24 @@ impl X86Generator {
         self.spill_total = 0;
         self.used_callee_saved.clear();
         self.alloca_vars.clear();
+        self.return_type = func.return_type.clone();
+        self.value_types = func
+            .parameters
+            .iter()
+            .chain(func.locals.iter())
+            .cloned()
+            .collect();
+        for temp in &func.temporaries {
+            if let Operand::Temporary(name) = &temp.operand {
+                self.value_types.insert(name.clone(), temp.typ.clone());
+            }
+        }
 
+        let block_order = reverse_postorder(func);
         let mut all_instructions: Vec<IRInstruction> = Vec::new();
-        let mut block_order: Vec<String> = Vec::new();
-        let mut blocks: Vec<&String> = func.blocks.keys().collect();
-        blocks.sort();
-        for label in &blocks {
-            if let Some(block) = func.blocks.get(*label) {
-                block_order.push(label.to_string());
-                for instr in &block.instructions {
-                    all_instructions.push(instr.clone());
-                }
+        for label in &block_order {
+            if let Some(block) = func.blocks.get(label) {
+                all_instructions.extend(block.instructions.iter().cloned());
             }
         }
 
@@ -177,12 +559,33 @@ impl X86Generator {
         self.allocator
             .set_min_spill_offset(alloca_total + callee_saved_estimate);
 
+        let mut narrow_addressed: Vec<(String, IRType)> = Vec::new();
+        for instr in &all_instructions {
+            if let IRInstruction::AddrOf(_, Operand::Variable(n)) = instr {
+                let typ = self.operand_type(&Operand::Variable(n.clone()));
+                let local = !self.global_vars.iter().any(|(name, _)| name == n);
+                let seen = narrow_addressed.iter().any(|(v, _)| v == n);
+                if local && Self::is_narrow_scalar(&typ) && !seen {
+                    narrow_addressed.push((n.clone(), typ));
+                }
+            }
+        }
+        for (name, typ) in &self.global_vars {
+            if self.narrow_globals.contains(name) {
+                narrow_addressed.push((name.clone(), typ.clone()));
+            }
+        }
+        self.narrow_addressed = narrow_addressed;
+
         self.allocator.reset();
-        self.allocator.analyze_live_ranges(&all_instructions);
+        self.allocator.analyze_live_ranges(func, &block_order);
         self.allocator.linear_scan_allocate();
         self.used_callee_saved = self.allocator.used_callee_saved();
         self.collect_spill_slots(&all_instructions);
 
+        self.debug_line = None;
+        let entry_line = func.blocks.get(&func.entry_block).and_then(|b| b.line_at(0));
+        output.push_str(&self.line_directive(entry_line));
         output.push_str(&format!("{}:\n", func.name));
         output.push_str("    push rbp\n    mov rbp, rsp\n");
 
@@ -190,22 +593,32 @@ impl X86Generator {
             output.push_str(&format!("    push {}\n", reg.name()));
         }
 
-        let param_regs = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
-        let num_stack_params = func.parameters.len().saturating_sub(6);
-        
-        for i in 6..func.parameters.len() {
-            let stack_offset = 16 + ((i - 6) as i32 * 8);
-            self.param_offsets.insert(func.parameters[i].0.clone(), stack_offset);
-        }
-        
+        let param_types: Vec<IRType> = func.parameters.iter().map(|(_, t)| t.clone()).collect();
+        let locations = CallingConvention::classify_args(&param_types);
+        let num_stack_params = locations
+            .iter()
+            .filter(|l| matches!(l, ArgLocation::Stack(_)))
+            .count();
+
         let mut param_offset = 16 + (num_stack_params as i32 * 8);
-        for i in 0..func.parameters.len().min(6) {
-            self.param_offsets.insert(func.parameters[i].0.clone(), param_offset);
-            output.push_str(&format!(
-                "    mov [rbp+{}], {}\n",
-                param_offset, param_regs[i]
-            ));
-            param_offset += 8;
+        for ((name, typ), location) in func.parameters.iter().zip(&locations) {
+            match location {
+                ArgLocation::Stack(slot) => {
+                    self.param_offsets.insert(name.clone(), 16 + *slot as i32 * 8);
+                }
+                ArgLocation::Integer(reg) => {
+                    self.param_offsets.insert(name.clone(), param_offset);
+                    // Старшие биты узкого целого аргумента не определены ABI
+                    output.push_str(&Self::narrow(typ, reg));
+                    output.push_str(&format!("    mov [rbp+{}], {}\n", param_offset, reg));
+                    param_offset += 8;
+                }
+                ArgLocation::Float(reg) => {
+                    self.param_offsets.insert(name.clone(), param_offset);
+                    output.push_str(&format!("    movsd qword [rbp+{}], {}\n", param_offset, reg));
+                    param_offset += 8;
+                }
+            }
         }
 
         if alloca_total > 0 {
@@ -225,18 +638,29 @@ impl X86Generator {
 
         self.stack_size = (alloca_total + extra_stack) as usize;
 
-        for block_label in &block_order {
+        for (idx, block_label) in block_order.iter().enumerate() {
             if let Some(block) = func.blocks.get(block_label) {
-                output.push_str(&format!(".{}:\n", block_label));
-                for instr in &block.instructions {
+                let next = block_order.get(idx + 1).map(|l| l.as_str());
+                let instructions = Self::orient_branches(&block.instructions, next);
+                let mut block_asm = String::new();
+                for (i, instr) in instructions.iter().enumerate() {
                     if matches!(instr, IRInstruction::Alloca(_, _)) {
                         continue;
                     }
-                    let asm = self.generate_instruction(instr);
-                    if !asm.is_empty() {
-                        output.push_str(&asm);
+                    block_asm.push_str(&self.line_directive(block.line_at(i)));
+                    block_asm.push_str(&self.generate_instruction(instr));
+                    if self.writes_narrow_memory(instr) {
+                        block_asm.push_str(&self.renormalize_addressed());
+                    }
+                }
+                if let Some(next) = next {
+                    let fallthrough = format!("    jmp .{}\n", next);
+                    if block_asm.ends_with(&fallthrough) {
+                        block_asm.truncate(block_asm.len() - fallthrough.len());
                     }
                 }
+                output.push_str(&format!(".{}:\n", block_label));
+                output.push_str(&block_asm);
             }
         }
 
@@ -244,6 +668,76 @@ impl X86Generator {
         output
     }
 
+    /// Разворачивает условный переход блока так, чтобы следующий по
+    /// размещению блок стал веткой «по умолчанию»
+    ///
+    /// `JUMP_IF_NOT c, A; JUMP B` при `A == next` превращается в
+    /// `JUMP_IF c, B; JUMP A`, а у `CMP_JMP` с `true`-веткой на `next`
+    /// меняются местами метки и инвертируется условие. Завершающий `jmp`
+    /// на следующий блок затем отбрасывается при выводе.
+    fn orient_branches(instructions: &[IRInstruction], next: Option<&str>) -> Vec<IRInstruction> {
+        let mut result = instructions.to_vec();
+        let next = match next {
+            Some(next) => next,
+            None => return result,
+        };
+        let is_next = |l: &Operand| matches!(l, Operand::Label(name) if name == next);
+        let n = result.len();
+
+        if let Some(IRInstruction::CmpJmp(l, r, tl, fl, cmp, jcc, is_float)) = result.last() {
+            let inverted = Self::invert_jcc(jcc).filter(|_| is_next(tl) && !is_next(fl));
+            if let Some(inverted) = inverted {
+                result[n - 1] = IRInstruction::CmpJmp(
+                    l.clone(),
+                    r.clone(),
+                    fl.clone(),
+                    tl.clone(),
+                    cmp.clone(),
+                    inverted.to_string(),
+                    *is_float,
+                );
+            }
+            return result;
+        }
+
+        if n >= 2 {
+            let flipped = match (&result[n - 2], &result[n - 1]) {
+                (IRInstruction::JumpIfNot(c, a), IRInstruction::Jump(b)) if is_next(a) => Some((
+                    IRInstruction::JumpIf(c.clone(), b.clone()),
+                    IRInstruction::Jump(a.clone()),
+                )),
+                (IRInstruction::JumpIf(c, a), IRInstruction::Jump(b)) if is_next(a) => Some((
+                    IRInstruction::JumpIfNot(c.clone(), b.clone()),
+                    IRInstruction::Jump(a.clone()),
+                )),
+                _ => None,
+            };
+            if let Some((cond, jump)) = flipped {
+                result[n - 2] = cond;
+                result[n - 1] = jump;
+            }
+        }
+        result
+    }
+
+    /// Противоположное условие перехода (для `ucomisd` флаги те же, что и
+    /// у беззнакового сравнения, поэтому инверсия точна и для NaN)
+    fn invert_jcc(jcc: &str) -> Option<&'static str> {
+        Some(match jcc {
+            "je" => "jne",
+            "jne" => "je",
+            "jl" => "jge",
+            "jge" => "jl",
+            "jg" => "jle",
+            "jle" => "jg",
+            "jb" => "jae",
+            "jae" => "jb",
+            "ja" => "jbe",
+            "jbe" => "ja",
+            _ => return None,
+        })
+    }
+
     fn collect_spill_slots(&mut self, instructions: &[IRInstruction]) {
         let mut spilled: HashMap<String, i32> = HashMap::new();
         for instr in instructions {
@@ -280,13 +774,22 @@ impl X86Generator {
         match instr {
             IRInstruction::Move(d, s) => self.gen_move(d, s),
             IRInstruction::Return(Some(v)) => {
-                let vs = self.op(v);
-                let vq = if vs.starts_with('[') && !vs.starts_with("[rel") {
-                    format!("qword {}", vs)
+                let mut o = if self.return_type == IRType::Float {
+                    self.load_xmm("xmm0", v)
                 } else {
-                    vs.clone()
+                    let vs = self.op(v);
+                    let vq = if vs.starts_with('[') && !vs.starts_with("[rel") {
+                        format!("qword {}", vs)
+                    } else {
+                        vs.clone()
+                    };
+                    let mut o = format!("    mov rax, {}\n", vq);
+                    let typ = self.operand_type(v);
+                    if typ.is_integer() && typ != self.return_type {
+                        o.push_str(&Self::narrow(&self.return_type, "rax"));
+                    }
+                    o
                 };
-                let mut o = format!("    mov rax, {}\n", vq);
                 for reg in self.used_callee_saved.iter().rev() {
                     o.push_str(&format!("    pop {}\n", reg.name()));
                 }
@@ -309,6 +812,9 @@ impl X86Generator {
             IRInstruction::And(d, l, r) => self.gen_binop("and", d, l, r),
             IRInstruction::Or(d, l, r) => self.gen_binop("or", d, l, r),
             IRInstruction::Xor(d, l, r) => self.gen_binop("xor", d, l, r),
+            IRInstruction::Shl(d, l, r) => self.gen_shift("sal", d, l, r),
+            IRInstruction::Shr(d, l, r) => self.gen_shift("shr", d, l, r),
+            IRInstruction::Sar(d, l, r) => self.gen_shift("sar", d, l, r),
             IRInstruction::Not(d, o) => {
                 let os = self.op(o);
                 let oq = if os.starts_with('[') && !os.starts_with("[rel") {
@@ -316,9 +822,15 @@ impl X86Generator {
                 } else {
                     os.clone()
                 };
+                // Инверсия знакового значения не нарушает расширение знака
+                let fix = match self.operand_type(d) {
+                    IRType::Int | IRType::Char => String::new(),
+                    _ => self.narrow_result(d, &[o], "rax"),
+                };
                 format!(
-                    "    mov rax, {}\n    not rax\n    mov {}, rax\n",
+                    "    mov rax, {}\n    not rax\n{}    mov {}, rax\n",
                     oq,
+                    fix,
                     self.op(d)
                 )
             }
@@ -329,11 +841,10 @@ impl X86Generator {
                 } else {
                     os.clone()
                 };
-                format!(
-                    "    mov rax, {}\n    neg rax\n    mov {}, rax\n",
-                    oq,
-                    self.op(d)
-                )
+                // У вещественного числа меняется только знаковый бит
+                let neg = if self.is_float(d) { "btc rax, 63" } else { "neg rax" };
+                let fix = self.narrow_result(d, &[o], "rax");
+                format!("    mov rax, {}\n    {}\n{}    mov {}, rax\n", oq, neg, fix, self.op(d))
             }
             IRInstruction::Jump(l) => format!("    jmp .{}\n", self.lbl(l)),
             IRInstruction::JumpIf(c, l) => self.gen_jump_if(c, l, false),
@@ -355,8 +866,12 @@ impl X86Generator {
                 };
                 if *is_float {
                     format!(
-                        "    movsd xmm0, {}\n    ucomisd xmm0, {}\n    {} .{}\n    jmp .{}\n",
-                        lq, rq, jcc_str, tls, fls
+                        "{}{}    ucomisd xmm0, xmm1\n    {} .{}\n    jmp .{}\n",
+                        self.load_xmm("xmm0", l),
+                        self.load_xmm("xmm1", r),
+                        jcc_str,
+                        tls,
+                        fls
                     )
                 } else {
                     if !ls.starts_with('[')
@@ -368,7 +883,9 @@ impl X86Generator {
                             "    mov rax, {}\n    cmp rax, {}\n    {} .{}\n    jmp .{}\n",
                             lq, rq, jcc_str, tls, fls
                         )
-                    } else if ls.starts_with('[') && rs.starts_with('[') {
+                    } else if (ls.starts_with('[') && rs.starts_with('['))
+                        || matches!(l, Operand::IntLiteral(_))
+                    {
                         format!(
                             "    mov rax, {}\n    cmp rax, {}\n    {} .{}\n    jmp .{}\n",
                             lq, rq, jcc_str, tls, fls
@@ -399,6 +916,14 @@ impl X86Generator {
             IRInstruction::CmpGeU(d, l, r) => self.gen_cmp_value("setae", d, l, r, false),
             IRInstruction::IntToFloat(d, s) => {
                 let ss = self.op(s);
+                if let Operand::IntLiteral(_) = s {
+                    // cvtsi2sd не принимает непосредственный операнд
+                    return format!(
+                        "    mov rax, {}\n    cvtsi2sd xmm0, rax\n    movq {}, xmm0\n",
+                        ss,
+                        self.op(d)
+                    );
+                }
                 let sq = if ss.starts_with('[') && !ss.starts_with("[rel") {
                     format!("qword {}", ss)
                 } else {
@@ -413,7 +938,8 @@ impl X86Generator {
                 } else {
                     ss.clone()
                 };
-                format!("    cvttsd2si rax, {}\n    mov {}, rax\n", sq, self.op(d))
+                let fix = Self::narrow(&self.operand_type(d), "rax");
+                format!("    cvttsd2si rax, {}\n{}    mov {}, rax\n", sq, fix, self.op(d))
             }
             IRInstruction::AddrOf(d, src) => {
                 let ds = self.op(d);
@@ -421,60 +947,41 @@ impl X86Generator {
                 format!("    lea rax, [{}]\n    mov {}, rax\n", addr, ds)
             }
             IRInstruction::ArrayLoad(dest, base, index) => {
-                let ds = self.op(dest);
-                let bs = self.op(base);
-                let istr = self.op(index);
-                if self.alloca_vars.contains(self.get_operand_name(base)) {
-                    format!(
-                        "    mov rax, {}\n    imul rax, 8\n    lea rdx, [{}]\n    add rax, rdx\n    mov rax, [rax]\n    mov {}, rax\n",
-                        istr, bs, ds
-                    )
-                } else {
-                    format!(
-                        "    mov rax, {}\n    imul rax, 8\n    add rax, {}\n    mov rax, [rax]\n    mov {}, rax\n",
-                        istr, bs, ds
-                    )
-                }
+                let elem = self.indexed_element(base);
+                let addr = self.element_addr(base, index, Self::slot_size(&elem));
+                let load = Self::sized_load(&elem, "[rax]");
+                format!("{}{}    mov {}, rax\n", addr, load, self.op(dest))
             }
             IRInstruction::ArrayStore(base, index, value) => {
-                let bs = self.op(base);
-                let istr = self.op(index);
-                let vs = self.op(value);
-                let (addr_part, val_part) = if self
-                    .alloca_vars
-                    .contains(self.get_operand_name(base))
-                {
-                    (
-                        format!(
-                            "    mov rax, {}\n    imul rax, 8\n    lea rdx, [{}]\n    add rax, rdx\n",
-                            istr, bs
-                        ),
-                        if vs.starts_with('[') {
-                            format!("    mov rcx, qword {}\n    mov qword [rax], rcx\n", vs)
-                        } else {
-                            format!("    mov qword [rax], {}\n", vs)
-                        },
-                    )
-                } else {
-                    (
-                        format!(
-                            "    mov rax, {}\n    imul rax, 8\n    add rax, {}\n",
-                            istr, bs
-                        ),
-                        if vs.starts_with('[') {
-                            format!("    mov rcx, qword {}\n    mov qword [rax], rcx\n", vs)
-                        } else {
-                            format!("    mov qword [rax], {}\n", vs)
-                        },
-                    )
-                };
-                format!("{}{}", addr_part, val_part)
+                let elem = self.indexed_element(base);
+                let addr = self.element_addr(base, index, Self::slot_size(&elem));
+                let load = self.load_rcx(value);
+                format!("{}{}{}", addr, load, Self::sized_store(&elem, "[rax]"))
+            }
+            IRInstruction::FieldLoad(dest, base, offset, typ) => {
+                let (setup, mem) = self.field_mem(base, *offset);
+                let load = Self::sized_load(typ, &mem);
+                format!("{}{}    mov {}, rax\n", setup, load, self.op(dest))
+            }
+            IRInstruction::FieldStore(base, offset, value, typ) => {
+                let (setup, mem) = self.field_mem(base, *offset);
+                let load = self.load_rcx(value);
+                let store = Self::sized_store(typ, &mem);
+                format!("{}{}{}", setup, load, store)
+            }
+            IRInstruction::Gep(dest, base, offset) => {
+                let (setup, mem) = self.field_mem(base, *offset);
+                format!("{}    lea rax, {}\n    mov {}, rax\n", setup, mem, self.op(dest))
             }
             IRInstruction::Label(l) => format!(".{}:\n", self.lbl(l)),
             IRInstruction::Call(d, f, a) => self.gen_call(d, f, a),
             IRInstruction::Load(d, a) => {
                 let as_ = self.op(a);
-                if as_.starts_with('[') {
+                if let Some(typ) = self.narrow_pointee(a) {
+                    let addr = Self::load_rax(&as_);
+                    let load = Self::sized_load(&typ, "[rax]");
+                    format!("{}{}    mov {}, rax\n", addr, load, self.op(d))
+                } else if as_.starts_with('[') {
                     format!(
                         "    mov rax, qword {}\n    mov rax, [rax]\n    mov {}, rax\n",
                         as_,
@@ -486,6 +993,11 @@ impl X86Generator {
             }
             IRInstruction::Store(a, s) => {
                 let as_ = self.op(a);
+                if let Some(typ) = self.narrow_pointee(a) {
+                    let addr = Self::load_rax(&as_);
+                    let load = self.load_rcx(s);
+                    return format!("{}{}{}", addr, load, Self::sized_store(&typ, "[rax]"));
+                }
                 let ss = self.op(s);
                 if as_.starts_with('[') {
                     if ss.starts_with('[') {
@@ -494,13 +1006,13 @@ impl X86Generator {
                             as_, ss
                         )
                     } else {
-                        format!("    mov rax, qword {}\n    mov [rax], {}\n", as_, ss)
+                        format!("    mov rax, qword {}\n    mov qword [rax], {}\n", as_, ss)
                     }
                 } else {
                     if ss.starts_with('[') {
                         format!("    mov rdx, qword {}\n    mov [{}], rdx\n", ss, as_)
                     } else {
-                        format!("    mov [{}], {}\n", as_, ss)
+                        format!("    mov qword [{}], {}\n", as_, ss)
                     }
                 }
             }
@@ -557,10 +1069,9 @@ impl X86Generator {
         let ds = self.op(d);
         let mut o = String::new();
         if flt {
-            o.push_str(&format!(
-                "    movsd xmm0, {}\n    ucomisd xmm0, {}\n",
-                ls, rs
-            ));
+            o.push_str(&self.load_xmm("xmm0", l));
+            o.push_str(&self.load_xmm("xmm1", r));
+            o.push_str("    ucomisd xmm0, xmm1\n");
         } else {
             let lq = if ls.starts_with('[') && !ls.starts_with("[rel") {
                 format!("qword {}", ls)
@@ -572,7 +1083,8 @@ impl X86Generator {
             } else {
                 rs.clone()
             };
-            if ls.starts_with('[') && rs.starts_with('[') {
+            let literal_left = matches!(l, Operand::IntLiteral(_));
+            if (ls.starts_with('[') && rs.starts_with('[')) || literal_left {
                 o.push_str(&format!("    mov rax, {}\n    cmp rax, {}\n", lq, rq));
             } else {
                 o.push_str(&format!("    cmp {}, {}\n", lq, rq));
@@ -585,6 +1097,32 @@ impl X86Generator {
         o
     }
 
+    /// Адрес поля структуры: подготовка базы в rax (если нужна) и операнд памяти
+    fn field_mem(&mut self, base: &Operand, offset: u32) -> (String, String) {
+        let name = match base {
+            Operand::Variable(n) | Operand::Temporary(n) => n.clone(),
+            _ => String::new(),
+        };
+        if self.global_vars.iter().any(|(n, _)| *n == name) {
+            return (String::new(), format!("[rel {}+{}]", name, offset));
+        }
+        if self.alloca_vars.contains(&name) {
+            let off = self.spill_offsets.get(&name).copied().unwrap_or(-8) + offset as i32;
+            return if off < 0 {
+                (String::new(), format!("[rbp-{}]", -off))
+            } else {
+                (String::new(), format!("[rbp+{}]", off))
+            };
+        }
+        let bs = self.op(base);
+        let setup = if bs.starts_with('[') {
+            format!("    mov rax, qword {}\n", bs)
+        } else {
+            format!("    mov rax, {}\n", bs)
+        };
+        (setup, format!("[rax+{}]", offset))
+    }
+
     fn addr_of(&mut self, op: &Operand) -> String {
         match op {
             Operand::Variable(n) | Operand::Temporary(n) => {
@@ -629,15 +1167,42 @@ impl X86Generator {
     }
 
     fn gen_move(&mut self, d: &Operand, s: &Operand) -> String {
+        let dt = self.operand_type(d);
+        if let Operand::IntLiteral(v) = s {
+            let truncated = Self::truncate_literal(&dt, *v);
+            if truncated != *v {
+                return self.gen_move(d, &Operand::IntLiteral(truncated));
+            }
+        }
         let ds = self.op(d);
         let ss = self.op(s);
+        let st = self.operand_type(s);
+        let fix = Self::narrow(&dt, "rax");
+        if st.is_integer() && st != dt && !fix.is_empty() && !matches!(s, Operand::IntLiteral(_)) {
+            let qword = |s: &str| {
+                if s.starts_with('[') {
+                    format!("qword {}", s)
+                } else {
+                    s.to_string()
+                }
+            };
+            return format!("    mov rax, {}\n{}    mov {}, rax\n", qword(&ss), fix, qword(&ds));
+        }
         if let Operand::FloatLiteral(v) = s {
             let lb = format!("L_flt{}", self.string_counter);
             self.string_counter += 1;
             self.string_literals
-                .push((format!("{}:", lb), format!("dq {}", v)));
+                .push((format!("{}:", lb), format!("dq {}", Self::float_data(*v))));
             return format!("    movsd xmm0, qword [{}]\n    movq {}, xmm0\n", lb, ds);
         }
+        if self.is_float(d) && st.is_integer() {
+            return format!("{}    movq {}, xmm0\n", self.load_xmm("xmm0", s), ds);
+        }
+        if ss.starts_with("rbp-") || ss.starts_with("rbp+") {
+            // Локальный массив: копируется адрес его первого элемента
+            let dq = if ds.starts_with('[') { format!("qword {}", ds) } else { ds.clone() };
+            return format!("    lea rax, [{}]\n    mov {}, rax\n", ss, dq);
+        }
         if !ds.starts_with('[') && !ss.starts_with('[') {
             return format!("    mov {}, {}\n", ds, ss);
         }
@@ -661,11 +1226,7 @@ impl X86Generator {
             return format!("    lea rax, [{}]\n    add rax, {}\n    mov {}, rax\n", ls, rq, dq);
         }
         
-        let is_float = matches!(l, Operand::FloatLiteral(_))
-            || matches!(r, Operand::FloatLiteral(_))
-            || ls.contains("xmm")
-            || rs.contains("xmm");
-        if is_float {
+        if self.is_float(d) {
             let op_f = match op {
                 "add" => "addsd",
                 "sub" => "subsd",
@@ -673,8 +1234,11 @@ impl X86Generator {
                 _ => op,
             };
             return format!(
-                "    movsd xmm0, {}\n    {} xmm0, {}\n    movq {}, xmm0\n",
-                ls, op_f, rs, ds
+                "{}{}    {} xmm0, xmm1\n    movq {}, xmm0\n",
+                self.load_xmm("xmm0", l),
+                self.load_xmm("xmm1", r),
+                op_f,
+                ds
             );
         }
         let lq = if ls.starts_with('[') && !ls.starts_with("[rel") {
@@ -687,14 +1251,19 @@ impl X86Generator {
         } else {
             rs.clone()
         };
+        // Побитовые операции над значениями int сохраняют расширение знака
+        let fix = |reg: &str| match (op, self.operand_type(d)) {
+            ("and" | "or" | "xor", IRType::Int) => String::new(),
+            _ => self.narrow_result(d, &[l, r], reg),
+        };
         if !ds.starts_with('[') && ls == ds {
-            return format!("    {} {}, {}\n", op, ds, rq);
+            return format!("    {} {}, {}\n{}", op, ds, rq, fix(&ds));
         }
         if !ds.starts_with('[')
             && rs == ds
             && (op == "add" || op == "imul" || op == "and" || op == "or" || op == "xor")
         {
-            return format!("    {} {}, {}\n", op, ds, lq);
+            return format!("    {} {}, {}\n{}", op, ds, lq, fix(&ds));
         }
         let dq = if ds.starts_with('[') {
             format!("qword {}", ds)
@@ -702,12 +1271,52 @@ impl X86Generator {
             ds.clone()
         };
         format!(
-            "    mov rax, {}\n    {} rax, {}\n    mov {}, rax\n",
-            lq, op, rq, dq
+            "    mov rax, {}\n    {} rax, {}\n{}    mov {}, rax\n",
+            lq,
+            op,
+            rq,
+            fix("rax"),
+            dq
         )
     }
 
+    /// Сдвиг: константная величина кодируется непосредственно,
+    /// переменная передается через `cl`
+    fn gen_shift(&mut self, op: &str, d: &Operand, l: &Operand, r: &Operand) -> String {
+        let qword = |s: String| {
+            if s.starts_with('[') && !s.starts_with("[rel") {
+                format!("qword {}", s)
+            } else {
+                s
+            }
+        };
+        let lq = qword(self.op(l));
+        let dq = qword(self.op(d));
+        let mut o = format!("    mov rax, {}\n", lq);
+        match r {
+            Operand::IntLiteral(n) => o.push_str(&format!("    {} rax, {}\n", op, n & 63)),
+            _ => {
+                let rq = qword(self.op(r));
+                o.push_str(&format!("    mov rcx, {}\n    {} rax, cl\n", rq, op));
+            }
+        }
+        if op != "sar" {
+            o.push_str(&self.narrow_result(d, &[l], "rax"));
+        }
+        o.push_str(&format!("    mov {}, rax\n", dq));
+        o
+    }
+
     fn gen_div(&mut self, d: &Operand, l: &Operand, r: &Operand, m: bool) -> String {
+        if !m && self.is_float(d) {
+            let ds = self.op(d);
+            return format!(
+                "{}{}    divsd xmm0, xmm1\n    movq {}, xmm0\n",
+                self.load_xmm("xmm0", l),
+                self.load_xmm("xmm1", r),
+                ds
+            );
+        }
         let ls = self.op(l);
         let rs = self.op(r);
         let ds = self.op(d);
@@ -722,41 +1331,98 @@ impl X86Generator {
         } else {
             rs.clone()
         };
+        // Операнды приводятся к общему типу; беззнаковое деление
+        // обнуляет rdx, знаковое расширяет знак rax в rdx
+        let typ = self.operand_type(d);
+        let (extend, div) = if typ.is_unsigned() {
+            ("xor edx, edx", "div")
+        } else {
+            ("cqo", "idiv")
+        };
         format!(
-            "    push r12\n    mov rax, {}\n    cdq\n    mov r12, {}\n    idiv r12\n    mov {}, {}\n    pop r12\n",
-            lq, rq, ds, rr
+            "    mov rax, {}\n{}    mov rcx, {}\n{}    {}\n    {} rcx\n    mov {}, {}\n",
+            lq,
+            self.narrow_result(d, &[l], "rax"),
+            rq,
+            self.narrow_result(d, &[r], "rcx"),
+            extend,
+            div,
+            ds,
+            rr
         )
     }
 
     fn gen_call(&mut self, d: &Operand, f: &Operand, a: &[Operand]) -> String {
         let mut o = String::new();
-        let regs = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
-        let arg_strs: Vec<String> = a.iter().map(|arg| self.op(arg)).collect();
         let fn_str = self.op(f);
         let dest_str = self.op(d);
-        let num_args = a.len();
-        let num_reg_args = num_args.min(6);
+
+        // Класс аргумента берется из сигнатуры, а для вариативной части
+        // (и неизвестных функций) - из типа самого операнда
+        let declared = self.signatures.get(&fn_str).cloned().unwrap_or_default();
+        let arg_types: Vec<IRType> = a
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| match declared.get(i) {
+                Some(typ) => typ.clone(),
+                None => self.operand_type(arg),
+            })
+            .collect();
+        let locations = CallingConvention::classify_args(&arg_types);
+        let stack_args: Vec<usize> = (0..a.len())
+            .filter(|&i| matches!(locations[i], ArgLocation::Stack(_)))
+            .collect();
+        let reg_args: Vec<usize> = (0..a.len())
+            .filter(|&i| !matches!(locations[i], ArgLocation::Stack(_)))
+            .collect();
+        let vector_count = locations
+            .iter()
+            .filter(|l| matches!(l, ArgLocation::Float(_)))
+            .count();
 
         o.push_str("    push rcx\n    push rdx\n    push rsi\n    push rdi\n");
         o.push_str("    push r8\n    push r9\n    push r10\n    push r11\n");
         o.push_str("    push r12\n    push rbx\n");
 
-        for i in (num_reg_args..num_args).rev() {
-            Self::push_arg(&mut o, &arg_strs[i]);
+        // rsp после пролога сдвинут на 8 * (число сохраненных регистров);
+        // вместе со стековыми аргументами он должен быть кратен 16 в точке call
+        let padding = (self.used_callee_saved.len() + stack_args.len()) % 2 == 1;
+        if padding {
+            o.push_str("    sub rsp, 8\n");
         }
-        for i in (0..num_reg_args).rev() {
-            Self::push_arg(&mut o, &arg_strs[i]);
+
+        for &i in stack_args.iter().rev() {
+            self.push_call_arg(&mut o, &a[i], arg_types[i] == IRType::Float);
+        }
+        for &i in reg_args.iter().rev() {
+            self.push_call_arg(&mut o, &a[i], arg_types[i] == IRType::Float);
         }
-        for i in 0..num_reg_args {
-            o.push_str(&format!("    pop {}\n", regs[i]));
+        for &i in &reg_args {
+            match locations[i] {
+                ArgLocation::Integer(reg) => o.push_str(&format!("    pop {}\n", reg)),
+                ArgLocation::Float(reg) => {
+                    o.push_str(&format!("    movsd {}, qword [rsp]\n    add rsp, 8\n", reg))
+                }
+                ArgLocation::Stack(_) => {}
+            }
         }
 
-        o.push_str("    xor eax, eax\n");
+        if vector_count > 0 {
+            o.push_str(&format!("    mov eax, {}\n", vector_count));
+        } else {
+            o.push_str("    xor eax, eax\n");
+        }
         o.push_str(&format!("    call {}\n", fn_str));
-        o.push_str("    mov r15, rax\n");
+        if self.is_float(d) {
+            o.push_str("    movq r15, xmm0\n");
+        } else {
+            o.push_str("    mov r15, rax\n");
+            o.push_str(&Self::narrow(&self.operand_type(d), "r15"));
+        }
 
-        if num_args > 6 {
-            o.push_str(&format!("    add rsp, {}\n", (num_args - 6) * 8));
+        let cleanup = stack_args.len() * 8 + if padding { 8 } else { 0 };
+        if cleanup > 0 {
+            o.push_str(&format!("    add rsp, {}\n", cleanup));
         }
 
         o.push_str("    pop rbx\n    pop r12\n");
@@ -768,6 +1434,18 @@ impl X86Generator {
         o
     }
 
+    /// Кладет аргумент вызова в стек; целое значение для вещественного
+    /// параметра предварительно преобразуется в `f64`
+    fn push_call_arg(&mut self, o: &mut String, arg: &Operand, as_float: bool) {
+        if as_float && !self.is_float(arg) {
+            o.push_str(&self.load_xmm("xmm0", arg));
+            o.push_str("    movq rax, xmm0\n    push rax\n");
+            return;
+        }
+        let arg_str = self.op(arg);
+        Self::push_arg(o, &arg_str);
+    }
+
     fn push_arg(o: &mut String, arg: &str) {
         if arg.starts_with("L_str") {
             o.push_str(&format!("    lea rax, [rel {}]\n", arg));
@@ -845,12 +1523,19 @@ impl X86Generator {
                 }
             }
             Operand::Label(n) => n.clone(),
-            Operand::IntLiteral(v) => v.to_string(),
+            Operand::IntLiteral(v) if i32::try_from(*v).is_ok() => v.to_string(),
+            Operand::IntLiteral(v) => {
+                // Непосредственный операнд x86-64 ограничен 32 битами
+                let lb = format!("L_int{}", self.string_counter);
+                self.string_counter += 1;
+                self.string_literals.push((format!("{}:", lb), format!("dq {}", v)));
+                format!("[{}]", lb)
+            }
             Operand::FloatLiteral(v) => {
                 let lb = format!("L_flt{}", self.string_counter);
                 self.string_counter += 1;
                 self.string_literals
-                    .push((format!("{}:", lb), format!("dq {}", v)));
+                    .push((format!("{}:", lb), format!("dq {}", Self::float_data(*v))));
                 format!("qword [{}]", lb)
             }
             Operand::BoolLiteral(v) => {
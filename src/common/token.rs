@@ -85,6 +85,21 @@ pub enum TokenKind {
     /// Диапазон: `[-2³¹, 2³¹-1]`
     IntLiteral(i32),
 
+    /// Целочисленный литерал с суффиксом `L`
+    ///
+    /// Диапазон: `[-2⁶³, 2⁶³-1]`
+    LongLiteral(i64),
+
+    /// Беззнаковый литерал с суффиксом `U`
+    ///
+    /// Диапазон: `[0, 2³²-1]`
+    UIntLiteral(u32),
+
+    /// Беззнаковый литерал с суффиксом `UL`
+    ///
+    /// Диапазон: `[0, 2⁶⁴-1]`
+    ULongLiteral(u64),
+
     /// Литерал с плавающей точкой
     ///
     /// 64-битное число двойной точности
@@ -95,6 +110,11 @@ pub enum TokenKind {
     /// Хранит содержимое строки без кавычек
     StringLiteral(String),
 
+    /// Символьный литерал
+    ///
+    /// Хранит символ после разбора escape-последовательности: `'a'`, `'\n'`
+    CharLiteral(char),
+
     /// Логический литерал
     ///
     /// `true` или `false`
@@ -127,6 +147,10 @@ pub enum TokenKind {
     Gt,
     /// Больше или равно: `>=`
     GtEq,
+    /// Сдвиг влево: `<<`
+    LtLt,
+    /// Сдвиг вправо: `>>`
+    GtGt,
     /// `->` - стрелка для указания возвращаемого типа
     Arrow,
 
@@ -137,6 +161,13 @@ pub enum TokenKind {
     /// Логическое НЕ: `!`
     Bang,
 
+    /// Побитовое ИЛИ: `|`
+    Pipe,
+    /// Побитовое исключающее ИЛИ: `^`
+    Caret,
+    /// Побитовое НЕ: `~`
+    Tilde,
+
     /// Присваивание: `=`
     Eq,
     /// Присваивание со сложением: `+=`
@@ -147,6 +178,18 @@ pub enum TokenKind {
     AsteriskEq,
     /// Присваивание с делением: `/=`
     SlashEq,
+    /// Присваивание с остатком: `%=`
+    PercentEq,
+    /// Присваивание с побитовым И: `&=`
+    AmpEq,
+    /// Присваивание с побитовым ИЛИ: `|=`
+    PipeEq,
+    /// Присваивание с исключающим ИЛИ: `^=`
+    CaretEq,
+    /// Присваивание со сдвигом влево: `<<=`
+    LtLtEq,
+    /// Присваивание со сдвигом вправо: `>>=`
+    GtGtEq,
 
     /// Левая круглая скобка: `(`
     LParen,
@@ -185,7 +228,21 @@ pub enum TokenKind {
     Ellipsis,
     /// `char` - символьный тип
     KwChar,
-    /// `&` - взятие адреса
+    /// `long` - 64-битный целый тип
+    KwLong,
+    /// `unsigned` - модификатор беззнакового типа
+    KwUnsigned,
+    /// `sizeof` - размер типа или выражения
+    KwSizeof,
+    /// `null` - нулевой указатель
+    KwNull,
+    /// `do` - цикл с постусловием
+    KwDo,
+    /// `enum` - объявление перечисления
+    KwEnum,
+    /// `?` - условный (тернарный) оператор
+    Question,
+    /// `&` - взятие адреса или побитовое И
     Amp,
 
     /// Маркер конца файла
@@ -310,8 +367,12 @@ impl Token {
         matches!(
             self.kind,
             TokenKind::IntLiteral(_)
+                | TokenKind::LongLiteral(_)
+                | TokenKind::UIntLiteral(_)
+                | TokenKind::ULongLiteral(_)
                 | TokenKind::FloatLiteral(_)
                 | TokenKind::StringLiteral(_)
+                | TokenKind::CharLiteral(_)
                 | TokenKind::BoolLiteral(_)
         )
     }
@@ -345,6 +406,18 @@ impl Token {
                 | TokenKind::MinusEq
                 | TokenKind::AsteriskEq
                 | TokenKind::SlashEq
+                | TokenKind::Amp
+                | TokenKind::Pipe
+                | TokenKind::Caret
+                | TokenKind::Tilde
+                | TokenKind::LtLt
+                | TokenKind::GtGt
+                | TokenKind::PercentEq
+                | TokenKind::AmpEq
+                | TokenKind::PipeEq
+                | TokenKind::CaretEq
+                | TokenKind::LtLtEq
+                | TokenKind::GtGtEq
         )
     }
 
@@ -430,6 +503,20 @@ impl Token {
         }
     }
 
+    /// Извлекает символ из токена-литерала.
+    ///
+    /// # Возвращает
+    ///
+    /// * `Some(char)` - если токен является `CharLiteral`
+    /// * `None` - в противном случае
+    pub fn as_char(&self) -> Option<char> {
+        if let TokenKind::CharLiteral(value) = self.kind {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
     /// Извлекает логическое значение из токена-литерала.
     ///
     /// # Возвращает
@@ -469,9 +556,13 @@ impl std::fmt::Display for Token {
 
         let literal_value = match &self.kind {
             TokenKind::IntLiteral(n) => format!(" {}", n),
+            TokenKind::LongLiteral(n) => format!(" {}", n),
+            TokenKind::UIntLiteral(n) => format!(" {}", n),
+            TokenKind::ULongLiteral(n) => format!(" {}", n),
             TokenKind::FloatLiteral(n) => format!(" {}", n),
             TokenKind::BoolLiteral(b) => format!(" {}", b),
             TokenKind::StringLiteral(s) => format!(" {}", s),
+            TokenKind::CharLiteral(c) => format!(" {}", c.escape_default()),
             _ => String::new(),
         };
 
@@ -509,8 +600,12 @@ pub fn token_type_name(kind: &TokenKind) -> &'static str {
         TokenKind::KwFn => "KW_FN",
         TokenKind::Identifier(_) => "IDENTIFIER",
         TokenKind::IntLiteral(_) => "INT_LITERAL",
+        TokenKind::LongLiteral(_) => "LONG_LITERAL",
+        TokenKind::UIntLiteral(_) => "UINT_LITERAL",
+        TokenKind::ULongLiteral(_) => "ULONG_LITERAL",
         TokenKind::FloatLiteral(_) => "FLOAT_LITERAL",
         TokenKind::StringLiteral(_) => "STRING_LITERAL",
+        TokenKind::CharLiteral(_) => "CHAR_LITERAL",
         TokenKind::BoolLiteral(_) => "BOOL_LITERAL",
         TokenKind::Plus => "PLUS",
         TokenKind::Minus => "MINUS",
@@ -554,7 +649,25 @@ pub fn token_type_name(kind: &TokenKind) -> &'static str {
         TokenKind::KwExtern => "KW_EXTERN",
         TokenKind::Ellipsis => "ELLIPSIS",
         TokenKind::KwChar => "KW_CHAR",
+        TokenKind::KwLong => "KW_LONG",
+        TokenKind::KwUnsigned => "KW_UNSIGNED",
+        TokenKind::KwSizeof => "KW_SIZEOF",
+        TokenKind::KwNull => "KW_NULL",
+        TokenKind::KwDo => "KW_DO",
+        TokenKind::KwEnum => "KW_ENUM",
+        TokenKind::Question => "QUESTION",
         TokenKind::Amp => "AMP",
+        TokenKind::Pipe => "PIPE",
+        TokenKind::Caret => "CARET",
+        TokenKind::Tilde => "TILDE",
+        TokenKind::LtLt => "LT_LT",
+        TokenKind::GtGt => "GT_GT",
+        TokenKind::PercentEq => "PERCENT_EQ",
+        TokenKind::AmpEq => "AMP_EQ",
+        TokenKind::PipeEq => "PIPE_EQ",
+        TokenKind::CaretEq => "CARET_EQ",
+        TokenKind::LtLtEq => "LT_LT_EQ",
+        TokenKind::GtGtEq => "GT_GT_EQ",
     }
 }
 
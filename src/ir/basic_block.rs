@@ -1,6 +1,7 @@
 //! Базовые блоки и представление функций в IR
 
-use super::ir_instructions::{IRInstruction, Operand};
+use super::ir_instructions::{IRInstruction, IRType, Operand, StructLayout, TypedOperand};
+use crate::preprocessor::SourceMap;
 use std::collections::HashMap;
 
 /// Базовый блок - последовательность инструкций без ветвлений
@@ -14,6 +15,8 @@ pub struct BasicBlock {
     pub predecessors: Vec<String>,
     /// Последователи в CFG
     pub successors: Vec<String>,
+    /// Таблица позиций: (индекс первой инструкции, строка исходного кода)
+    pub lines: Vec<(usize, usize)>,
 }
 
 impl BasicBlock {
@@ -24,6 +27,7 @@ impl BasicBlock {
             instructions: Vec::new(),
             predecessors: Vec::new(),
             successors: Vec::new(),
+            lines: Vec::new(),
         }
     }
 
@@ -32,6 +36,46 @@ impl BasicBlock {
         self.instructions.push(instr);
     }
 
+    /// Добавляет инструкцию, порожденную строкой `line`
+    pub fn add_instruction_at(&mut self, instr: IRInstruction, line: Option<usize>) {
+        if let Some(line) = line {
+            self.set_line(line);
+        }
+        self.add_instruction(instr);
+    }
+
+    /// Забирает инструкции блока вместе с их строками исходного кода.
+    /// Блок остается пустым; оптимизации заполняют его заново через
+    /// [`BasicBlock::add_instruction_at`], сохраняя таблицу позиций.
+    pub fn take_instructions(&mut self) -> Vec<(IRInstruction, Option<usize>)> {
+        let lines: Vec<Option<usize>> =
+            (0..self.instructions.len()).map(|i| self.line_at(i)).collect();
+        self.lines.clear();
+        std::mem::take(&mut self.instructions).into_iter().zip(lines).collect()
+    }
+
+    /// Отмечает, что следующие инструкции порождены строкой `line`
+    pub fn set_line(&mut self, line: usize) {
+        if line == 0 {
+            return;
+        }
+        let index = self.instructions.len();
+        match self.lines.last_mut() {
+            Some((_, last)) if *last == line => {}
+            Some((start, last)) if *start == index => *last = line,
+            _ => self.lines.push((index, line)),
+        }
+    }
+
+    /// Строка исходного кода для инструкции с индексом `index`
+    pub fn line_at(&self, index: usize) -> Option<usize> {
+        self.lines
+            .iter()
+            .take_while(|(start, _)| *start <= index)
+            .last()
+            .map(|(_, line)| *line)
+    }
+
     /// Добавляет предшественника
     pub fn add_predecessor(&mut self, pred: String) {
         if !self.predecessors.contains(&pred) {
@@ -58,6 +102,25 @@ impl BasicBlock {
     pub fn terminator(&self) -> Option<&IRInstruction> {
         self.instructions.last().filter(|i| i.is_terminator())
     }
+
+    /// Метки всех переходов блока в порядке появления
+    ///
+    /// Инструкции после безусловного перехода или возврата недостижимы
+    /// и не учитываются.
+    pub fn branch_targets(&self) -> Vec<String> {
+        let mut targets: Vec<String> = Vec::new();
+        for instr in &self.instructions {
+            for target in instr.branch_targets() {
+                if !targets.iter().any(|t| t == target) {
+                    targets.push(target.to_string());
+                }
+            }
+            if matches!(instr, IRInstruction::Jump(_) | IRInstruction::Return(_)) {
+                break;
+            }
+        }
+        targets
+    }
 }
 
 /// Функция в IR
@@ -66,11 +129,13 @@ pub struct FunctionIR {
     /// Имя функции
     pub name: String,
     /// Возвращаемый тип
-    pub return_type: String,
+    pub return_type: IRType,
     /// Параметры (имя, тип)
-    pub parameters: Vec<(String, String)>,
+    pub parameters: Vec<(String, IRType)>,
     /// Локальные переменные (имя, тип)
-    pub locals: Vec<(String, String)>,
+    pub locals: Vec<(String, IRType)>,
+    /// Временные значения с их типами
+    pub temporaries: Vec<TypedOperand>,
     /// Базовые блоки
     pub blocks: HashMap<String, BasicBlock>,
     /// Входной блок
@@ -81,12 +146,13 @@ pub struct FunctionIR {
 
 impl FunctionIR {
     /// Создает новую функцию
-    pub fn new(name: String, return_type: String) -> Self {
+    pub fn new(name: String, return_type: IRType) -> Self {
         Self {
             name,
             return_type,
             parameters: Vec::new(),
             locals: Vec::new(),
+            temporaries: Vec::new(),
             blocks: HashMap::new(),
             entry_block: String::new(),
             exit_blocks: Vec::new(),
@@ -119,6 +185,35 @@ impl FunctionIR {
             self.exit_blocks.push(label);
         }
     }
+
+    /// Задает тип временного значения
+    pub fn set_temp_type(&mut self, name: &str, typ: IRType) {
+        match self
+            .temporaries
+            .iter_mut()
+            .find(|t| matches!(&t.operand, Operand::Temporary(n) if n == name))
+        {
+            Some(temp) => temp.typ = typ,
+            None => self
+                .temporaries
+                .push(TypedOperand::new(Operand::Temporary(name.to_string()), typ)),
+        }
+    }
+
+    /// Тип параметра, локальной переменной или временного значения
+    pub fn value_type(&self, name: &str) -> Option<&IRType> {
+        self.parameters
+            .iter()
+            .chain(self.locals.iter())
+            .find(|(n, _)| n == name)
+            .map(|(_, t)| t)
+            .or_else(|| {
+                self.temporaries
+                    .iter()
+                    .find(|t| matches!(&t.operand, Operand::Temporary(n) if n == name))
+                    .map(|t| &t.typ)
+            })
+    }
 }
 
 /// Полная IR программа
@@ -126,8 +221,17 @@ impl FunctionIR {
 pub struct ProgramIR {
     /// Функции (хранятся в Vec для сохранения порядка)
     pub functions: Vec<FunctionIR>,
+    /// Внешние функции: только сигнатуры, без блоков
+    pub externs: Vec<FunctionIR>,
     /// Глобальные переменные
-    pub globals: Vec<(String, String)>,
+    pub globals: Vec<(String, IRType)>,
+    /// Константные инициализаторы глобальных переменных (по элементу на ячейку)
+    pub global_inits: HashMap<String, Vec<Operand>>,
+    /// Раскладки структур
+    pub structs: Vec<StructLayout>,
+    /// Карта препроцессора: строки в `BasicBlock::lines` относятся к
+    /// результату препроцессора и переводятся через нее в исходные
+    pub source_map: Option<SourceMap>,
 }
 
 impl ProgramIR {
@@ -135,7 +239,11 @@ impl ProgramIR {
     pub fn new() -> Self {
         Self {
             functions: Vec::new(),
+            externs: Vec::new(),
             globals: Vec::new(),
+            global_inits: HashMap::new(),
+            structs: Vec::new(),
+            source_map: None,
         }
     }
 
@@ -149,16 +257,42 @@ impl ProgramIR {
         self.functions.iter().find(|f| f.name == name)
     }
 
+    /// Сигнатура функции или внешней функции по имени
+    pub fn get_signature(&self, name: &str) -> Option<&FunctionIR> {
+        self.get_function(name)
+            .or_else(|| self.externs.iter().find(|f| f.name == name))
+    }
+
     /// Получает мутабельную функцию по имени
     pub fn get_function_mut(&mut self, name: &str) -> Option<&mut FunctionIR> {
         self.functions.iter_mut().find(|f| f.name == name)
     }
 
     /// Добавляет глобальную переменную
-    pub fn add_global(&mut self, name: String, typ: String) {
+    pub fn add_global(&mut self, name: String, typ: IRType) {
         self.globals.push((name, typ));
     }
 
+    /// Тип глобальной переменной
+    pub fn global_type(&self, name: &str) -> Option<&IRType> {
+        self.globals.iter().find(|(n, _)| n == name).map(|(_, t)| t)
+    }
+
+    /// Задает константный инициализатор глобальной переменной
+    pub fn set_global_init(&mut self, name: String, values: Vec<Operand>) {
+        self.global_inits.insert(name, values);
+    }
+
+    /// Добавляет раскладку структуры
+    pub fn add_struct(&mut self, layout: StructLayout) {
+        self.structs.push(layout);
+    }
+
+    /// Получает раскладку структуры по имени
+    pub fn get_struct(&self, name: &str) -> Option<&StructLayout> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
     /// Сортирует функции: main первой, остальные по алфавиту
     pub fn sort_functions_by_name(&mut self) {
         self.functions.sort_by(|a, b| {
@@ -213,6 +347,9 @@ impl IRStatistics {
                         IRInstruction::Or(_, _, _) => "OR",
                         IRInstruction::Not(_, _) => "NOT",
                         IRInstruction::Xor(_, _, _) => "XOR",
+                        IRInstruction::Shl(_, _, _) => "SHL",
+                        IRInstruction::Shr(_, _, _) => "SHR",
+                        IRInstruction::Sar(_, _, _) => "SAR",
                         IRInstruction::CmpEq(_, _, _) => "CMP_EQ",
                         IRInstruction::CmpNe(_, _, _) => "CMP_NE",
                         IRInstruction::CmpLt(_, _, _) => "CMP_LT",
@@ -246,6 +383,8 @@ impl IRStatistics {
                         IRInstruction::Move(_, _) => "MOVE",
                         IRInstruction::ArrayLoad(_, _, _) => "ARRAY_LOAD",
                         IRInstruction::ArrayStore(_, _, _) => "ARRAY_STORE",
+                        IRInstruction::FieldLoad(_, _, _, _) => "FIELD_LOAD",
+                        IRInstruction::FieldStore(_, _, _, _) => "FIELD_STORE",
                         IRInstruction::CmpJmp(_, _, _, _, _, _, _) => "CMP_JMP",
                         IRInstruction::AddrOf(_, _) => "ADDR_OF",
                     };
@@ -271,6 +410,9 @@ impl IRStatistics {
                         | IRInstruction::Or(dest, _, _)
                         | IRInstruction::Not(dest, _)
                         | IRInstruction::Xor(dest, _, _)
+                        | IRInstruction::Shl(dest, _, _)
+                        | IRInstruction::Shr(dest, _, _)
+                        | IRInstruction::Sar(dest, _, _)
                         | IRInstruction::CmpEq(dest, _, _)
                         | IRInstruction::CmpNe(dest, _, _)
                         | IRInstruction::CmpLt(dest, _, _)
@@ -295,7 +437,8 @@ impl IRStatistics {
                         | IRInstruction::Call(dest, _, _)
                         | IRInstruction::Move(dest, _)
                         | IRInstruction::ArrayLoad(dest, _, _)
-                        | IRInstruction::ArrayStore(_, _, dest) => {
+                        | IRInstruction::ArrayStore(_, _, dest)
+                        | IRInstruction::FieldLoad(dest, _, _, _) => {
                             if let Operand::Temporary(name) = dest {
                                 temp_set.insert(name.clone());
                             }
@@ -310,41 +310,23 @@ impl ControlFlowGraph {
 }
 
 /// Строит CFG для функции
+///
+/// Последователи собираются со всех переходов блока, а не только с
+/// последней инструкции: условный переход внутри блока (например, в
+/// `switch`) тоже порождает ребро.
 pub fn build_cfg(func_ir: &mut FunctionIR) {
     let blocks: Vec<String> = func_ir.blocks.keys().cloned().collect();
 
-    for block_label in blocks {
-        let terminator = if let Some(block) = func_ir.get_block(&block_label) {
-            block.terminator().cloned()
-        } else {
-            None
-        };
+    for block in func_ir.blocks.values_mut() {
+        block.predecessors.clear();
+        block.successors.clear();
+    }
 
-        let successors = match terminator {
-            Some(IRInstruction::Jump(label)) => {
-                if let Operand::Label(l) = label {
-                    vec![l]
-                } else {
-                    vec![]
-                }
-            }
-            Some(IRInstruction::JumpIf(_, label)) => {
-                if let Operand::Label(l) = label {
-                    vec![l]
-                } else {
-                    vec![]
-                }
-            }
-            Some(IRInstruction::JumpIfNot(_, label)) => {
-                if let Operand::Label(l) = label {
-                    vec![l]
-                } else {
-                    vec![]
-                }
-            }
-            Some(IRInstruction::Return(_)) => vec![],
-            _ => vec![],
-        };
+    for block_label in blocks {
+        let successors = func_ir
+            .get_block(&block_label)
+            .map(|b| b.branch_targets())
+            .unwrap_or_default();
 
         for succ in successors {
             if func_ir.get_block(&succ).is_some() {
@@ -360,3 +342,149 @@ pub fn build_cfg(func_ir: &mut FunctionIR) {
         }
     }
 }
+
+/// Порядок размещения блоков: обратный постпорядок обхода от входного блока
+///
+/// Последователи обходятся так, чтобы сразу за блоком шел тот, в который
+/// управление переходит «по умолчанию»: безусловная цель после условного
+/// перехода и ветка `true` у `CMP_JMP`. Недостижимые блоки в порядок не входят.
+pub fn reverse_postorder(func_ir: &FunctionIR) -> Vec<String> {
+    let successors = |label: &str| -> Vec<String> {
+        let block = match func_ir.get_block(label) {
+            Some(block) => block,
+            None => return Vec::new(),
+        };
+        let mut targets: Vec<String> = block
+            .branch_targets()
+            .into_iter()
+            .filter(|t| func_ir.blocks.contains_key(t))
+            .collect();
+        if let Some(IRInstruction::CmpJmp(..)) = block.terminator() {
+            targets.reverse();
+        }
+        targets
+    };
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut postorder: Vec<String> = Vec::new();
+
+    if func_ir.blocks.contains_key(&func_ir.entry_block) {
+        let entry = func_ir.entry_block.clone();
+        visited.insert(entry.clone());
+        let mut stack: Vec<(String, Vec<String>, usize)> =
+            vec![(entry.clone(), successors(&entry), 0)];
+
+        while let Some((label, succs, idx)) = stack.last_mut() {
+            if *idx < succs.len() {
+                let next = succs[*idx].clone();
+                *idx += 1;
+                if visited.insert(next.clone()) {
+                    let next_succs = successors(&next);
+                    stack.push((next, next_succs, 0));
+                }
+            } else {
+                postorder.push(label.clone());
+                stack.pop();
+            }
+        }
+    }
+
+    postorder.reverse();
+    postorder
+}
+
+/// Множества живых переменных на входе и выходе каждого базового блока
+#[derive(Debug, Clone, Default)]
+pub struct Liveness {
+    /// Переменные, живые на входе в блок
+    pub live_in: HashMap<String, HashSet<String>>,
+    /// Переменные, живые на выходе из блока
+    pub live_out: HashMap<String, HashSet<String>>,
+}
+
+impl Liveness {
+    /// Живые переменные на входе в блок
+    pub fn live_in(&self, block: &str) -> Option<&HashSet<String>> {
+        self.live_in.get(block)
+    }
+
+    /// Живые переменные на выходе из блока
+    pub fn live_out(&self, block: &str) -> Option<&HashSet<String>> {
+        self.live_out.get(block)
+    }
+}
+
+fn value_name(op: &Operand) -> Option<&str> {
+    match op {
+        Operand::Temporary(name) | Operand::Variable(name) => Some(name.as_str()),
+        _ => None,
+    }
+}
+
+/// Анализ живых переменных (обратный поток данных)
+///
+/// Использует последователей, построенных `build_cfg`:
+/// `out[B] = ∪ in[S]` по всем последователям, `in[B] = use[B] ∪ (out[B] - def[B])`.
+/// Итерации повторяются до неподвижной точки, поэтому переменные,
+/// используемые в заголовке цикла, остаются живыми вдоль обратного ребра.
+pub fn compute_liveness(func_ir: &FunctionIR) -> Liveness {
+    let blocks: Vec<String> = func_ir.blocks.keys().cloned().collect();
+
+    let mut use_set: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut def_set: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for label in &blocks {
+        let mut block_use = HashSet::new();
+        let mut block_def = HashSet::new();
+
+        if let Some(block) = func_ir.get_block(label) {
+            for instr in &block.instructions {
+                for name in instr.operands().into_iter().filter_map(value_name) {
+                    if !block_def.contains(name) {
+                        block_use.insert(name.to_string());
+                    }
+                }
+                if let Some(name) = instr.dest().and_then(value_name) {
+                    block_def.insert(name.to_string());
+                }
+            }
+        }
+
+        use_set.insert(label.clone(), block_use);
+        def_set.insert(label.clone(), block_def);
+    }
+
+    let mut liveness = Liveness::default();
+    for label in &blocks {
+        liveness.live_in.insert(label.clone(), HashSet::new());
+        liveness.live_out.insert(label.clone(), HashSet::new());
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for label in &blocks {
+            let mut out = HashSet::new();
+            if let Some(block) = func_ir.get_block(label) {
+                for succ in &block.successors {
+                    if let Some(succ_in) = liveness.live_in.get(succ) {
+                        out.extend(succ_in.iter().cloned());
+                    }
+                }
+            }
+
+            let mut new_in: HashSet<String> =
+                out.difference(&def_set[label]).cloned().collect();
+            new_in.extend(use_set[label].iter().cloned());
+
+            if new_in != liveness.live_in[label] || out != liveness.live_out[label] {
+                liveness.live_in.insert(label.clone(), new_in);
+                liveness.live_out.insert(label.clone(), out);
+                changed = true;
+            }
+        }
+    }
+
+    liveness
+}
@@ -4,7 +4,7 @@
 //! уменьшая накладные расходы на call/ret.
 
 use super::basic_block::{BasicBlock, FunctionIR, ProgramIR};
-use super::ir_instructions::{IRInstruction, Operand};
+use super::ir_instructions::{IRInstruction, IRType, Operand};
 use std::collections::{HashMap, HashSet};
 
 /// Статистика инлайнинга
@@ -237,6 +237,17 @@ impl InlineOptimizer {
             name_map.insert(local_name.clone(), new_name);
         }
 
+        // Временные значения вызываемой функции становятся локальными
+        // переменными вызывающей с теми же типами, чтобы не пересечься с ее `tN`
+        let mut temp_locals: Vec<(String, IRType)> = Vec::new();
+        for temp in &callee.temporaries {
+            if let Operand::Temporary(temp_name) = &temp.operand {
+                let new_name = self.rename_var(temp_name);
+                name_map.insert(temp_name.clone(), new_name.clone());
+                temp_locals.push((new_name, temp.typ.clone()));
+            }
+        }
+
         let suffix = self.rename_counter;
         let after_call_label = format!("__after_inline_{}", suffix);
         let return_label = format!("__inline_return_{}", suffix);
@@ -251,24 +262,26 @@ impl InlineOptimizer {
         let mut after_call = BasicBlock::new(after_call_label.clone());
 
         for (i, instr) in original_block.instructions.iter().enumerate() {
+            let line = original_block.line_at(i);
             if i < call_instr_idx {
                 if !matches!(instr, IRInstruction::Param(_, _)) {
-                    before_call.add_instruction(instr.clone());
+                    before_call.add_instruction_at(instr.clone(), line);
                 }
             } else if i > call_instr_idx {
-                after_call.add_instruction(instr.clone());
+                after_call.add_instruction_at(instr.clone(), line);
             }
         }
+        let call_line = original_block.line_at(call_instr_idx);
 
-        for (i, (param_name, _)) in callee.parameters.iter().enumerate() {
+        for (i, (param_name, param_type)) in callee.parameters.iter().enumerate() {
             if i < call_args.len() {
                 let temp_name = self.rename_var(param_name);
                 name_map.insert(param_name.clone(), temp_name.clone());
-                caller.locals.push((temp_name.clone(), "int".to_string()));
-                before_call.add_instruction(IRInstruction::Move(
-                    Operand::Variable(temp_name),
-                    call_args[i].clone(),
-                ));
+                caller.locals.push((temp_name.clone(), param_type.clone()));
+                before_call.add_instruction_at(
+                    IRInstruction::Move(Operand::Variable(temp_name), call_args[i].clone()),
+                    call_line,
+                );
             }
         }
 
@@ -280,7 +293,7 @@ impl InlineOptimizer {
             let new_label = block_name_map.get(orig_label).unwrap().clone();
             let mut new_block = BasicBlock::new(new_label);
 
-            for instr in &block.instructions {
+            for (i, instr) in block.instructions.iter().enumerate() {
                 let new_instr = self.rename_instruction(
                     instr,
                     &name_map,
@@ -289,7 +302,7 @@ impl InlineOptimizer {
                     &after_call_label,
                     &return_label,
                 );
-                new_block.add_instruction(new_instr);
+                new_block.add_instruction_at(new_instr, block.line_at(i));
             }
 
             if !new_block.is_terminator() {
@@ -313,6 +326,7 @@ impl InlineOptimizer {
                 caller.locals.push((new_name, local_type.clone()));
             }
         }
+        caller.locals.extend(temp_locals);
 
         caller.blocks.remove(block_label);
         caller.add_block(before_call);
@@ -419,6 +433,15 @@ impl InlineOptimizer {
             IRInstruction::Xor(d, l, r) => {
                 IRInstruction::Xor(rename_op(d), rename_op(l), rename_op(r))
             }
+            IRInstruction::Shl(d, l, r) => {
+                IRInstruction::Shl(rename_op(d), rename_op(l), rename_op(r))
+            }
+            IRInstruction::Shr(d, l, r) => {
+                IRInstruction::Shr(rename_op(d), rename_op(l), rename_op(r))
+            }
+            IRInstruction::Sar(d, l, r) => {
+                IRInstruction::Sar(rename_op(d), rename_op(l), rename_op(r))
+            }
             IRInstruction::Load(d, a) => IRInstruction::Load(rename_op(d), rename_op(a)),
             IRInstruction::Store(a, s) => IRInstruction::Store(rename_op(a), rename_op(s)),
             IRInstruction::ArrayLoad(d, b, i) => {
@@ -427,6 +450,12 @@ impl InlineOptimizer {
             IRInstruction::ArrayStore(b, i, v) => {
                 IRInstruction::ArrayStore(rename_op(b), rename_op(i), rename_op(v))
             }
+            IRInstruction::FieldLoad(d, b, off, t) => {
+                IRInstruction::FieldLoad(rename_op(d), rename_op(b), *off, t.clone())
+            }
+            IRInstruction::FieldStore(b, off, v, t) => {
+                IRInstruction::FieldStore(rename_op(b), *off, rename_op(v), t.clone())
+            }
             IRInstruction::Call(d, f, args) => {
                 let new_args: Vec<Operand> = args.iter().map(|a| rename_op(a)).collect();
                 IRInstruction::Call(rename_op(d), f.clone(), new_args)
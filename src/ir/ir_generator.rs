@@ -1,22 +1,35 @@
 //! Генератор IR из декорированного AST
 
 use super::basic_block::{BasicBlock, FunctionIR, ProgramIR};
-use super::ir_instructions::{IRInstruction, Operand};
+use super::ir_instructions::{IRInstruction, IRType, Operand, StructLayout};
+use crate::lexer::Scanner;
 use crate::parser::ast::*;
-use crate::semantic::symbol_table::SymbolTable;
+use crate::semantic::symbol_table::{SymbolKind, SymbolTable};
+use crate::semantic::type_system::{BinaryOpType, TypeChecker, UnaryOpType};
 use std::collections::HashMap;
 
+/// Скрытый параметр функции, возвращающей структуру: адрес буфера,
+/// в который копируется результат
+pub const SRET_PARAM: &str = "__sret";
+
 pub struct IRGenerator {
     pub symbol_table: SymbolTable,
     program: ProgramIR,
     current_function: Option<String>,
     temp_counter: usize,
     label_counter: usize,
+    current_line: usize,
     var_to_temp: HashMap<String, String>,
-    current_locals: Vec<(String, String)>,
+    current_locals: Vec<(String, IRType)>,
+    current_params: Vec<(String, IRType)>,
     function_counter: usize,
     break_labels: Vec<String>,
     continue_labels: Vec<String>,
+    struct_layouts: HashMap<String, StructLayout>,
+    struct_vars: HashMap<String, String>,
+    global_struct_vars: HashMap<String, String>,
+    function_types: HashMap<String, IRType>,
+    pinned_types: HashMap<String, IRType>,
 }
 
 impl IRGenerator {
@@ -27,11 +40,18 @@ impl IRGenerator {
             current_function: None,
             temp_counter: 0,
             label_counter: 0,
+            current_line: 0,
             var_to_temp: HashMap::new(),
             current_locals: Vec::new(),
+            current_params: Vec::new(),
             function_counter: 0,
             break_labels: Vec::new(),
             continue_labels: Vec::new(),
+            struct_layouts: HashMap::new(),
+            struct_vars: HashMap::new(),
+            global_struct_vars: HashMap::new(),
+            function_types: HashMap::new(),
+            pinned_types: HashMap::new(),
         }
     }
 
@@ -58,14 +78,50 @@ impl IRGenerator {
         let mut global_vars = Vec::new();
         for decl in program.declarations {
             match decl {
-                Declaration::Function(func) => func_list.push(func),
-                Declaration::Struct(_) => {}
+                Declaration::Function(func) => {
+                    let ret = self.ast_ir_type(&func.return_type);
+                    self.function_types.insert(func.name.clone(), ret);
+                    func_list.push(func);
+                }
+                Declaration::Struct(s) => {
+                    let layout = self.build_struct_layout(&s);
+                    self.struct_layouts.insert(layout.name.clone(), layout.clone());
+                    self.program.add_struct(layout);
+                }
                 Declaration::Variable(var) => global_vars.push(var),
-                Declaration::ExternFunction(_) => {}
+                Declaration::Enum(_) => {}
+                Declaration::ExternFunction(ext) => {
+                    let ret = self.ast_ir_type(&ext.return_type);
+                    self.function_types.insert(ext.name.clone(), ret.clone());
+                    let mut decl = FunctionIR::new(ext.name.clone(), ret);
+                    for param in &ext.parameters {
+                        let param_type = self.ast_ir_type(&param.param_type);
+                        decl.parameters.push((param.name.clone(), param_type));
+                    }
+                    self.program.externs.push(decl);
+                }
             }
         }
         for var in global_vars {
-            self.program.add_global(var.name, var.var_type.to_string());
+            if let crate::parser::ast::Type::Struct(name) = &var.var_type {
+                self.global_struct_vars.insert(var.name.clone(), name.clone());
+            }
+            let declared = self.ast_ir_type(&var.var_type);
+            let declared = Self::infer_array_length(declared, var.initializer.as_deref());
+            let values = var
+                .initializer
+                .as_deref()
+                .and_then(|init| self.const_initializer(init, &declared));
+            let typ = match (&var.var_type, values.as_deref()) {
+                (crate::parser::ast::Type::Inferred, Some([value])) => {
+                    Self::literal_type(value).unwrap_or(IRType::Int)
+                }
+                _ => declared,
+            };
+            self.program.add_global(var.name.clone(), typ);
+            if let Some(values) = values {
+                self.program.set_global_init(var.name, values);
+            }
         }
         func_list.sort_by(|a, b| {
             if a.name == "main" {
@@ -84,18 +140,30 @@ impl IRGenerator {
     }
 
     fn generate_function(&mut self, func: &FunctionDecl) {
-        let return_type = func.return_type.to_string();
+        let return_type = self.ast_ir_type(&func.return_type);
         let mut func_ir = FunctionIR::new(func.name.clone(), return_type.clone());
+        self.struct_vars.clear();
+        // Структура возвращается через скрытый указатель на буфер вызывающего
+        if let IRType::Struct(..) = return_type {
+            let sret = IRType::Pointer(Box::new(return_type.clone()));
+            func_ir.parameters.push((SRET_PARAM.to_string(), sret));
+        }
         for param in &func.parameters {
-            let param_type = param.param_type.to_string();
+            let param_type = self.ast_ir_type(&param.param_type);
             func_ir.parameters.push((param.name.clone(), param_type));
+            if let crate::parser::ast::Type::Struct(name) = &param.param_type {
+                self.struct_vars.insert(param.name.clone(), name.clone());
+            }
         }
         self.current_function = Some(func.name.clone());
         self.temp_counter = 0;
         self.label_counter = 0;
+        self.current_line = func.node.line;
         self.function_counter += 1;
         self.var_to_temp.clear();
         self.current_locals.clear();
+        self.current_params = func_ir.parameters.clone();
+        self.pinned_types.clear();
         self.break_labels.clear();
         self.continue_labels.clear();
         let mut all_blocks: Vec<BasicBlock> = Vec::new();
@@ -105,7 +173,7 @@ impl IRGenerator {
             self.generate_statement(stmt, &mut current_block, &mut all_blocks);
         }
         if !current_block.is_terminator() {
-            if return_type == "void" {
+            if return_type == IRType::Void {
                 current_block.add_instruction(IRInstruction::Return(None));
             } else {
                 current_block.add_instruction(IRInstruction::Return(Some(Operand::IntLiteral(0))));
@@ -120,15 +188,602 @@ impl IRGenerator {
             func_ir.add_block(block.clone());
         }
         super::control_flow::build_cfg(&mut func_ir);
+        self.type_temporaries(&mut func_ir);
         self.program.add_function(func_ir);
         self.current_function = None;
     }
 
+    /// Значения константного инициализатора глобальной переменной,
+    /// приведенные к типу элемента; вложенные списки раскладываются по
+    /// слотам массива построчно, строка в массиве `char` - по байтам
+    fn const_initializer(&self, init: &Expression, typ: &IRType) -> Option<Vec<Operand>> {
+        let mut element = typ;
+        while let IRType::Array(inner, _) = element {
+            element = inner;
+        }
+        let element = Self::semantic_type(element);
+        match init {
+            Expression::ArrayInitializer(arr) => {
+                let mut slots = Vec::new();
+                Self::flatten_initializer(arr, typ, 0, &mut slots);
+                let mut values = Vec::new();
+                for (slot, elem) in slots {
+                    if values.len() < slot {
+                        values.resize(slot, Operand::IntLiteral(0));
+                    }
+                    values.push(Self::const_as(self.const_operand(elem)?, element.as_ref())?);
+                }
+                Some(values)
+            }
+            _ => match (typ, Self::string_bytes(init, typ)) {
+                (IRType::Array(..), Some(bytes)) => Some(bytes),
+                _ => Self::const_as(self.const_operand(init)?, element.as_ref()).map(|v| vec![v]),
+            },
+        }
+    }
+
+    /// Байты строкового литерала, инициализирующего массив `char`, с
+    /// завершающим нулем, если он помещается в массив
+    fn string_bytes(init: &Expression, typ: &IRType) -> Option<Vec<Operand>> {
+        let (value, len) = match (init, typ) {
+            (Expression::Literal(l), IRType::Array(inner, len)) if **inner == IRType::Char => {
+                match &l.value {
+                    LiteralValue::String(s) => (s, *len),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let bytes = Scanner::string_bytes(value).into_iter().chain([0]).take(len);
+        Some(bytes.map(|b| Operand::IntLiteral(b as i8 as i64)).collect())
+    }
+
+    /// Приводит свернутую константу к типу; строки и указатели не меняются
+    fn const_as(
+        value: Operand,
+        typ: Option<&crate::semantic::type_system::Type>,
+    ) -> Option<Operand> {
+        use crate::semantic::type_system::Type;
+        match typ {
+            Some(t) if t.is_integer() || matches!(t, Type::Float | Type::Bool) => {
+                Self::const_cast(value, t)
+            }
+            _ => Some(value),
+        }
+    }
+
+    /// Вычисляет константное выражение на этапе компиляции; целые
+    /// операции выполняются в типе выражения с его знаковостью
+    fn const_operand(&self, expr: &Expression) -> Option<Operand> {
+        let typ = self.get_expression_type(expr);
+        match expr {
+            Expression::Literal(l) => Some(self.generate_literal(l)),
+            Expression::Sizeof(s) => Some(Operand::IntLiteral(self.sizeof_value(s) as i64)),
+            Expression::Grouped(g) => self.const_operand(&g.expr),
+            Expression::Identifier(i) => self.enum_constant(&i.name).map(Operand::IntLiteral),
+            Expression::Conditional(c) => {
+                let taken = match self.const_operand(&c.condition)? {
+                    Operand::IntLiteral(v) => v != 0,
+                    Operand::BoolLiteral(v) => v,
+                    Operand::FloatLiteral(v) => v != 0.0,
+                    _ => return None,
+                };
+                let branch = if taken { &c.then_expr } else { &c.else_expr };
+                Self::const_as(self.const_operand(branch)?, typ.as_ref())
+            }
+            Expression::Unary(u) => match (u.operator, self.const_operand(&u.operand)?) {
+                (UnaryOp::Plus, v) => Self::const_as(v, typ.as_ref()),
+                (UnaryOp::Neg, Operand::IntLiteral(v)) => {
+                    Self::const_as(Operand::IntLiteral(v.wrapping_neg()), typ.as_ref())
+                }
+                (UnaryOp::Neg, Operand::FloatLiteral(v)) => Some(Operand::FloatLiteral(-v)),
+                (UnaryOp::Not, Operand::BoolLiteral(v)) => Some(Operand::BoolLiteral(!v)),
+                (UnaryOp::Not, Operand::IntLiteral(v)) => Some(Operand::BoolLiteral(v == 0)),
+                (UnaryOp::BitNot, Operand::IntLiteral(v)) => {
+                    Self::const_as(Operand::IntLiteral(!v), typ.as_ref())
+                }
+                _ => None,
+            },
+            Expression::Cast(c) => {
+                let value = self.const_operand(&c.expr)?;
+                let target = crate::semantic::type_system::Type::from_ast(&c.target_type);
+                Self::const_cast(value, &target)
+            }
+            Expression::Binary(b) => {
+                let as_int = |op: Operand| match op {
+                    Operand::BoolLiteral(v) => Operand::IntLiteral(v as i64),
+                    op => op,
+                };
+                let l = as_int(self.const_operand(&b.left)?);
+                let r = as_int(self.const_operand(&b.right)?);
+                match (l, r) {
+                    (Operand::IntLiteral(l), Operand::IntLiteral(r)) => {
+                        // Сравнение идет в общем типе операндов, арифметика -
+                        // в типе результата
+                        let operands = self
+                            .get_expression_type(&b.left)
+                            .zip(self.get_expression_type(&b.right))
+                            .and_then(|(lt, rt)| TypeChecker::new().common_numeric_type(&lt, &rt));
+                        let unsigned = match b.operator {
+                            BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                                operands.is_some_and(|t| t.is_unsigned())
+                            }
+                            _ => typ.as_ref().is_some_and(|t| t.is_unsigned()),
+                        };
+                        let (ul, ur) = (l as u64, r as u64);
+                        let order = if unsigned { ul.cmp(&ur) } else { l.cmp(&r) };
+                        let flag = |v: bool| Some(Operand::IntLiteral(v as i64));
+                        let v = match b.operator {
+                            BinaryOp::Add => l.wrapping_add(r),
+                            BinaryOp::Sub => l.wrapping_sub(r),
+                            BinaryOp::Mul => l.wrapping_mul(r),
+                            BinaryOp::Div if r != 0 && unsigned => (ul / ur) as i64,
+                            BinaryOp::Mod if r != 0 && unsigned => (ul % ur) as i64,
+                            BinaryOp::Div if r != 0 => l.wrapping_div(r),
+                            BinaryOp::Mod if r != 0 => l.wrapping_rem(r),
+                            BinaryOp::BitAnd => l & r,
+                            BinaryOp::BitOr => l | r,
+                            BinaryOp::BitXor => l ^ r,
+                            BinaryOp::Shl => l.wrapping_shl(r as u32),
+                            BinaryOp::Shr if unsigned => ul.wrapping_shr(r as u32) as i64,
+                            BinaryOp::Shr => l.wrapping_shr(r as u32),
+                            BinaryOp::Eq => return flag(l == r),
+                            BinaryOp::Ne => return flag(l != r),
+                            BinaryOp::Lt => return flag(order.is_lt()),
+                            BinaryOp::Le => return flag(order.is_le()),
+                            BinaryOp::Gt => return flag(order.is_gt()),
+                            BinaryOp::Ge => return flag(order.is_ge()),
+                            BinaryOp::And => return flag(l != 0 && r != 0),
+                            BinaryOp::Or => return flag(l != 0 || r != 0),
+                            _ => return None,
+                        };
+                        Self::const_as(Operand::IntLiteral(v), typ.as_ref())
+                    }
+                    (l, r) => {
+                        let as_float = |op: Operand| match op {
+                            Operand::IntLiteral(v) => Some(v as f64),
+                            Operand::FloatLiteral(v) => Some(v),
+                            _ => None,
+                        };
+                        let (l, r) = (as_float(l)?, as_float(r)?);
+                        let v = match b.operator {
+                            BinaryOp::Add => l + r,
+                            BinaryOp::Sub => l - r,
+                            BinaryOp::Mul => l * r,
+                            BinaryOp::Div => l / r,
+                            BinaryOp::Eq => return Some(Operand::IntLiteral((l == r) as i64)),
+                            BinaryOp::Ne => return Some(Operand::IntLiteral((l != r) as i64)),
+                            BinaryOp::Lt => return Some(Operand::IntLiteral((l < r) as i64)),
+                            BinaryOp::Le => return Some(Operand::IntLiteral((l <= r) as i64)),
+                            BinaryOp::Gt => return Some(Operand::IntLiteral((l > r) as i64)),
+                            BinaryOp::Ge => return Some(Operand::IntLiteral((l >= r) as i64)),
+                            _ => return None,
+                        };
+                        Some(Operand::FloatLiteral(v))
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Приведение константы при компиляции; сужение целых отбрасывает
+    /// старшие биты, как `narrow` в кодогенераторе
+    fn const_cast(value: Operand, to: &crate::semantic::type_system::Type) -> Option<Operand> {
+        use crate::semantic::type_system::Type;
+        let int = match value {
+            Operand::FloatLiteral(v) if matches!(to, Type::Float) => {
+                return Some(Operand::FloatLiteral(v))
+            }
+            Operand::FloatLiteral(v) if matches!(to, Type::Bool) => {
+                return Some(Operand::BoolLiteral(v != 0.0))
+            }
+            Operand::FloatLiteral(v) => v as i64,
+            Operand::IntLiteral(v) => v,
+            Operand::BoolLiteral(v) => v as i64,
+            Operand::StringLiteral(_) if to.is_pointer() => return Some(value),
+            _ => return None,
+        };
+        let value = match to {
+            Type::Float => Operand::FloatLiteral(int as f64),
+            Type::Bool => Operand::BoolLiteral(int != 0),
+            Type::Int | Type::Enum(_) => Operand::IntLiteral(int as i32 as i64),
+            Type::UInt => Operand::IntLiteral(int as u32 as i64),
+            Type::Char => Operand::IntLiteral(int as i8 as i64),
+            Type::Long | Type::ULong | Type::Pointer(_) => Operand::IntLiteral(int),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Строит раскладку структуры по порядку объявления полей
+    fn build_struct_layout(&self, decl: &StructDecl) -> StructLayout {
+        let mut field_types = HashMap::new();
+        let mut field_order = Vec::new();
+        for field in &decl.fields {
+            if !field_types.contains_key(&field.name) {
+                field_order.push(field.name.clone());
+                field_types.insert(
+                    field.name.clone(),
+                    crate::semantic::type_system::Type::from_ast(&field.var_type),
+                );
+            }
+        }
+        let (offsets, size, alignment) =
+            self.symbol_table.struct_layout(&field_types, &field_order);
+        let mut fields = Vec::new();
+        for name in &field_order {
+            if let Some(&offset) = offsets.get(name) {
+                fields.push((name.clone(), self.ir_type(&field_types[name]), offset));
+            }
+        }
+        StructLayout {
+            name: decl.name.clone(),
+            fields,
+            size,
+            alignment,
+        }
+    }
+
+    fn ir_type(&self, typ: &crate::semantic::type_system::Type) -> IRType {
+        use crate::semantic::type_system::Type;
+        match typ {
+            Type::Int => IRType::Int,
+            Type::Long => IRType::Long,
+            Type::UInt => IRType::UInt,
+            Type::ULong => IRType::ULong,
+            Type::Float => IRType::Float,
+            Type::Bool => IRType::Bool,
+            Type::Void => IRType::Void,
+            Type::String => IRType::String,
+            Type::Char => IRType::Char,
+            Type::Enum(_) => IRType::Int,
+            Type::Struct(name) => IRType::Struct(
+                name.clone(),
+                self.struct_layouts.get(name).map(|l| l.size).unwrap_or(0),
+            ),
+            Type::Pointer(inner) => IRType::Pointer(Box::new(self.ir_type(inner))),
+            Type::Array(inner, count) => IRType::Array(Box::new(self.ir_type(inner)), *count),
+            Type::Function { .. } => IRType::Unknown,
+        }
+    }
+
+    fn ast_ir_type(&self, typ: &crate::parser::ast::Type) -> IRType {
+        self.ir_type(&crate::semantic::type_system::Type::from_ast(typ))
+    }
+
+    /// Тип константного операнда
+    fn literal_type(op: &Operand) -> Option<IRType> {
+        match op {
+            Operand::IntLiteral(v) if i32::try_from(*v).is_ok() => Some(IRType::Int),
+            Operand::IntLiteral(_) => Some(IRType::Long),
+            Operand::FloatLiteral(_) => Some(IRType::Float),
+            Operand::BoolLiteral(_) => Some(IRType::Bool),
+            Operand::StringLiteral(_) => Some(IRType::String),
+            _ => None,
+        }
+    }
+
+    /// Общий целый тип двух IR-типов по обычным арифметическим преобразованиям
+    fn common_integer_type(&self, left: &IRType, right: &IRType) -> IRType {
+        match (Self::semantic_type(left), Self::semantic_type(right)) {
+            (Some(l), Some(r)) => TypeChecker::new()
+                .common_numeric_type(&l, &r)
+                .map(|t| self.ir_type(&t))
+                .unwrap_or(IRType::Int),
+            _ => IRType::Int,
+        }
+    }
+
+    /// Закрепляет за временным значением целый тип выражения из семантики
+    fn pin_integer_type(&mut self, d: &Operand, typ: Option<crate::semantic::type_system::Type>) {
+        if let Some(t) = typ.filter(|t| t.is_integer()) {
+            self.pin_type(d, &t);
+        }
+    }
+
+    /// Закрепляет за временным значением тип из семантики
+    fn pin_type(&mut self, d: &Operand, typ: &crate::semantic::type_system::Type) {
+        if let Operand::Temporary(name) = d {
+            let ir = self.ir_type(typ);
+            self.pinned_types.insert(name.clone(), ir);
+        }
+    }
+
+    /// Выводит типы временных значений функции по их определениям
+    ///
+    /// Арифметика над вещественным операндом дает `f64`, сравнения дают `bool`,
+    /// вызов получает возвращаемый тип функции. Проход повторяется, пока типы
+    /// меняются: временное значение может использоваться в блоке, который
+    /// предшествует определению в порядке хранения. Целые типы, закрепленные
+    /// по семантике выражения, имеют приоритет над выводом.
+    fn type_temporaries(&self, func_ir: &mut FunctionIR) {
+        let mut types: HashMap<String, IRType> = self.pinned_types.clone();
+        let labels: Vec<String> = super::control_flow::reverse_postorder(func_ir);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for label in &labels {
+                let block = match func_ir.get_block(label) {
+                    Some(block) => block,
+                    None => continue,
+                };
+                for instr in &block.instructions {
+                    let name = match instr.dest() {
+                        Some(Operand::Temporary(name)) => name,
+                        _ => continue,
+                    };
+                    let typ = match self.instruction_type(instr, func_ir, &types) {
+                        Some(typ) => typ,
+                        None => continue,
+                    };
+                    if !types.contains_key(name) {
+                        types.insert(name.clone(), typ);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        let mut typed: Vec<(String, IRType)> = types.into_iter().collect();
+        typed.sort_by_key(|(name, _)| {
+            name.trim_start_matches('t').parse::<usize>().unwrap_or(usize::MAX)
+        });
+        for (name, typ) in typed {
+            func_ir.set_temp_type(&name, typ);
+        }
+    }
+
+    fn operand_ir_type(
+        &self,
+        op: &Operand,
+        func_ir: &FunctionIR,
+        temps: &HashMap<String, IRType>,
+    ) -> Option<IRType> {
+        match op {
+            Operand::Temporary(name) => temps.get(name).cloned(),
+            Operand::Variable(name) => func_ir
+                .value_type(name)
+                .or_else(|| self.program.global_type(name))
+                .cloned(),
+            _ => Self::literal_type(op),
+        }
+    }
+
+    fn instruction_type(
+        &self,
+        instr: &IRInstruction,
+        func_ir: &FunctionIR,
+        temps: &HashMap<String, IRType>,
+    ) -> Option<IRType> {
+        let ty = |op: &Operand| self.operand_ir_type(op, func_ir, temps);
+        match instr {
+            IRInstruction::Add(_, l, r)
+            | IRInstruction::Sub(_, l, r)
+            | IRInstruction::Mul(_, l, r)
+            | IRInstruction::Div(_, l, r)
+            | IRInstruction::Mod(_, l, r) => match (ty(l), ty(r)) {
+                (Some(IRType::Float), _) | (_, Some(IRType::Float)) => Some(IRType::Float),
+                (Some(IRType::Pointer(inner)), _) | (_, Some(IRType::Pointer(inner))) => {
+                    Some(IRType::Pointer(inner))
+                }
+                (Some(l), Some(r)) if l.is_integer() && r.is_integer() => {
+                    Some(self.common_integer_type(&l, &r))
+                }
+                _ => Some(IRType::Int),
+            },
+            IRInstruction::Neg(_, s) => ty(s).or(Some(IRType::Int)),
+            IRInstruction::And(_, l, r)
+            | IRInstruction::Or(_, l, r)
+            | IRInstruction::Xor(_, l, r) => match (ty(l), ty(r)) {
+                (Some(l), Some(r)) if l.is_integer() && r.is_integer() => {
+                    Some(self.common_integer_type(&l, &r))
+                }
+                (l, _) => l.or(Some(IRType::Int)),
+            },
+            IRInstruction::Not(_, l) => ty(l).or(Some(IRType::Int)),
+            IRInstruction::Shl(_, l, _)
+            | IRInstruction::Shr(_, l, _)
+            | IRInstruction::Sar(_, l, _) => match ty(l) {
+                Some(l) if l.is_integer() => Some(self.common_integer_type(&l, &IRType::Int)),
+                _ => Some(IRType::Int),
+            },
+            IRInstruction::Move(_, s) => ty(s),
+            IRInstruction::Call(_, f, _) => match f {
+                Operand::Label(name) | Operand::Variable(name) => {
+                    self.function_types.get(name).cloned()
+                }
+                _ => None,
+            },
+            IRInstruction::ArrayLoad(_, base, _) => match ty(base) {
+                Some(IRType::Array(inner, _)) | Some(IRType::Pointer(inner)) => {
+                    let mut elem = *inner;
+                    while let IRType::Array(inner, _) = elem {
+                        elem = *inner;
+                    }
+                    Some(elem)
+                }
+                _ => Some(IRType::Int),
+            },
+            IRInstruction::Load(_, addr) => match ty(addr) {
+                Some(IRType::Pointer(inner)) => Some(*inner),
+                _ => None,
+            },
+            IRInstruction::Gep(_, base, _) | IRInstruction::AddrOf(_, base) => {
+                Some(IRType::Pointer(Box::new(ty(base).unwrap_or(IRType::Unknown))))
+            }
+            IRInstruction::Phi(_, pairs) => pairs.iter().find_map(|(v, _)| ty(v)),
+            _ => instr.result_type(temps),
+        }
+    }
+
+    /// Раскладка структуры, если имя обозначает переменную структурного типа
+    fn struct_var_layout(&self, name: &str) -> Option<&StructLayout> {
+        let struct_name = match self.struct_vars.get(name) {
+            Some(s) => s,
+            None if self.current_locals.iter().any(|(n, _)| n == name) => return None,
+            None => self.global_struct_vars.get(name)?,
+        };
+        self.struct_layouts.get(struct_name)
+    }
+
+    /// Раскладка структуры - значения выражения
+    fn value_layout(&self, expr: &Expression) -> Option<&StructLayout> {
+        match self.get_expression_type(expr)? {
+            crate::semantic::type_system::Type::Struct(name) => self.struct_layouts.get(&name),
+            _ => None,
+        }
+    }
+
+    /// База и смещение структуры-значения: переменная, поле-структура,
+    /// элемент массива структур или `*p`. Для элемента массива и `*p`
+    /// базой служит вычисленный адрес
+    fn struct_operand(
+        &mut self,
+        expr: &Expression,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> Option<(Operand, u32, StructLayout)> {
+        let layout = self.value_layout(expr)?.clone();
+        let (base, offset) = match expr {
+            Expression::Identifier(i) => {
+                self.struct_var_layout(&i.name)?;
+                (Operand::Variable(i.name.clone()), 0)
+            }
+            Expression::Grouped(g) => return self.struct_operand(&g.expr, cb, ab),
+            Expression::Call(call) => (self.generate_call(call, cb, ab), 0),
+            Expression::StructAccess(sa) => {
+                let (base, _, offset) = self.struct_field(sa, cb, ab)?;
+                (base, offset)
+            }
+            Expression::ArrayAccess(aa) => (self.array_element_address(aa, cb, ab), 0),
+            Expression::Unary(u) if u.operator == UnaryOp::Deref => {
+                (self.pointer_base(&u.operand, cb, ab), 0)
+            }
+            _ => return None,
+        };
+        Some((base, offset, layout))
+    }
+
+    /// Значение указателя как база доступа к полям
+    fn pointer_base(
+        &mut self,
+        pointer: &Expression,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> Operand {
+        let value = self.generate_expression(pointer, cb, ab);
+        // Глобальная переменная-база адресует саму структуру,
+        // поэтому указатель сначала копируется во временную
+        match value {
+            Operand::Variable(name) if !self.is_local(&name) => {
+                let t = self.new_temp();
+                cb.add_instruction(IRInstruction::Move(t.clone(), Operand::Variable(name)));
+                t
+            }
+            value => value,
+        }
+    }
+
+    /// Раскладка структуры, на которую указывает выражение
+    fn pointee_layout(&self, pointer: &Expression) -> Option<&StructLayout> {
+        use crate::semantic::type_system::Type;
+        match self.get_expression_type(pointer)? {
+            Type::Pointer(inner) => match *inner {
+                Type::Struct(name) => self.struct_layouts.get(&name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Тип и смещение поля для `s.field` или `p->field` без генерации кода
+    fn field_info(&self, sa: &StructAccessExpr) -> Option<(IRType, u32)> {
+        let layout = if sa.is_arrow {
+            self.pointee_layout(&sa.object)?
+        } else {
+            self.value_layout(&sa.object)?
+        };
+        let (_, typ, offset) = layout.field(&sa.field)?;
+        Some((typ.clone(), *offset as u32))
+    }
+
+    /// База, тип и смещение поля. Для доступа через указатель базой
+    /// служит вычисленное значение указателя, для вложенной структуры
+    /// смещения полей складываются
+    fn struct_field(
+        &mut self,
+        sa: &StructAccessExpr,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> Option<(Operand, IRType, u32)> {
+        let (typ, offset) = self.field_info(sa)?;
+        let (base, outer) = if sa.is_arrow {
+            (self.pointer_base(&sa.object, cb, ab), 0)
+        } else {
+            let (base, outer, _) = self.struct_operand(&sa.object, cb, ab)?;
+            (base, outer)
+        };
+        Some((base, typ, outer + offset))
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        self.current_locals.iter().chain(&self.current_params).any(|(n, _)| n == name)
+    }
+
+    /// Копирует структуру поле за полем; вложенные структуры и массивы
+    /// раскладываются до скалярных полей
+    fn copy_struct(
+        &mut self,
+        (dst, dst_offset): (&Operand, u32),
+        (src, src_offset): (&Operand, u32),
+        layout: &StructLayout,
+        cb: &mut BasicBlock,
+    ) {
+        let mut slots = Vec::new();
+        for (_, typ, offset) in &layout.fields {
+            self.scalar_slots(typ, *offset, &mut slots);
+        }
+        for (offset, typ) in slots {
+            let t = self.new_temp();
+            let off = offset as u32;
+            cb.add_instruction(IRInstruction::FieldLoad(
+                t.clone(),
+                src.clone(),
+                src_offset + off,
+                typ.clone(),
+            ));
+            cb.add_instruction(IRInstruction::FieldStore(dst.clone(), dst_offset + off, t, typ));
+        }
+    }
+
+    /// Скалярные части значения типа `typ`, лежащего по смещению `offset`
+    fn scalar_slots(&self, typ: &IRType, offset: usize, out: &mut Vec<(usize, IRType)>) {
+        match typ {
+            IRType::Array(inner, count) => {
+                for i in 0..*count {
+                    self.scalar_slots(inner, offset + i * inner.size(), out);
+                }
+            }
+            IRType::Struct(name, _) => {
+                if let Some(layout) = self.struct_layouts.get(name) {
+                    for (_, field, field_offset) in &layout.fields {
+                        self.scalar_slots(field, offset + field_offset, out);
+                    }
+                }
+            }
+            other => out.push((offset, other.clone())),
+        }
+    }
+
     fn create_block(&mut self, suffix: &str) -> BasicBlock {
         let label = self.new_label();
         let mut label_str = Self::label_to_string(&label);
         label_str.push_str(&format!("_{}", suffix));
-        BasicBlock::new(label_str)
+        let mut block = BasicBlock::new(label_str);
+        block.set_line(self.current_line);
+        block
     }
 
     fn generate_statement(
@@ -137,13 +792,24 @@ impl IRGenerator {
         current_block: &mut BasicBlock,
         all_blocks: &mut Vec<BasicBlock>,
     ) {
+        self.current_line = stmt.node_position().line;
+        current_block.set_line(self.current_line);
         match stmt {
             Statement::VariableDecl(var) => {
-                self.current_locals
-                    .push((var.name.clone(), var.var_type.to_string()));
+                let local_type = match &var.var_type {
+                    crate::parser::ast::Type::Inferred => var
+                        .initializer
+                        .as_deref()
+                        .and_then(|init| self.get_expression_type(init))
+                        .map(|t| self.ir_type(&t))
+                        .unwrap_or(IRType::Int),
+                    t => self.ast_ir_type(t),
+                };
+                let local_type = Self::infer_array_length(local_type, var.initializer.as_deref());
+                self.current_locals.push((var.name.clone(), local_type.clone()));
 
-                if let crate::parser::ast::Type::Array(_, size) = &var.var_type {
-                    let array_size = size.unwrap_or(0) as u32 * 8;
+                if let IRType::Array(..) = &local_type {
+                    let array_size = Self::array_bytes(&local_type);
                     if array_size > 0 {
                         current_block.add_instruction(IRInstruction::Alloca(
                             Operand::Variable(var.name.clone()),
@@ -152,25 +818,60 @@ impl IRGenerator {
                     }
                 }
 
+                if let crate::parser::ast::Type::Struct(name) = &var.var_type {
+                    self.struct_vars.insert(var.name.clone(), name.clone());
+                    let struct_size = self.struct_layouts.get(name).map(|l| l.size).unwrap_or(0);
+                    current_block.add_instruction(IRInstruction::Alloca(
+                        Operand::Variable(var.name.clone()),
+                        struct_size.max(8) as u32,
+                    ));
+                } else {
+                    self.struct_vars.remove(&var.name);
+                }
+
                 if let Some(init) = &var.initializer {
+                    if let Some((src, offset, layout)) =
+                        self.struct_operand(init, current_block, all_blocks)
+                    {
+                        let dst = Operand::Variable(var.name.clone());
+                        self.copy_struct((&dst, 0), (&src, offset), &layout, current_block);
+                        return;
+                    }
                     match init.as_ref() {
                         Expression::ArrayInitializer(arr_init) => {
-                            for (i, elem) in arr_init.elements.iter().enumerate() {
-                                let val = self.generate_expression(elem, current_block, all_blocks);
-                                current_block.add_instruction(IRInstruction::ArrayStore(
+                            let array = Operand::Variable(var.name.clone());
+                            self.store_initializer(
+                                &array,
+                                &local_type,
+                                arr_init,
+                                current_block,
+                                all_blocks,
+                            );
+                        }
+                        _ => match Self::string_bytes(init, &local_type) {
+                            Some(bytes) => {
+                                // Строка копируется в массив побайтно, остаток обнуляется
+                                let array = Operand::Variable(var.name.clone());
+                                let zeros = std::iter::repeat(Operand::IntLiteral(0));
+                                let slots = Self::array_slots(&local_type);
+                                let bytes = bytes.into_iter().chain(zeros).take(slots);
+                                for (slot, byte) in bytes.enumerate() {
+                                    current_block.add_instruction(IRInstruction::ArrayStore(
+                                        array.clone(),
+                                        Operand::IntLiteral(slot as i64),
+                                        byte,
+                                    ));
+                                }
+                            }
+                            None => {
+                                let value =
+                                    self.generate_expression(init, current_block, all_blocks);
+                                current_block.add_instruction(IRInstruction::Move(
                                     Operand::Variable(var.name.clone()),
-                                    Operand::IntLiteral(i as i32),
-                                    val,
+                                    value,
                                 ));
                             }
-                        }
-                        _ => {
-                            let value = self.generate_expression(init, current_block, all_blocks);
-                            current_block.add_instruction(IRInstruction::Move(
-                                Operand::Variable(var.name.clone()),
-                                value,
-                            ));
-                        }
+                        },
                     }
                 }
             }
@@ -187,13 +888,27 @@ impl IRGenerator {
                 let old = std::mem::replace(current_block, mb);
                 all_blocks.push(old);
             }
+            Statement::DoWhile(dw) => {
+                let mb = self.generate_do_while_statement(dw, current_block, all_blocks);
+                let old = std::mem::replace(current_block, mb);
+                all_blocks.push(old);
+            }
             Statement::For(fs) => {
                 let mb = self.generate_for_statement(fs, current_block, all_blocks);
                 let old = std::mem::replace(current_block, mb);
                 all_blocks.push(old);
             }
             Statement::Return(rs) => {
-                if let Some(v) = &rs.value {
+                let sret = self.current_params.iter().any(|(n, _)| n == SRET_PARAM);
+                let value = rs.value.as_deref();
+                let copied = value
+                    .filter(|_| sret)
+                    .and_then(|v| self.struct_operand(v, current_block, all_blocks));
+                if let Some((src, offset, layout)) = copied {
+                    let dst = Operand::Variable(SRET_PARAM.to_string());
+                    self.copy_struct((&dst, 0), (&src, offset), &layout, current_block);
+                    current_block.add_instruction(IRInstruction::Return(Some(dst)));
+                } else if let Some(v) = value {
                     let val = self.generate_expression(v, current_block, all_blocks);
                     current_block.add_instruction(IRInstruction::Return(Some(val)));
                 } else {
@@ -239,23 +954,385 @@ impl IRGenerator {
             }
             Expression::Grouped(g) => self.is_float_operand(&g.expr),
             Expression::Unary(u) => self.is_float_operand(&u.operand),
+            Expression::StructAccess(sa) => self
+                .field_info(sa)
+                .map(|(typ, _)| typ == IRType::Float)
+                .unwrap_or(false),
+            Expression::Cast(c) => matches!(c.target_type, crate::parser::ast::Type::Float),
+            Expression::Conditional(c) => {
+                self.is_float_operand(&c.then_expr) || self.is_float_operand(&c.else_expr)
+            }
+            Expression::Comma(c) => self.is_float_operand(&c.right),
             _ => false,
         }
     }
 
     fn get_expression_type(&self, expr: &Expression) -> Option<crate::semantic::type_system::Type> {
         match expr {
-            Expression::Identifier(i) => self.symbol_table.lookup(&i.name).map(|s| s.typ.clone()),
+            Expression::Identifier(i) => {
+                let mut locals = self.current_locals.iter().rev().chain(&self.current_params);
+                match locals.find(|(n, _)| *n == i.name) {
+                    Some((_, typ)) => Self::semantic_type(typ),
+                    None => self.symbol_table.lookup(&i.name).map(|s| s.typ.clone()),
+                }
+            }
             Expression::Literal(l) => match &l.value {
                 LiteralValue::Int(_) => Some(crate::semantic::type_system::Type::Int),
                 LiteralValue::Float(_) => Some(crate::semantic::type_system::Type::Float),
                 LiteralValue::Bool(_) => Some(crate::semantic::type_system::Type::Bool),
                 LiteralValue::String(_) => Some(crate::semantic::type_system::Type::String),
+                LiteralValue::Char(_) => Some(crate::semantic::type_system::Type::Char),
+                LiteralValue::Long(_) => Some(crate::semantic::type_system::Type::Long),
+                LiteralValue::UInt(_) => Some(crate::semantic::type_system::Type::UInt),
+                LiteralValue::ULong(_) => Some(crate::semantic::type_system::Type::ULong),
+                LiteralValue::Null => Some(crate::semantic::type_system::Type::null_pointer()),
+            },
+            Expression::StructAccess(sa) => {
+                let (typ, _) = self.field_info(sa)?;
+                Self::semantic_type(&typ)
+            }
+            Expression::Grouped(g) => self.get_expression_type(&g.expr),
+            Expression::Binary(b) => {
+                let left = self.get_expression_type(&b.left)?;
+                let right = self.get_expression_type(&b.right)?;
+                let op = BinaryOpType::from(&b.operator);
+                TypeChecker::new().binary_result_type(&left, &right, op)
+            }
+            Expression::Unary(u) => {
+                let operand = self.get_expression_type(&u.operand)?;
+                TypeChecker::new().unary_result_type(&operand, UnaryOpType::from(&u.operator))
+            }
+            Expression::Assignment(a) => self.get_expression_type(&a.target),
+            Expression::ArrayAccess(aa) => match self.get_expression_type(&aa.array)? {
+                crate::semantic::type_system::Type::Array(inner, _)
+                | crate::semantic::type_system::Type::Pointer(inner) => Some(*inner),
+                _ => None,
+            },
+            Expression::Call(c) => match c.callee.as_ref() {
+                Expression::Identifier(f) => {
+                    self.symbol_table.lookup(&f.name).and_then(|s| s.return_type().cloned())
+                }
+                _ => None,
             },
+            Expression::Cast(c) => Some(crate::semantic::type_system::Type::from_ast(
+                &c.target_type,
+            )),
+            Expression::Sizeof(_) => Some(crate::semantic::type_system::Type::ULong),
+            Expression::ArrayInitializer(arr) => Some(crate::semantic::type_system::Type::Array(
+                Box::new(self.get_expression_type(arr.elements.first()?)?),
+                arr.elements.len(),
+            )),
+            Expression::Conditional(c) => {
+                let then_type = self.get_expression_type(&c.then_expr)?;
+                let else_type = self.get_expression_type(&c.else_expr)?;
+                TypeChecker::new().conditional_result_type(&then_type, &else_type)
+            }
+            Expression::Comma(c) => self.get_expression_type(&c.right),
+        }
+    }
+
+    /// Обратное отображение скалярного IR-типа в тип семантического анализа
+    fn semantic_type(typ: &IRType) -> Option<crate::semantic::type_system::Type> {
+        use crate::semantic::type_system::Type;
+        match typ {
+            IRType::Int => Some(Type::Int),
+            IRType::Long => Some(Type::Long),
+            IRType::UInt => Some(Type::UInt),
+            IRType::ULong => Some(Type::ULong),
+            IRType::Float => Some(Type::Float),
+            IRType::Bool => Some(Type::Bool),
+            IRType::Char => Some(Type::Char),
+            IRType::String => Some(Type::String),
+            IRType::Struct(name, _) => Some(Type::Struct(name.clone())),
+            IRType::Pointer(inner) => Some(Type::Pointer(Box::new(Self::semantic_type(inner)?))),
+            IRType::Array(inner, n) => Some(Type::Array(Box::new(Self::semantic_type(inner)?), *n)),
+            _ => None,
+        }
+    }
+
+    /// Признак беззнаковой операции: общий тип операндов беззнаковый;
+    /// адреса сравниваются без знака
+    fn is_unsigned_operation(&self, left: &Expression, right: &Expression) -> bool {
+        match (self.get_expression_type(left), self.get_expression_type(right)) {
+            (Some(l), Some(r)) if l.element_type().is_some() || r.element_type().is_some() => {
+                true
+            }
+            (Some(l), Some(r)) => TypeChecker::new()
+                .common_numeric_type(&l, &r)
+                .is_some_and(|t| t.is_unsigned()),
+            _ => false,
+        }
+    }
+
+    /// Шаг адресной арифметики для указателя или массива в байтах
+    ///
+    /// Совпадает с шагом индексации `p[i]` в кодогенераторе и со значением
+    /// `sizeof` элемента
+    fn element_stride(&self, typ: &crate::semantic::type_system::Type) -> Option<usize> {
+        use crate::semantic::type_system::Type;
+        match typ {
+            Type::Pointer(inner) | Type::Array(inner, _) => Some(self.type_size(inner).max(1)),
             _ => None,
         }
     }
 
+    /// Шаг слота, по которому `array_element` считает номер элемента:
+    /// размер скалярного элемента самого внутреннего измерения
+    fn slot_stride(&self, typ: &crate::semantic::type_system::Type) -> usize {
+        use crate::semantic::type_system::Type;
+        match typ {
+            Type::Pointer(inner) | Type::Array(inner, _) if inner.is_array() => {
+                self.slot_stride(inner)
+            }
+            _ => self.element_stride(typ).unwrap_or(8),
+        }
+    }
+
+    /// Смещение в байтах для `index` элементов с шагом `stride`
+    fn scale_offset(&mut self, index: Operand, stride: usize, cb: &mut BasicBlock) -> Operand {
+        match index {
+            index if stride == 1 => index,
+            Operand::IntLiteral(v) => Operand::IntLiteral(v.wrapping_mul(stride as i64)),
+            index => {
+                let d = self.new_temp();
+                self.pin_integer_type(&d, Some(crate::semantic::type_system::Type::Long));
+                let stride = Operand::IntLiteral(stride as i64);
+                cb.add_instruction(IRInstruction::Mul(d.clone(), index, stride));
+                d
+            }
+        }
+    }
+
+    /// Массив в адресной арифметике заменяется адресом первого элемента;
+    /// параметр-массив и указатель уже хранят адрес
+    fn decay_array(&mut self, expr: &Expression, value: Operand, cb: &mut BasicBlock) -> Operand {
+        let name = match expr {
+            Expression::Identifier(i) => &i.name,
+            Expression::Grouped(g) => return self.decay_array(&g.expr, value, cb),
+            _ => return value,
+        };
+        if self.current_params.iter().any(|(n, _)| n == name) {
+            return value;
+        }
+        let typ = match self.current_locals.iter().rev().find(|(n, _)| n == name) {
+            Some((_, typ)) => Some(typ),
+            None => self.program.global_type(name),
+        };
+        if !matches!(typ, Some(IRType::Array(..))) {
+            return value;
+        }
+        let d = self.new_temp();
+        cb.add_instruction(IRInstruction::AddrOf(d.clone(), value));
+        d
+    }
+
+    /// Число слотов значения: массивы хранятся построчно, по слоту на
+    /// каждый скалярный элемент
+    fn array_slots(typ: &IRType) -> usize {
+        match typ {
+            IRType::Array(inner, count) => Self::array_slots(inner) * count,
+            _ => 1,
+        }
+    }
+
+    /// Место под массив на стеке: байтовые элементы идут подряд,
+    /// размер округляется до 8 байт
+    fn array_bytes(typ: &IRType) -> u32 {
+        (typ.size().div_ceil(8) * 8) as u32
+    }
+
+    /// Раскладывает вложенный инициализатор по слотам массива в построчном
+    /// порядке: `{{1, 2}, {3}}` для `int[2][2]` дает слоты 0, 1 и 2
+    fn flatten_initializer<'a>(
+        init: &'a ArrayInitializerExpr,
+        typ: &IRType,
+        base: usize,
+        out: &mut Vec<(usize, &'a Expression)>,
+    ) {
+        let inner = match typ {
+            IRType::Array(inner, _) => inner.as_ref(),
+            other => other,
+        };
+        let row = Self::array_slots(inner);
+        for (i, elem) in init.elements.iter().enumerate() {
+            match elem {
+                Expression::ArrayInitializer(sub) => {
+                    Self::flatten_initializer(sub, inner, base + i * row, out)
+                }
+                _ => out.push((base + i * row, elem)),
+            }
+        }
+    }
+
+    /// Опущенный размер `int a[] = {...}` равен числу элементов списка
+    fn infer_array_length(typ: IRType, init: Option<&Expression>) -> IRType {
+        match (typ, init) {
+            (IRType::Array(inner, 0), Some(Expression::ArrayInitializer(arr))) => {
+                IRType::Array(inner, arr.elements.len())
+            }
+            (typ, _) => typ,
+        }
+    }
+
+    /// Записывает элементы списка, приведенные к типу элемента, в слоты
+    /// массива и обнуляет остальные
+    fn store_initializer(
+        &mut self,
+        array: &Operand,
+        typ: &IRType,
+        init: &ArrayInitializerExpr,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) {
+        let mut slots = Vec::new();
+        Self::flatten_initializer(init, typ, 0, &mut slots);
+        let mut element = typ;
+        while let IRType::Array(inner, _) = element {
+            element = inner;
+        }
+        let element = Self::semantic_type(element);
+        let mut filled = vec![false; Self::array_slots(typ)];
+        for (slot, elem) in slots {
+            let mut val = self.generate_expression(elem, cb, ab);
+            if let (Some(from), Some(to)) = (self.get_expression_type(elem), element.as_ref()) {
+                val = self.convert_type(val, &from, to, cb);
+            }
+            cb.add_instruction(IRInstruction::ArrayStore(
+                array.clone(),
+                Operand::IntLiteral(slot as i64),
+                val,
+            ));
+            if let Some(f) = filled.get_mut(slot) {
+                *f = true;
+            }
+        }
+        for (slot, _) in filled.iter().enumerate().filter(|(_, f)| !**f) {
+            cb.add_instruction(IRInstruction::ArrayStore(
+                array.clone(),
+                Operand::IntLiteral(slot as i64),
+                Operand::IntLiteral(0),
+            ));
+        }
+    }
+
+    /// Тип элемента, выбираемого индексацией `a[i]`
+    fn access_type(&self, aa: &ArrayAccessExpr) -> Option<crate::semantic::type_system::Type> {
+        self.get_expression_type(&aa.array)?.element_type().cloned()
+    }
+
+    /// Индексация строки многомерного массива: `m[i]` в `m[i][j]`
+    fn row_access<'a>(&self, expr: &'a Expression) -> Option<&'a ArrayAccessExpr> {
+        match expr {
+            Expression::ArrayAccess(aa) if self.access_type(aa)?.is_array() => Some(aa),
+            Expression::Grouped(g) => self.row_access(&g.expr),
+            _ => None,
+        }
+    }
+
+    /// Корневой массив цепочки индексаций и номер слота элемента:
+    /// для `int m[3][4]` выражение `m[i][j]` адресует слот `i * 4 + j`
+    fn array_element<'a>(
+        &mut self,
+        aa: &'a ArrayAccessExpr,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> (&'a Expression, Operand, Operand) {
+        let row = self
+            .access_type(aa)
+            .map(|t| Self::array_slots(&self.ir_type(&t)))
+            .unwrap_or(1);
+        let (root, base, outer) = match self.row_access(&aa.array) {
+            Some(inner) => {
+                let (root, base, outer) = self.array_element(inner, cb, ab);
+                (root, base, Some(outer))
+            }
+            None => (aa.array.as_ref(), self.generate_expression(&aa.array, cb, ab), None),
+        };
+        let idx = self.generate_expression(&aa.index, cb, ab);
+        let idx = self.scale_offset(idx, row, cb);
+        let idx = match (outer, idx) {
+            (None, idx) => idx,
+            (Some(Operand::IntLiteral(o)), Operand::IntLiteral(i)) => {
+                Operand::IntLiteral(o.wrapping_add(i))
+            }
+            (Some(outer), idx) => {
+                let d = self.new_temp();
+                self.pin_type(&d, &crate::semantic::type_system::Type::Long);
+                cb.add_instruction(IRInstruction::Add(d.clone(), outer, idx));
+                d
+            }
+        };
+        (root, base, idx)
+    }
+
+    /// Адрес элемента `a[i]`, `m[i][j]` или строки `m[i]`
+    fn array_element_address(
+        &mut self,
+        aa: &ArrayAccessExpr,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> Operand {
+        let (root, base, idx) = self.array_element(aa, cb, ab);
+        let base = self.decay_array(root, base, cb);
+        let stride = self.get_expression_type(root).map(|t| self.slot_stride(&t)).unwrap_or(8);
+        let offset = self.scale_offset(idx, stride, cb);
+        let d = self.new_temp();
+        if let Some(t) = self.access_type(aa) {
+            let elem = match t {
+                crate::semantic::type_system::Type::Array(inner, _) => *inner,
+                t => t,
+            };
+            self.pin_type(&d, &crate::semantic::type_system::Type::Pointer(Box::new(elem)));
+        }
+        cb.add_instruction(IRInstruction::Add(d.clone(), base, offset));
+        d
+    }
+
+    /// Адресная арифметика: в `p + i` и `p - i` индекс умножается на шаг
+    /// элемента, разность указателей `p - q` делится на шаг
+    fn generate_pointer_arithmetic(
+        &mut self,
+        b: &BinaryExpr,
+        left: &Operand,
+        right: &Operand,
+        cb: &mut BasicBlock,
+    ) -> Option<Operand> {
+        let lt = self.get_expression_type(&b.left)?;
+        let rt = self.get_expression_type(&b.right)?;
+        let op_type = BinaryOpType::from(&b.operator);
+        let result = TypeChecker::new().binary_result_type(&lt, &rt, op_type)?;
+        let (pointer, index, stride) =
+            match (b.operator, self.element_stride(&lt), self.element_stride(&rt)) {
+                (BinaryOp::Sub, Some(stride), Some(_)) => {
+                    let l = self.decay_array(&b.left, left.clone(), cb);
+                    let r = self.decay_array(&b.right, right.clone(), cb);
+                    let diff = self.new_temp();
+                    self.pin_integer_type(&diff, Some(result.clone()));
+                    cb.add_instruction(IRInstruction::Sub(diff.clone(), l, r));
+                    if stride == 1 {
+                        return Some(diff);
+                    }
+                    let d = self.new_temp();
+                    self.pin_integer_type(&d, Some(result));
+                    let stride = Operand::IntLiteral(stride as i64);
+                    cb.add_instruction(IRInstruction::Div(d.clone(), diff, stride));
+                    return Some(d);
+                }
+                (BinaryOp::Add | BinaryOp::Sub, Some(stride), None) => {
+                    (self.decay_array(&b.left, left.clone(), cb), right.clone(), stride)
+                }
+                (BinaryOp::Add, None, Some(stride)) => {
+                    (self.decay_array(&b.right, right.clone(), cb), left.clone(), stride)
+                }
+                _ => return None,
+            };
+        let offset = self.scale_offset(index, stride, cb);
+        let d = self.new_temp();
+        self.pin_type(&d, &result);
+        cb.add_instruction(Self::binary_instruction(b.operator, false, d.clone(), pointer, offset));
+        Some(d)
+    }
+
     fn convert_type(
         &mut self,
         expr: Operand,
@@ -264,18 +1341,12 @@ impl IRGenerator {
         cb: &mut BasicBlock,
     ) -> Operand {
         match (from, to) {
-            (
-                crate::semantic::type_system::Type::Int,
-                crate::semantic::type_system::Type::Float,
-            ) => {
+            (f, crate::semantic::type_system::Type::Float) if f.is_integer() => {
                 let d = self.new_temp();
                 cb.add_instruction(IRInstruction::IntToFloat(d.clone(), expr));
                 d
             }
-            (
-                crate::semantic::type_system::Type::Float,
-                crate::semantic::type_system::Type::Int,
-            ) => {
+            (crate::semantic::type_system::Type::Float, t) if t.is_integer() => {
                 let d = self.new_temp();
                 cb.add_instruction(IRInstruction::FloatToInt(d.clone(), expr));
                 d
@@ -299,27 +1370,30 @@ impl IRGenerator {
                 _ => {
                     let mut left = self.generate_expression(&b.left, current_block, all_blocks);
                     let mut right = self.generate_expression(&b.right, current_block, all_blocks);
+                    if let Some(d) =
+                        self.generate_pointer_arithmetic(b, &left, &right, current_block)
+                    {
+                        return d;
+                    }
                     let lt = self.get_expression_type(&b.left);
                     let rt = self.get_expression_type(&b.right);
                     match (&lt, &rt) {
-                        (
-                            Some(crate::semantic::type_system::Type::Int),
-                            Some(crate::semantic::type_system::Type::Float),
-                        ) => {
+                        (Some(l), Some(crate::semantic::type_system::Type::Float))
+                            if l.is_integer() =>
+                        {
                             left = self.convert_type(
                                 left,
-                                &crate::semantic::type_system::Type::Int,
+                                l,
                                 &crate::semantic::type_system::Type::Float,
                                 current_block,
                             );
                         }
-                        (
-                            Some(crate::semantic::type_system::Type::Float),
-                            Some(crate::semantic::type_system::Type::Int),
-                        ) => {
+                        (Some(crate::semantic::type_system::Type::Float), Some(r))
+                            if r.is_integer() =>
+                        {
                             right = self.convert_type(
                                 right,
-                                &crate::semantic::type_system::Type::Int,
+                                r,
                                 &crate::semantic::type_system::Type::Float,
                                 current_block,
                             );
@@ -329,12 +1403,9 @@ impl IRGenerator {
                     let d = self.new_temp();
                     let is_float =
                         self.is_float_operand(&b.left) || self.is_float_operand(&b.right);
+                    let unsigned = self.is_unsigned_operation(&b.left, &b.right);
+                    self.pin_integer_type(&d, self.get_expression_type(expr));
                     let instr = match b.operator {
-                        BinaryOp::Add => IRInstruction::Add(d.clone(), left, right),
-                        BinaryOp::Sub => IRInstruction::Sub(d.clone(), left, right),
-                        BinaryOp::Mul => IRInstruction::Mul(d.clone(), left, right),
-                        BinaryOp::Div => IRInstruction::Div(d.clone(), left, right),
-                        BinaryOp::Mod => IRInstruction::Mod(d.clone(), left, right),
                         BinaryOp::Eq => {
                             if is_float {
                                 IRInstruction::CmpEqF(d.clone(), left, right)
@@ -352,6 +1423,8 @@ impl IRGenerator {
                         BinaryOp::Lt => {
                             if is_float {
                                 IRInstruction::CmpLtF(d.clone(), left, right)
+                            } else if unsigned {
+                                IRInstruction::CmpLtU(d.clone(), left, right)
                             } else {
                                 IRInstruction::CmpLt(d.clone(), left, right)
                             }
@@ -359,6 +1432,8 @@ impl IRGenerator {
                         BinaryOp::Le => {
                             if is_float {
                                 IRInstruction::CmpLeF(d.clone(), left, right)
+                            } else if unsigned {
+                                IRInstruction::CmpLeU(d.clone(), left, right)
                             } else {
                                 IRInstruction::CmpLe(d.clone(), left, right)
                             }
@@ -366,6 +1441,8 @@ impl IRGenerator {
                         BinaryOp::Gt => {
                             if is_float {
                                 IRInstruction::CmpGtF(d.clone(), left, right)
+                            } else if unsigned {
+                                IRInstruction::CmpGtU(d.clone(), left, right)
                             } else {
                                 IRInstruction::CmpGt(d.clone(), left, right)
                             }
@@ -373,19 +1450,52 @@ impl IRGenerator {
                         BinaryOp::Ge => {
                             if is_float {
                                 IRInstruction::CmpGeF(d.clone(), left, right)
+                            } else if unsigned {
+                                IRInstruction::CmpGeU(d.clone(), left, right)
                             } else {
                                 IRInstruction::CmpGe(d.clone(), left, right)
                             }
                         }
-                        _ => unreachable!(),
+                        BinaryOp::Shr => {
+                            let unsigned = lt.as_ref().is_some_and(|t| t.is_unsigned());
+                            let d = d.clone();
+                            Self::binary_instruction(BinaryOp::Shr, unsigned, d, left, right)
+                        }
+                        op => Self::binary_instruction(op, unsigned, d.clone(), left, right),
                     };
                     current_block.add_instruction(instr);
                     d
                 }
             },
             Expression::Unary(u) => {
+                let update = matches!(
+                    u.operator,
+                    UnaryOp::PreIncrement
+                        | UnaryOp::PostIncrement
+                        | UnaryOp::PreDecrement
+                        | UnaryOp::PostDecrement
+                );
+                let field = match u.operand.as_ref() {
+                    Expression::StructAccess(sa) if update => {
+                        self.struct_field(sa, current_block, all_blocks)
+                    }
+                    _ => None,
+                };
+                if let Some(result) =
+                    field.and_then(|f| self.generate_field_update(u, f, current_block))
+                {
+                    return result;
+                }
                 let op = self.generate_expression(&u.operand, current_block, all_blocks);
                 let d = self.new_temp();
+                // Инкремент указателя сдвигает его на один элемент
+                let step = match self.get_expression_type(&u.operand) {
+                    Some(t) if t.is_pointer() => self.element_stride(&t).unwrap_or(1) as i64,
+                    _ => 1,
+                };
+                if matches!(u.operator, UnaryOp::Neg | UnaryOp::BitNot) {
+                    self.pin_integer_type(&d, self.get_expression_type(expr));
+                }
                 match u.operator {
                     UnaryOp::Neg => {
                         current_block.add_instruction(IRInstruction::Neg(d.clone(), op))
@@ -397,6 +1507,9 @@ impl IRGenerator {
                     UnaryOp::Plus => {
                         current_block.add_instruction(IRInstruction::Move(d.clone(), op))
                     }
+                    UnaryOp::BitNot => {
+                        current_block.add_instruction(IRInstruction::Not(d.clone(), op))
+                    }
                     UnaryOp::Deref => {
                         current_block.add_instruction(IRInstruction::Load(d.clone(), op));
                     }
@@ -409,25 +1522,25 @@ impl IRGenerator {
                             ));
                             return d;
                         }
-                        Expression::ArrayAccess(aa) => {
-                            let arr = self.generate_expression(&aa.array, current_block, all_blocks);
-                            let idx = self.generate_expression(&aa.index, current_block, all_blocks);
+                        Expression::StructAccess(sa) => {
                             let d = self.new_temp();
-                            let offset = self.new_temp();
-                            current_block.add_instruction(IRInstruction::Mul(
-                                offset.clone(), idx, Operand::IntLiteral(8)
-                            ));
-                            current_block.add_instruction(IRInstruction::Add(
-                                d.clone(), arr, offset
-                            ));
+                            if let Some((base, _, offset)) =
+                                self.struct_field(sa, current_block, all_blocks)
+                            {
+                                current_block
+                                    .add_instruction(IRInstruction::Gep(d.clone(), base, offset));
+                            }
                             return d;
                         }
+                        Expression::ArrayAccess(aa) => {
+                            return self.array_element_address(aa, current_block, all_blocks);
+                        }
                         _ => {
                             return self.new_temp();
                         }
                     },
                     UnaryOp::PreIncrement => {
-                        let one = Operand::IntLiteral(1);
+                        let one = Operand::IntLiteral(step);
                         current_block.add_instruction(IRInstruction::Add(
                             d.clone(),
                             op.clone(),
@@ -437,7 +1550,7 @@ impl IRGenerator {
                     }
                     UnaryOp::PostIncrement => {
                         let t = self.new_temp();
-                        let one = Operand::IntLiteral(1);
+                        let one = Operand::IntLiteral(step);
                         current_block.add_instruction(IRInstruction::Move(t.clone(), op.clone()));
                         current_block.add_instruction(IRInstruction::Add(
                             d.clone(),
@@ -448,7 +1561,7 @@ impl IRGenerator {
                         return t;
                     }
                     UnaryOp::PreDecrement => {
-                        let one = Operand::IntLiteral(1);
+                        let one = Operand::IntLiteral(step);
                         current_block.add_instruction(IRInstruction::Sub(
                             d.clone(),
                             op.clone(),
@@ -458,7 +1571,7 @@ impl IRGenerator {
                     }
                     UnaryOp::PostDecrement => {
                         let t = self.new_temp();
-                        let one = Operand::IntLiteral(1);
+                        let one = Operand::IntLiteral(step);
                         current_block.add_instruction(IRInstruction::Move(t.clone(), op.clone()));
                         current_block.add_instruction(IRInstruction::Sub(
                             d.clone(),
@@ -472,16 +1585,64 @@ impl IRGenerator {
                 d
             }
             Expression::Assignment(a) => {
-                let val = self.generate_expression(&a.value, current_block, all_blocks);
-                match &*a.target {
-                    Expression::ArrayAccess(aa) => {
-                        let arr = self.generate_expression(&aa.array, current_block, all_blocks);
-                        let idx = self.generate_expression(&aa.index, current_block, all_blocks);
-                        current_block.add_instruction(IRInstruction::ArrayStore(
-                            arr.clone(),
-                            idx.clone(),
-                            val.clone(),
+                let struct_copy = a.operator == AssignmentOp::Assign
+                    && self.value_layout(&a.target).is_some()
+                    && self.value_layout(&a.value).is_some();
+                if struct_copy {
+                    let lhs = self.struct_operand(&a.target, current_block, all_blocks);
+                    let rhs = self.struct_operand(&a.value, current_block, all_blocks);
+                    if let (Some((dst, dst_off, layout)), Some((src, src_off, _))) = (lhs, rhs) {
+                        self.copy_struct((&dst, dst_off), (&src, src_off), &layout, current_block);
+                        return dst;
+                    }
+                }
+                let mut val = self.generate_expression(&a.value, current_block, all_blocks);
+                if matches!(a.operator, AssignmentOp::AddAssign | AssignmentOp::SubAssign) {
+                    let stride = self
+                        .get_expression_type(&a.target)
+                        .filter(|t| t.is_pointer())
+                        .and_then(|t| self.element_stride(&t));
+                    if let Some(stride) = stride {
+                        val = self.scale_offset(val, stride, current_block);
+                    }
+                }
+                let field = match &*a.target {
+                    Expression::StructAccess(sa) => {
+                        self.struct_field(sa, current_block, all_blocks)
+                    }
+                    _ => None,
+                };
+                match (&*a.target, field) {
+                    (_, Some((base, typ, offset))) => {
+                        let new_val = if a.operator == AssignmentOp::Assign {
+                            val
+                        } else {
+                            let old = self.new_temp();
+                            current_block.add_instruction(IRInstruction::FieldLoad(
+                                old.clone(),
+                                base.clone(),
+                                offset,
+                                typ.clone(),
+                            ));
+                            let new_val = self.new_temp();
+                            current_block.add_instruction(self.compound_instruction(
+                                a,
+                                new_val.clone(),
+                                old,
+                                val,
+                            ));
+                            new_val
+                        };
+                        current_block.add_instruction(IRInstruction::FieldStore(
+                            base,
+                            offset,
+                            new_val.clone(),
+                            typ,
                         ));
+                        new_val
+                    }
+                    (Expression::ArrayAccess(aa), _) => {
+                        let (_, arr, idx) = self.array_element(aa, current_block, all_blocks);
                         if a.operator != AssignmentOp::Assign {
                             let old = self.new_temp();
                             current_block.add_instruction(IRInstruction::ArrayLoad(
@@ -490,22 +1651,12 @@ impl IRGenerator {
                                 idx.clone(),
                             ));
                             let new_val = self.new_temp();
-                            let instr = match a.operator {
-                                AssignmentOp::AddAssign => {
-                                    IRInstruction::Add(new_val.clone(), old, val)
-                                }
-                                AssignmentOp::SubAssign => {
-                                    IRInstruction::Sub(new_val.clone(), old, val)
-                                }
-                                AssignmentOp::MulAssign => {
-                                    IRInstruction::Mul(new_val.clone(), old, val)
-                                }
-                                AssignmentOp::DivAssign => {
-                                    IRInstruction::Div(new_val.clone(), old, val)
-                                }
-                                _ => unreachable!(),
-                            };
-                            current_block.add_instruction(instr);
+                            current_block.add_instruction(self.compound_instruction(
+                                a,
+                                new_val.clone(),
+                                old,
+                                val,
+                            ));
                             current_block.add_instruction(IRInstruction::ArrayStore(
                                 arr,
                                 idx,
@@ -513,9 +1664,14 @@ impl IRGenerator {
                             ));
                             return new_val;
                         }
+                        current_block.add_instruction(IRInstruction::ArrayStore(
+                            arr,
+                            idx,
+                            val.clone(),
+                        ));
                         val
                     }
-                    Expression::Unary(u) if u.operator == UnaryOp::Deref => {
+                    (Expression::Unary(u), _) if u.operator == UnaryOp::Deref => {
                         let ptr = self.generate_expression(&u.operand, current_block, all_blocks);
                         match a.operator {
                             AssignmentOp::Assign => {
@@ -527,22 +1683,12 @@ impl IRGenerator {
                                 current_block
                                     .add_instruction(IRInstruction::Load(old.clone(), ptr.clone()));
                                 let new_val = self.new_temp();
-                                let instr = match a.operator {
-                                    AssignmentOp::AddAssign => {
-                                        IRInstruction::Add(new_val.clone(), old, val.clone())
-                                    }
-                                    AssignmentOp::SubAssign => {
-                                        IRInstruction::Sub(new_val.clone(), old, val.clone())
-                                    }
-                                    AssignmentOp::MulAssign => {
-                                        IRInstruction::Mul(new_val.clone(), old, val.clone())
-                                    }
-                                    AssignmentOp::DivAssign => {
-                                        IRInstruction::Div(new_val.clone(), old, val.clone())
-                                    }
-                                    _ => unreachable!(),
-                                };
-                                current_block.add_instruction(instr);
+                                current_block.add_instruction(self.compound_instruction(
+                                    a,
+                                    new_val.clone(),
+                                    old,
+                                    val.clone(),
+                                ));
                                 current_block
                                     .add_instruction(IRInstruction::Store(ptr, new_val.clone()));
                             }
@@ -557,36 +1703,10 @@ impl IRGenerator {
                         match a.operator {
                             AssignmentOp::Assign => current_block
                                 .add_instruction(IRInstruction::Move(tgt.clone(), val.clone())),
-                            AssignmentOp::AddAssign => {
-                                let t = self.new_temp();
-                                current_block.add_instruction(IRInstruction::Add(
-                                    t.clone(),
-                                    tgt.clone(),
-                                    val.clone(),
-                                ));
-                                current_block.add_instruction(IRInstruction::Move(tgt.clone(), t));
-                            }
-                            AssignmentOp::SubAssign => {
-                                let t = self.new_temp();
-                                current_block.add_instruction(IRInstruction::Sub(
-                                    t.clone(),
-                                    tgt.clone(),
-                                    val.clone(),
-                                ));
-                                current_block.add_instruction(IRInstruction::Move(tgt.clone(), t));
-                            }
-                            AssignmentOp::MulAssign => {
-                                let t = self.new_temp();
-                                current_block.add_instruction(IRInstruction::Mul(
-                                    t.clone(),
-                                    tgt.clone(),
-                                    val.clone(),
-                                ));
-                                current_block.add_instruction(IRInstruction::Move(tgt.clone(), t));
-                            }
-                            AssignmentOp::DivAssign => {
+                            _ => {
                                 let t = self.new_temp();
-                                current_block.add_instruction(IRInstruction::Div(
+                                current_block.add_instruction(self.compound_instruction(
+                                    a,
                                     t.clone(),
                                     tgt.clone(),
                                     val.clone(),
@@ -599,19 +1719,219 @@ impl IRGenerator {
                 }
             }
             Expression::Call(c) => self.generate_call(c, current_block, all_blocks),
-            Expression::StructAccess(sa) => {
-                self.generate_expression(&sa.object, current_block, all_blocks)
-            }
+            Expression::StructAccess(sa) => match self.struct_field(sa, current_block, all_blocks) {
+                Some((base, IRType::Array(..), offset)) => {
+                    // Поле-массив в выражении дает адрес первого элемента
+                    let d = self.new_temp();
+                    if let Some(crate::semantic::type_system::Type::Array(inner, _)) =
+                        self.get_expression_type(expr)
+                    {
+                        self.pin_type(&d, &crate::semantic::type_system::Type::Pointer(inner));
+                    }
+                    current_block.add_instruction(IRInstruction::Gep(d.clone(), base, offset));
+                    d
+                }
+                Some((base, typ, offset)) => {
+                    let d = self.new_temp();
+                    current_block
+                        .add_instruction(IRInstruction::FieldLoad(d.clone(), base, offset, typ));
+                    d
+                }
+                None => self.generate_expression(&sa.object, current_block, all_blocks),
+            },
             Expression::Grouped(g) => self.generate_expression(&g.expr, current_block, all_blocks),
             Expression::ArrayAccess(aa) => {
-                let arr = self.generate_expression(&aa.array, current_block, all_blocks);
-                let idx = self.generate_expression(&aa.index, current_block, all_blocks);
+                // Неполная индексация многомерного массива дает адрес строки
+                if self.access_type(aa).is_some_and(|t| t.is_array()) {
+                    return self.array_element_address(aa, current_block, all_blocks);
+                }
+                let (_, arr, idx) = self.array_element(aa, current_block, all_blocks);
                 let d = self.new_temp();
                 current_block.add_instruction(IRInstruction::ArrayLoad(d.clone(), arr, idx));
                 d
             }
-            Expression::ArrayInitializer(_arr) => self.new_temp(),
+            Expression::ArrayInitializer(arr) => {
+                // Список вне объявления размещается во временном массиве на стеке
+                let typ = match self.get_expression_type(expr) {
+                    Some(t) => self.ir_type(&t),
+                    None => IRType::Array(Box::new(IRType::Int), arr.elements.len()),
+                };
+                self.temp_counter += 1;
+                let name = format!("__init{}", self.temp_counter);
+                self.current_locals.push((name.clone(), typ.clone()));
+                let array = Operand::Variable(name);
+                let size = Self::array_bytes(&typ);
+                current_block.add_instruction(IRInstruction::Alloca(array.clone(), size));
+                self.store_initializer(&array, &typ, arr, current_block, all_blocks);
+                array
+            }
+            Expression::Cast(c) => self.generate_cast(c, current_block, all_blocks),
+            Expression::Sizeof(s) => Operand::IntLiteral(self.sizeof_value(s) as i64),
+            Expression::Conditional(c) => self.generate_conditional(c, current_block, all_blocks),
+            Expression::Comma(c) => {
+                self.generate_expression(&c.left, current_block, all_blocks);
+                self.generate_expression(&c.right, current_block, all_blocks)
+            }
+        }
+    }
+
+    /// Явное приведение: преобразование между `f64` и целыми, сравнение
+    /// с нулем для `bool`, иначе перенос значения во временное значение
+    /// целевого типа (усечение целых выполняет кодогенератор)
+    fn generate_cast(
+        &mut self,
+        c: &CastExpr,
+        current_block: &mut BasicBlock,
+        all_blocks: &mut Vec<BasicBlock>,
+    ) -> Operand {
+        use crate::semantic::type_system::Type;
+        let value = self.generate_expression(&c.expr, current_block, all_blocks);
+        let from = self.get_expression_type(&c.expr);
+        let to = Type::from_ast(&c.target_type);
+        let from_float = matches!(from, Some(Type::Float));
+        let d = match (&from, &to) {
+            (_, Type::Void) => return value,
+            (Some(f), t) if f == t => return value,
+            (_, Type::Bool) if from_float => {
+                let d = self.new_temp();
+                current_block.add_instruction(IRInstruction::CmpNeF(
+                    d.clone(),
+                    value,
+                    Operand::FloatLiteral(0.0),
+                ));
+                d
+            }
+            (_, Type::Bool) => {
+                let d = self.new_temp();
+                current_block.add_instruction(IRInstruction::CmpNe(
+                    d.clone(),
+                    value,
+                    Operand::IntLiteral(0),
+                ));
+                d
+            }
+            (_, Type::Float) => {
+                let d = self.new_temp();
+                current_block.add_instruction(IRInstruction::IntToFloat(d.clone(), value));
+                d
+            }
+            _ if from_float => {
+                let d = self.new_temp();
+                current_block.add_instruction(IRInstruction::FloatToInt(d.clone(), value));
+                d
+            }
+            _ => {
+                let d = self.new_temp();
+                current_block.add_instruction(IRInstruction::Move(d.clone(), value));
+                d
+            }
+        };
+        self.pin_type(&d, &to);
+        d
+    }
+
+    /// Значение `sizeof` по раскладке типа; выражение-операнд не вычисляется
+    fn sizeof_value(&self, s: &SizeofExpr) -> usize {
+        let typ = match &s.operand {
+            SizeofOperand::Type(t) => crate::semantic::type_system::Type::from_ast(t),
+            SizeofOperand::Expr(e) => match self.get_expression_type(e) {
+                Some(t) => t,
+                None => return 8,
+            },
+        };
+        self.type_size(&typ)
+    }
+
+    /// Размер типа в байтах; размер структуры берется из ее раскладки
+    fn type_size(&self, typ: &crate::semantic::type_system::Type) -> usize {
+        use crate::semantic::type_system::Type;
+        match typ {
+            Type::Struct(name) => self.struct_layouts.get(name).map(|l| l.size).unwrap_or(0),
+            Type::Array(inner, count) => self.type_size(inner) * count,
+            t => t.size().unwrap_or(0),
+        }
+    }
+
+    /// Инструкция для арифметической, побитовой операции или сдвига
+    ///
+    /// Сдвиг вправо беззнакового значения логический, знакового - арифметический
+    fn binary_instruction(
+        op: BinaryOp,
+        unsigned: bool,
+        d: Operand,
+        l: Operand,
+        r: Operand,
+    ) -> IRInstruction {
+        match op {
+            BinaryOp::Add => IRInstruction::Add(d, l, r),
+            BinaryOp::Sub => IRInstruction::Sub(d, l, r),
+            BinaryOp::Mul => IRInstruction::Mul(d, l, r),
+            BinaryOp::Div => IRInstruction::Div(d, l, r),
+            BinaryOp::Mod => IRInstruction::Mod(d, l, r),
+            BinaryOp::BitAnd => IRInstruction::And(d, l, r),
+            BinaryOp::BitOr => IRInstruction::Or(d, l, r),
+            BinaryOp::BitXor => IRInstruction::Xor(d, l, r),
+            BinaryOp::Shl => IRInstruction::Shl(d, l, r),
+            BinaryOp::Shr if unsigned => IRInstruction::Shr(d, l, r),
+            BinaryOp::Shr => IRInstruction::Sar(d, l, r),
+            _ => unreachable!("{} не является арифметической операцией", op),
+        }
+    }
+
+    /// Вычисление нового значения для составного присваивания
+    fn compound_instruction(
+        &mut self,
+        a: &AssignmentExpr,
+        d: Operand,
+        old: Operand,
+        val: Operand,
+    ) -> IRInstruction {
+        let op = a.operator.binary_op().expect("составное присваивание");
+        let target = self.get_expression_type(&a.target);
+        let unsigned = match op {
+            BinaryOp::Shl | BinaryOp::Shr => target.as_ref().is_some_and(|t| t.is_unsigned()),
+            _ => self.is_unsigned_operation(&a.target, &a.value),
+        };
+        let result = match (target, self.get_expression_type(&a.value)) {
+            (Some(t), Some(v)) => {
+                TypeChecker::new().binary_result_type(&t, &v, BinaryOpType::from(&op))
+            }
+            _ => None,
+        };
+        self.pin_integer_type(&d, result);
+        Self::binary_instruction(op, unsigned, d, old, val)
+    }
+
+    /// Инкремент/декремент поля структуры
+    fn generate_field_update(
+        &mut self,
+        u: &UnaryExpr,
+        (base, typ, offset): (Operand, IRType, u32),
+        cb: &mut BasicBlock,
+    ) -> Option<Operand> {
+        let (is_inc, is_post) = match u.operator {
+            UnaryOp::PreIncrement => (true, false),
+            UnaryOp::PostIncrement => (true, true),
+            UnaryOp::PreDecrement => (false, false),
+            UnaryOp::PostDecrement => (false, true),
+            _ => return None,
+        };
+        let old = self.new_temp();
+        cb.add_instruction(IRInstruction::FieldLoad(
+            old.clone(),
+            base.clone(),
+            offset,
+            typ.clone(),
+        ));
+        let new_val = self.new_temp();
+        let one = Operand::IntLiteral(1);
+        if is_inc {
+            cb.add_instruction(IRInstruction::Add(new_val.clone(), old.clone(), one));
+        } else {
+            cb.add_instruction(IRInstruction::Sub(new_val.clone(), old.clone(), one));
         }
+        cb.add_instruction(IRInstruction::FieldStore(base, offset, new_val.clone(), typ));
+        Some(if is_post { old } else { new_val })
     }
 
     fn generate_short_circuit_and(
@@ -626,6 +1946,7 @@ impl IRGenerator {
         let ml = self.new_label();
         cb.add_instruction(IRInstruction::Move(r.clone(), Operand::IntLiteral(0)));
         cb.add_instruction(IRInstruction::JumpIfNot(left, ml.clone()));
+        cb.add_instruction(IRInstruction::Jump(el.clone()));
         let mut eb = BasicBlock::new(Self::label_to_string(&el));
         let right = self.generate_expression(&b.right, &mut eb, ab);
         eb.add_instruction(IRInstruction::Move(r.clone(), right));
@@ -648,6 +1969,7 @@ impl IRGenerator {
         let ml = self.new_label();
         cb.add_instruction(IRInstruction::Move(r.clone(), Operand::IntLiteral(1)));
         cb.add_instruction(IRInstruction::JumpIf(left, ml.clone()));
+        cb.add_instruction(IRInstruction::Jump(el.clone()));
         let mut eb = BasicBlock::new(Self::label_to_string(&el));
         let right = self.generate_expression(&b.right, &mut eb, ab);
         eb.add_instruction(IRInstruction::Move(r.clone(), right));
@@ -658,6 +1980,46 @@ impl IRGenerator {
         r
     }
 
+    /// `c ? a : b` вычисляет только выбранную ветвь: каждая ветвь в своем
+    /// блоке приводится к общему типу и пишет результат в одно временное
+    fn generate_conditional(
+        &mut self,
+        c: &ConditionalExpr,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> Operand {
+        let cond = self.generate_expression(&c.condition, cb, ab);
+        let then_type = self.get_expression_type(&c.then_expr);
+        let else_type = self.get_expression_type(&c.else_expr);
+        let result_type = match (&then_type, &else_type) {
+            (Some(t), Some(e)) => TypeChecker::new().conditional_result_type(t, e),
+            _ => None,
+        };
+        let r = self.new_temp();
+        if let Some(t) = &result_type {
+            self.pin_type(&r, t);
+        }
+        let tl = self.new_label();
+        let el = self.new_label();
+        let ml = self.new_label();
+        cb.add_instruction(IRInstruction::JumpIfNot(cond, el.clone()));
+        cb.add_instruction(IRInstruction::Jump(tl.clone()));
+        for (branch, typ, label) in [(&c.then_expr, then_type, tl), (&c.else_expr, else_type, el)] {
+            let mut bb = BasicBlock::new(Self::label_to_string(&label));
+            let value = self.generate_expression(branch, &mut bb, ab);
+            let mut value = self.decay_array(branch, value, &mut bb);
+            if let (Some(from), Some(to)) = (typ, &result_type) {
+                value = self.convert_type(value, &from, to, &mut bb);
+            }
+            bb.add_instruction(IRInstruction::Move(r.clone(), value));
+            bb.add_instruction(IRInstruction::Jump(ml.clone()));
+            ab.push(bb);
+        }
+        let old = std::mem::replace(cb, BasicBlock::new(Self::label_to_string(&ml)));
+        ab.push(old);
+        r
+    }
+
     fn generate_if_statement(
         &mut self,
         is: &IfStmt,
@@ -731,6 +2093,36 @@ impl IRGenerator {
         mb
     }
 
+    /// Тело выполняется хотя бы раз; continue переходит к проверке условия
+    fn generate_do_while_statement(
+        &mut self,
+        dw: &DoWhileStmt,
+        cb: &mut BasicBlock,
+        ab: &mut Vec<BasicBlock>,
+    ) -> BasicBlock {
+        let mut bodyb = self.create_block("do_body");
+        let mut condb = self.create_block("do_cond");
+        let mb = self.create_block("do_end");
+        let bl = Operand::Label(bodyb.label.clone());
+        let cl = Operand::Label(condb.label.clone());
+        let ml = Operand::Label(mb.label.clone());
+        self.break_labels.push(mb.label.clone());
+        self.continue_labels.push(condb.label.clone());
+        cb.add_instruction(IRInstruction::Jump(bl.clone()));
+
+        self.generate_statement(&dw.body, &mut bodyb, ab);
+        if !bodyb.is_terminator() {
+            bodyb.add_instruction(IRInstruction::Jump(cl));
+        }
+        ab.push(bodyb);
+
+        self.generate_conditional_jump(&dw.condition, bl, ml, &mut condb, ab);
+        ab.push(condb);
+        self.break_labels.pop();
+        self.continue_labels.pop();
+        mb
+    }
+
     fn generate_for_statement(
         &mut self,
         fs: &ForStmt,
@@ -786,14 +2178,42 @@ impl IRGenerator {
             _ => return self.new_temp(),
         };
         let mut args = Vec::new();
-        for (i, arg) in call.arguments.iter().enumerate() {
-            let av = self.generate_expression(arg, cb, ab);
-            cb.add_instruction(IRInstruction::Param(i as u32, av.clone()));
+        // Для структуры-результата первым аргументом идет адрес буфера,
+        // который и становится значением вызова
+        let result = match self.function_types.get(&fname) {
+            Some(IRType::Struct(_, size)) => {
+                let size = (*size).max(8) as u32;
+                let buffer = self.new_temp();
+                cb.add_instruction(IRInstruction::Alloca(buffer.clone(), size));
+                let ptr = self.new_temp();
+                cb.add_instruction(IRInstruction::AddrOf(ptr.clone(), buffer.clone()));
+                cb.add_instruction(IRInstruction::Param(0, ptr.clone()));
+                args.push(ptr);
+                Some(buffer)
+            }
+            _ => None,
+        };
+        for arg in &call.arguments {
+            // Структура передается адресом копии, чтобы запись в параметр
+            // не меняла аргумент
+            let av = match self.struct_operand(arg, cb, ab) {
+                Some((src, offset, layout)) => {
+                    let copy = self.new_temp();
+                    let size = layout.size.max(8) as u32;
+                    cb.add_instruction(IRInstruction::Alloca(copy.clone(), size));
+                    self.copy_struct((&copy, 0), (&src, offset), &layout, cb);
+                    let ptr = self.new_temp();
+                    cb.add_instruction(IRInstruction::AddrOf(ptr.clone(), copy));
+                    ptr
+                }
+                None => self.generate_expression(arg, cb, ab),
+            };
+            cb.add_instruction(IRInstruction::Param(args.len() as u32, av.clone()));
             args.push(av);
         }
         let d = self.new_temp();
         cb.add_instruction(IRInstruction::Call(d.clone(), Operand::Label(fname), args));
-        d
+        result.unwrap_or(d)
     }
 
     fn optimize_counted_loop(
@@ -869,14 +2289,14 @@ impl IRGenerator {
         self.continue_labels.push(condb.label.clone());
         cb.add_instruction(IRInstruction::Move(
             Operand::Variable(lv.to_string()),
-            Operand::IntLiteral(sv),
+            Operand::IntLiteral(sv as i64),
         ));
         cb.add_instruction(IRInstruction::Jump(cl.clone()));
         let ct = self.new_temp();
         condb.add_instruction(IRInstruction::CmpLt(
             ct.clone(),
             Operand::Variable(lv.to_string()),
-            Operand::IntLiteral(ev),
+            Operand::IntLiteral(ev as i64),
         ));
         condb.add_instruction(IRInstruction::JumpIfNot(ct, ml.clone()));
         condb.add_instruction(IRInstruction::Jump(bl.clone()));
@@ -898,10 +2318,16 @@ impl IRGenerator {
 
     fn generate_literal(&self, lit: &Literal) -> Operand {
         match &lit.value {
-            LiteralValue::Int(v) => Operand::IntLiteral(*v),
+            LiteralValue::Int(v) => Operand::IntLiteral(*v as i64),
+            LiteralValue::Long(v) => Operand::IntLiteral(*v),
+            LiteralValue::UInt(v) => Operand::IntLiteral(*v as i64),
+            // Значения выше `i64::MAX` хранятся в дополнительном коде
+            LiteralValue::ULong(v) => Operand::IntLiteral(*v as i64),
             LiteralValue::Float(v) => Operand::FloatLiteral(*v),
             LiteralValue::Bool(v) => Operand::BoolLiteral(*v),
             LiteralValue::String(v) => Operand::StringLiteral(v.clone()),
+            LiteralValue::Char(v) => Operand::IntLiteral(*v as i64),
+            LiteralValue::Null => Operand::IntLiteral(0),
         }
     }
 
@@ -909,6 +2335,9 @@ impl IRGenerator {
         if let Some(t) = self.var_to_temp.get(&ident.name) {
             return Operand::Temporary(t.clone());
         }
+        if let Some(value) = self.enum_constant(&ident.name) {
+            return Operand::IntLiteral(value);
+        }
         if let Some(s) = self.symbol_table.lookup(&ident.name) {
             if let crate::semantic::type_system::Type::Function { .. } = &s.typ {
                 return Operand::Label(ident.name.clone());
@@ -917,6 +2346,19 @@ impl IRGenerator {
         Operand::Variable(ident.name.clone())
     }
 
+    /// Значение константы перечисления, если имя не перекрыто локальной
+    /// переменной или параметром
+    fn enum_constant(&self, name: &str) -> Option<i64> {
+        let mut locals = self.current_locals.iter().chain(&self.current_params);
+        if locals.any(|(n, _)| n == name) {
+            return None;
+        }
+        self.symbol_table
+            .lookup(name)
+            .filter(|s| s.kind == SymbolKind::EnumConstant)
+            .and_then(|s| s.const_value)
+    }
+
     fn generate_switch(
         &mut self,
         ss: &SwitchStmt,
@@ -929,7 +2371,7 @@ impl IRGenerator {
         let mut case_blocks: Vec<BasicBlock> = Vec::new();
         for case in ss.cases.iter() {
             let cr = self.new_temp();
-            let lo = self.generate_literal(&case.value);
+            let lo = self.generate_expression(&case.value, cb, ab);
             cb.add_instruction(IRInstruction::CmpEq(cr.clone(), ev.clone(), lo));
             let mut caseb = self.create_block("case");
             let case_l = Operand::Label(caseb.label.clone());
@@ -981,8 +2423,13 @@ impl IRGenerator {
                 let left = self.generate_expression(&b.left, current_block, all_blocks);
                 let right = self.generate_expression(&b.right, current_block, all_blocks);
                 let is_float = self.is_float_operand(&b.left) || self.is_float_operand(&b.right);
+                let unsigned = self.is_unsigned_operation(&b.left, &b.right);
 
                 let (cmp_instr, jcc_true) = match (b.operator, is_float) {
+                    (BinaryOp::Gt, false) if unsigned => ("cmp", "ja"),
+                    (BinaryOp::Lt, false) if unsigned => ("cmp", "jb"),
+                    (BinaryOp::Ge, false) if unsigned => ("cmp", "jae"),
+                    (BinaryOp::Le, false) if unsigned => ("cmp", "jbe"),
                     (BinaryOp::Gt, false) => ("cmp", "jg"),
                     (BinaryOp::Gt, true) => ("ucomisd", "ja"),
                     (BinaryOp::Lt, false) => ("cmp", "jl"),
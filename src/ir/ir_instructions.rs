@@ -7,12 +7,16 @@ use std::hash::{Hash, Hasher};
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum IRType {
     Int,
+    Long,
+    UInt,
+    ULong,
     Float,
     Bool,
     Void,
     String,
     Char,
-    Struct(String),
+    /// Структура: имя и размер в байтах
+    Struct(String, usize),
     Pointer(Box<IRType>),
     Array(Box<IRType>, usize),
     Unknown,
@@ -22,34 +26,76 @@ impl fmt::Display for IRType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             IRType::Int => write!(f, "i32"),
+            IRType::Long => write!(f, "i64"),
+            IRType::UInt => write!(f, "u32"),
+            IRType::ULong => write!(f, "u64"),
             IRType::Float => write!(f, "f64"),
             IRType::Bool => write!(f, "bool"),
             IRType::Void => write!(f, "void"),
             IRType::String => write!(f, "string"),
             IRType::Char => write!(f, "i8"),
-            IRType::Struct(name) => write!(f, "struct {}", name),
+            IRType::Struct(name, _) => write!(f, "struct {}", name),
             IRType::Pointer(inner) => write!(f, "{}*", inner),
-            IRType::Array(inner, size) => write!(f, "{}[{}]", inner, size),
+            IRType::Array(..) => {
+                let mut element = self;
+                let mut dims = String::new();
+                while let IRType::Array(inner, size) = element {
+                    dims.push_str(&format!("[{}]", size));
+                    element = inner;
+                }
+                write!(f, "{}{}", element, dims)
+            }
             IRType::Unknown => write!(f, "?"),
         }
     }
 }
 
 impl IRType {
+    /// Размер в памяти; совпадает с `Type::size`
     pub fn size(&self) -> usize {
         match self {
-            IRType::Int => 4,
+            IRType::Int | IRType::UInt => 4,
+            IRType::Long | IRType::ULong => 8,
             IRType::Float => 8,
             IRType::Bool => 1,
             IRType::Void => 0,
             IRType::String => 8,
             IRType::Char => 1,
-            IRType::Struct(_) => 0,
+            IRType::Struct(_, size) => *size,
             IRType::Pointer(_) => 8,
             IRType::Array(inner, count) => inner.size() * count,
             IRType::Unknown => 0,
         }
     }
+
+    /// Целый тип, включая `char`
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            IRType::Int | IRType::Long | IRType::UInt | IRType::ULong | IRType::Char
+        )
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, IRType::UInt | IRType::ULong)
+    }
+}
+
+/// Раскладка структуры в памяти
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLayout {
+    pub name: String,
+    /// Поля в порядке объявления: (имя, тип, смещение)
+    pub fields: Vec<(String, IRType, usize)>,
+    pub size: usize,
+    pub alignment: usize,
+}
+
+impl StructLayout {
+    /// Ищет поле по имени
+    pub fn field(&self, name: &str) -> Option<&(String, IRType, usize)> {
+        self.fields.iter().find(|(n, _, _)| n == name)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -72,7 +118,7 @@ impl TypedOperand {
 pub enum Operand {
     Temporary(String),
     Variable(String),
-    IntLiteral(i32),
+    IntLiteral(i64),
     FloatLiteral(f64),
     BoolLiteral(bool),
     StringLiteral(String),
@@ -157,6 +203,11 @@ pub enum IRInstruction {
     Or(Operand, Operand, Operand),
     Not(Operand, Operand),
     Xor(Operand, Operand, Operand),
+    Shl(Operand, Operand, Operand),
+    /// Логический сдвиг вправо
+    Shr(Operand, Operand, Operand),
+    /// Арифметический сдвиг вправо
+    Sar(Operand, Operand, Operand),
 
     CmpEq(Operand, Operand, Operand),
     CmpNe(Operand, Operand, Operand),
@@ -185,6 +236,11 @@ pub enum IRInstruction {
     ArrayLoad(Operand, Operand, Operand),
     ArrayStore(Operand, Operand, Operand),
 
+    /// Загрузка поля: dest, база структуры, смещение, тип поля
+    FieldLoad(Operand, Operand, u32, IRType),
+    /// Запись поля: база структуры, смещение, значение, тип поля
+    FieldStore(Operand, u32, Operand, IRType),
+
     Jump(Operand),
     JumpIf(Operand, Operand),
     JumpIfNot(Operand, Operand),
@@ -217,6 +273,9 @@ impl IRInstruction {
             IRInstruction::Or(_, s1, s2) => vec![s1, s2],
             IRInstruction::Not(_, s) => vec![s],
             IRInstruction::Xor(_, s1, s2) => vec![s1, s2],
+            IRInstruction::Shl(_, s1, s2) => vec![s1, s2],
+            IRInstruction::Shr(_, s1, s2) => vec![s1, s2],
+            IRInstruction::Sar(_, s1, s2) => vec![s1, s2],
             IRInstruction::CmpEq(_, s1, s2) => vec![s1, s2],
             IRInstruction::CmpNe(_, s1, s2) => vec![s1, s2],
             IRInstruction::CmpLt(_, s1, s2) => vec![s1, s2],
@@ -239,6 +298,8 @@ impl IRInstruction {
             IRInstruction::Gep(_, b, _) => vec![b],
             IRInstruction::ArrayLoad(_, base, index) => vec![base, index],
             IRInstruction::ArrayStore(base, index, val) => vec![base, index, val],
+            IRInstruction::FieldLoad(_, base, _, _) => vec![base],
+            IRInstruction::FieldStore(base, _, val, _) => vec![base, val],
             IRInstruction::Jump(l) => vec![l],
             IRInstruction::JumpIf(c, l) => vec![c, l],
             IRInstruction::JumpIfNot(c, l) => vec![c, l],
@@ -273,6 +334,9 @@ impl IRInstruction {
             | IRInstruction::Or(d, _, _)
             | IRInstruction::Not(d, _)
             | IRInstruction::Xor(d, _, _)
+            | IRInstruction::Shl(d, _, _)
+            | IRInstruction::Shr(d, _, _)
+            | IRInstruction::Sar(d, _, _)
             | IRInstruction::CmpEq(d, _, _)
             | IRInstruction::CmpNe(d, _, _)
             | IRInstruction::CmpLt(d, _, _)
@@ -295,7 +359,8 @@ impl IRInstruction {
             | IRInstruction::Move(d, _)
             | IRInstruction::IntToFloat(d, _)
             | IRInstruction::FloatToInt(d, _)
-            | IRInstruction::ArrayLoad(d, _, _) => {
+            | IRInstruction::ArrayLoad(d, _, _)
+            | IRInstruction::FieldLoad(d, _, _, _) => {
                 ops.push(d);
             }
             IRInstruction::Store(_, _) => {}
@@ -313,6 +378,71 @@ impl IRInstruction {
         ops
     }
 
+    /// Операнд-приемник инструкции (определяемое значение)
+    pub fn dest(&self) -> Option<&Operand> {
+        match self {
+            IRInstruction::Add(d, _, _)
+            | IRInstruction::Sub(d, _, _)
+            | IRInstruction::Mul(d, _, _)
+            | IRInstruction::Div(d, _, _)
+            | IRInstruction::Mod(d, _, _)
+            | IRInstruction::Neg(d, _)
+            | IRInstruction::And(d, _, _)
+            | IRInstruction::Or(d, _, _)
+            | IRInstruction::Not(d, _)
+            | IRInstruction::Xor(d, _, _)
+            | IRInstruction::Shl(d, _, _)
+            | IRInstruction::Shr(d, _, _)
+            | IRInstruction::Sar(d, _, _)
+            | IRInstruction::CmpEq(d, _, _)
+            | IRInstruction::CmpNe(d, _, _)
+            | IRInstruction::CmpLt(d, _, _)
+            | IRInstruction::CmpLe(d, _, _)
+            | IRInstruction::CmpGt(d, _, _)
+            | IRInstruction::CmpGe(d, _, _)
+            | IRInstruction::CmpEqF(d, _, _)
+            | IRInstruction::CmpNeF(d, _, _)
+            | IRInstruction::CmpLtF(d, _, _)
+            | IRInstruction::CmpLeF(d, _, _)
+            | IRInstruction::CmpGtF(d, _, _)
+            | IRInstruction::CmpGeF(d, _, _)
+            | IRInstruction::CmpLtU(d, _, _)
+            | IRInstruction::CmpLeU(d, _, _)
+            | IRInstruction::CmpGtU(d, _, _)
+            | IRInstruction::CmpGeU(d, _, _)
+            | IRInstruction::Load(d, _)
+            | IRInstruction::Alloca(d, _)
+            | IRInstruction::Gep(d, _, _)
+            | IRInstruction::Move(d, _)
+            | IRInstruction::IntToFloat(d, _)
+            | IRInstruction::FloatToInt(d, _)
+            | IRInstruction::ArrayLoad(d, _, _)
+            | IRInstruction::FieldLoad(d, _, _, _)
+            | IRInstruction::Phi(d, _)
+            | IRInstruction::Call(d, _, _)
+            | IRInstruction::AddrOf(d, _) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Метки, на которые инструкция может передать управление
+    pub fn branch_targets(&self) -> Vec<&str> {
+        let labels: Vec<&Operand> = match self {
+            IRInstruction::Jump(l) | IRInstruction::JumpIf(_, l) | IRInstruction::JumpIfNot(_, l) => {
+                vec![l]
+            }
+            IRInstruction::CmpJmp(_, _, tl, fl, _, _, _) => vec![tl, fl],
+            _ => vec![],
+        };
+        labels
+            .into_iter()
+            .filter_map(|l| match l {
+                Operand::Label(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn is_terminator(&self) -> bool {
         matches!(
             self,
@@ -353,6 +483,7 @@ impl IRInstruction {
             IRInstruction::IntToFloat(_, _) => Some(IRType::Float),
             IRInstruction::FloatToInt(_, _) => Some(IRType::Int),
             IRInstruction::ArrayLoad(_, _, _) => Some(IRType::Int),
+            IRInstruction::FieldLoad(_, _, _, typ) => Some(typ.clone()),
             IRInstruction::Load(d, _) => {
                 if let Operand::Temporary(name) = d {
                     operand_types.get(name).cloned()
@@ -385,6 +516,9 @@ impl fmt::Display for IRInstruction {
             IRInstruction::Or(d, s1, s2) => write!(f, "{} = OR {}, {}", d, s1, s2),
             IRInstruction::Not(d, s) => write!(f, "{} = NOT {}", d, s),
             IRInstruction::Xor(d, s1, s2) => write!(f, "{} = XOR {}, {}", d, s1, s2),
+            IRInstruction::Shl(d, s1, s2) => write!(f, "{} = SHL {}, {}", d, s1, s2),
+            IRInstruction::Shr(d, s1, s2) => write!(f, "{} = SHR {}, {}", d, s1, s2),
+            IRInstruction::Sar(d, s1, s2) => write!(f, "{} = SAR {}, {}", d, s1, s2),
             IRInstruction::CmpEq(d, s1, s2) => write!(f, "{} = CMP_EQ {}, {}", d, s1, s2),
             IRInstruction::CmpNe(d, s1, s2) => write!(f, "{} = CMP_NE {}, {}", d, s1, s2),
             IRInstruction::CmpLt(d, s1, s2) => write!(f, "{} = CMP_LT {}, {}", d, s1, s2),
@@ -411,6 +545,12 @@ impl fmt::Display for IRInstruction {
             IRInstruction::ArrayStore(base, index, val) => {
                 write!(f, "ARRAY_STORE {}, {}, {}", base, index, val)
             }
+            IRInstruction::FieldLoad(d, base, offset, typ) => {
+                write!(f, "{} = FIELD_LOAD {}, {} ({})", d, base, offset, typ)
+            }
+            IRInstruction::FieldStore(base, offset, val, typ) => {
+                write!(f, "FIELD_STORE {}, {}, {} ({})", base, offset, val, typ)
+            }
             IRInstruction::Jump(l) => write!(f, "JUMP {}", l),
             IRInstruction::JumpIf(c, l) => write!(f, "JUMP_IF {}, {}", c, l),
             IRInstruction::JumpIfNot(c, l) => write!(f, "JUMP_IF_NOT {}, {}", c, l),
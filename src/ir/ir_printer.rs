@@ -14,10 +14,27 @@ impl IRPrinter {
         output.push_str(&format!("# Program: MiniC IR\n"));
         output.push_str(&format!("# Generated: {}\n\n", Self::current_time()));
 
+        for layout in &program.structs {
+            output.push_str(&format!(
+                ".struct {} (size {}, align {})\n",
+                layout.name, layout.size, layout.alignment
+            ));
+            for (name, typ, offset) in &layout.fields {
+                output.push_str(&format!("  +{}: {} {}\n", offset, typ, name));
+            }
+            output.push('\n');
+        }
+
         if !program.globals.is_empty() {
             output.push_str(".global\n");
             for (name, typ) in &program.globals {
-                output.push_str(&format!("  {}: {}\n", name, typ));
+                match program.global_inits.get(name) {
+                    Some(values) => {
+                        let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                        output.push_str(&format!("  {}: {} = {}\n", name, typ, values.join(", ")));
+                    }
+                    None => output.push_str(&format!("  {}: {}\n", name, typ)),
+                }
             }
             output.push_str("\n");
         }
@@ -150,6 +167,7 @@ impl IRPrinter {
             return_type: String,
             parameters: Vec<JSONParam>,
             locals: Vec<JSONVar>,
+            temporaries: Vec<JSONVar>,
             blocks: Vec<JSONBlock>,
             entry_block: String,
             exit_blocks: Vec<String>,
@@ -196,13 +214,13 @@ impl IRPrinter {
             .iter()
             .map(|func| JSONFunction {
                 name: func.name.clone(),
-                return_type: func.return_type.clone(),
+                return_type: func.return_type.to_string(),
                 parameters: func
                     .parameters
                     .iter()
                     .map(|(name, typ)| JSONParam {
                         name: name.clone(),
-                        typ: typ.clone(),
+                        typ: typ.to_string(),
                     })
                     .collect(),
                 locals: func
@@ -210,7 +228,15 @@ impl IRPrinter {
                     .iter()
                     .map(|(name, typ)| JSONVar {
                         name: name.clone(),
-                        typ: typ.clone(),
+                        typ: typ.to_string(),
+                    })
+                    .collect(),
+                temporaries: func
+                    .temporaries
+                    .iter()
+                    .map(|t| JSONVar {
+                        name: t.operand.to_string(),
+                        typ: t.typ.to_string(),
                     })
                     .collect(),
                 blocks: func
@@ -237,7 +263,7 @@ impl IRPrinter {
             .iter()
             .map(|(name, typ)| JSONGlobal {
                 name: name.clone(),
-                typ: typ.clone(),
+                typ: typ.to_string(),
             })
             .collect();
 
@@ -11,7 +11,7 @@ pub mod ir_printer;
 pub mod peephole_optimizer;
 
 pub use basic_block::*;
-pub use control_flow::ControlFlowGraph;
+pub use control_flow::{build_cfg, compute_liveness, reverse_postorder, ControlFlowGraph, Liveness};
 pub use ir_generator::IRGenerator;
 pub use ir_instructions::*;
 pub use ir_printer::IRPrinter;
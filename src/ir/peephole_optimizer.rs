@@ -89,8 +89,23 @@ impl PeepholeOptimizer {
             }
         }
 
+        // Беззнаковость результата определяет, как сворачивается деление
+        let unsigned: HashSet<String> = func
+            .parameters
+            .iter()
+            .chain(func.locals.iter())
+            .filter(|(_, t)| t.is_unsigned())
+            .map(|(n, _)| n.clone())
+            .chain(func.temporaries.iter().filter(|t| t.typ.is_unsigned()).filter_map(|t| {
+                match &t.operand {
+                    Operand::Temporary(n) => Some(n.clone()),
+                    _ => None,
+                }
+            }))
+            .collect();
+
         for block in func.blocks.values_mut() {
-            let block_report = Self::optimize_block(block, &used_vars, &used_temps);
+            let block_report = Self::optimize_block(block, &used_vars, &used_temps, &unsigned);
             if block_report.changes_made > 0 {
                 report.add(&block_report);
             }
@@ -118,12 +133,12 @@ impl PeepholeOptimizer {
                 .replace("while_cond", "while_end")
                 .replace("for_cond", "for_end");
 
-            let mut invoke_instructions: Vec<(usize, IRInstruction)> = Vec::new();
+            let mut invoke_instructions: Vec<(usize, IRInstruction, Option<usize>)> = Vec::new();
 
             if let Some(body_block) = func.blocks.get(&body_label).cloned() {
                 for (i, instr) in body_block.instructions.iter().enumerate() {
                     if Self::is_loop_invariant(instr, cond_label, &func.blocks) {
-                        invoke_instructions.push((i, instr.clone()));
+                        invoke_instructions.push((i, instr.clone(), body_block.line_at(i)));
                     }
                 }
             }
@@ -148,23 +163,30 @@ impl PeepholeOptimizer {
                         .len()
                         .saturating_sub(if target_block.is_terminator() { 1 } else { 0 });
 
-                    for (_, instr) in invoke_instructions.iter().rev() {
-                        target_block.instructions.insert(insert_pos, instr.clone());
+                    let mut existing = target_block.take_instructions();
+                    let tail = existing.split_off(insert_pos);
+                    for (instr, line) in existing {
+                        target_block.add_instruction_at(instr, line);
+                    }
+                    for (_, instr, line) in &invoke_instructions {
+                        target_block.add_instruction_at(instr.clone(), *line);
                         report.instructions_added += 1;
                         report.licm_moved += 1;
                     }
+                    for (instr, line) in tail {
+                        target_block.add_instruction_at(instr, line);
+                    }
                 }
 
                 if let Some(body_block) = func.blocks.get_mut(&body_label) {
                     let indices: HashSet<usize> =
-                        invoke_instructions.iter().map(|(i, _)| *i).collect();
-                    body_block.instructions = body_block
-                        .instructions
-                        .iter()
-                        .enumerate()
-                        .filter(|(i, _)| !indices.contains(i))
-                        .map(|(_, instr)| instr.clone())
-                        .collect();
+                        invoke_instructions.iter().map(|(i, _, _)| *i).collect();
+                    let body = body_block.take_instructions();
+                    for (i, (instr, line)) in body.into_iter().enumerate() {
+                        if !indices.contains(&i) {
+                            body_block.add_instruction_at(instr, line);
+                        }
+                    }
                     report.instructions_removed += invoke_instructions.len();
                 }
 
@@ -190,7 +212,9 @@ impl PeepholeOptimizer {
             | IRInstruction::Call(_, _, _)
             | IRInstruction::Param(_, _)
             | IRInstruction::Return(_)
-            | IRInstruction::Load(_, _) => return false,
+            | IRInstruction::Load(_, _)
+            | IRInstruction::FieldLoad(_, _, _, _)
+            | IRInstruction::FieldStore(_, _, _, _) => return false,
             _ => {}
         }
 
@@ -237,6 +261,9 @@ impl PeepholeOptimizer {
             | IRInstruction::Or(dest, _, _)
             | IRInstruction::Not(dest, _)
             | IRInstruction::Xor(dest, _, _)
+            | IRInstruction::Shl(dest, _, _)
+            | IRInstruction::Shr(dest, _, _)
+            | IRInstruction::Sar(dest, _, _)
             | IRInstruction::Load(dest, _)
             | IRInstruction::IntToFloat(dest, _)
             | IRInstruction::FloatToInt(dest, _) => {
@@ -250,32 +277,41 @@ impl PeepholeOptimizer {
         }
     }
 
-    fn optimize_block(block: &mut super::basic_block::BasicBlock, used_vars: &HashSet<String>, used_temps: &HashSet<String>) -> OptimizationReport {
+    fn optimize_block(
+        block: &mut super::basic_block::BasicBlock,
+        used_vars: &HashSet<String>,
+        used_temps: &HashSet<String>,
+        unsigned: &HashSet<String>,
+    ) -> OptimizationReport {
         let mut report = OptimizationReport::new();
         let mut new_instructions = Vec::new();
 
-        for instr in &block.instructions {
-            let (folded, applied) = Self::fold_constants(instr);
+        for (instr, line) in block.take_instructions() {
+            let is_unsigned = match instr.dest() {
+                Some(Operand::Variable(n)) | Some(Operand::Temporary(n)) => unsigned.contains(n),
+                _ => false,
+            };
+            let (folded, applied) = Self::fold_constants(&instr, is_unsigned);
             if applied {
-                new_instructions.push(folded);
+                new_instructions.push((folded, line));
                 report.changes_made += 1;
                 report.simplifications_applied += 1;
                 continue;
             }
 
-            let (simplified, applied) = Self::algebraic_simplify(instr);
+            let (simplified, applied) = Self::algebraic_simplify(&instr);
             if applied {
-                new_instructions.push(simplified);
+                new_instructions.push((simplified, line));
                 report.changes_made += 1;
                 report.simplifications_applied += 1;
                 continue;
             }
 
-            new_instructions.push(instr.clone());
+            new_instructions.push((instr, line));
         }
 
         let mut after_dead = Vec::new();
-        for instr in new_instructions {
+        for (instr, line) in new_instructions {
             let keep = match &instr {
                 IRInstruction::Store(_, _)
                 | IRInstruction::Param(_, _)
@@ -296,6 +332,9 @@ impl PeepholeOptimizer {
                 | IRInstruction::Or(dest, _, _)
                 | IRInstruction::Not(dest, _)
                 | IRInstruction::Xor(dest, _, _)
+            | IRInstruction::Shl(dest, _, _)
+            | IRInstruction::Shr(dest, _, _)
+            | IRInstruction::Sar(dest, _, _)
                 | IRInstruction::CmpEq(dest, _, _)
                 | IRInstruction::CmpNe(dest, _, _)
                 | IRInstruction::CmpLt(dest, _, _)
@@ -326,7 +365,7 @@ impl PeepholeOptimizer {
             };
 
             if keep {
-                after_dead.push(instr);
+                after_dead.push((instr, line));
             } else {
                 report.changes_made += 1;
                 report.dead_code_removed += 1;
@@ -334,8 +373,7 @@ impl PeepholeOptimizer {
             }
         }
 
-        let mut final_instructions = Vec::new();
-        for instr in after_dead {
+        for (instr, line) in after_dead {
             if let IRInstruction::Move(dest, src) = &instr {
                 if dest == src {
                     report.changes_made += 1;
@@ -343,21 +381,20 @@ impl PeepholeOptimizer {
                     continue;
                 }
             }
-            final_instructions.push(instr);
+            block.add_instruction_at(instr, line);
         }
 
-        block.instructions = final_instructions;
         report
     }
 
-    fn fold_constants(instr: &IRInstruction) -> (IRInstruction, bool) {
+    /// Сворачивает операцию над литералами; деление с беззнаковым
+    /// результатом выполняется над `u64`, как `div` в кодогенераторе
+    fn fold_constants(instr: &IRInstruction, unsigned: bool) -> (IRInstruction, bool) {
         match instr {
             IRInstruction::Add(dest, left, right) => {
                 if let (Operand::IntLiteral(l), Operand::IntLiteral(r)) = (left, right) {
-                    return (
-                        IRInstruction::Move(dest.clone(), Operand::IntLiteral(l + r)),
-                        true,
-                    );
+                    let folded = Operand::IntLiteral(l.wrapping_add(*r));
+                    return (IRInstruction::Move(dest.clone(), folded), true);
                 }
                 if let (Operand::FloatLiteral(l), Operand::FloatLiteral(r)) = (left, right) {
                     return (
@@ -368,10 +405,8 @@ impl PeepholeOptimizer {
             }
             IRInstruction::Sub(dest, left, right) => {
                 if let (Operand::IntLiteral(l), Operand::IntLiteral(r)) = (left, right) {
-                    return (
-                        IRInstruction::Move(dest.clone(), Operand::IntLiteral(l - r)),
-                        true,
-                    );
+                    let folded = Operand::IntLiteral(l.wrapping_sub(*r));
+                    return (IRInstruction::Move(dest.clone(), folded), true);
                 }
                 if let (Operand::FloatLiteral(l), Operand::FloatLiteral(r)) = (left, right) {
                     return (
@@ -382,10 +417,8 @@ impl PeepholeOptimizer {
             }
             IRInstruction::Mul(dest, left, right) => {
                 if let (Operand::IntLiteral(l), Operand::IntLiteral(r)) = (left, right) {
-                    return (
-                        IRInstruction::Move(dest.clone(), Operand::IntLiteral(l * r)),
-                        true,
-                    );
+                    let folded = Operand::IntLiteral(l.wrapping_mul(*r));
+                    return (IRInstruction::Move(dest.clone(), folded), true);
                 }
                 if let (Operand::FloatLiteral(l), Operand::FloatLiteral(r)) = (left, right) {
                     return (
@@ -397,10 +430,13 @@ impl PeepholeOptimizer {
             IRInstruction::Div(dest, left, right) => {
                 if let (Operand::IntLiteral(l), Operand::IntLiteral(r)) = (left, right) {
                     if *r != 0 {
-                        return (
-                            IRInstruction::Move(dest.clone(), Operand::IntLiteral(l / r)),
-                            true,
-                        );
+                        let value = if unsigned {
+                            (*l as u64 / *r as u64) as i64
+                        } else {
+                            l.wrapping_div(*r)
+                        };
+                        let folded = Operand::IntLiteral(value);
+                        return (IRInstruction::Move(dest.clone(), folded), true);
                     }
                 }
                 if let (Operand::FloatLiteral(l), Operand::FloatLiteral(r)) = (left, right) {
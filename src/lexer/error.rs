@@ -74,15 +74,17 @@ pub enum LexerError {
     /// Некорректный формат числового литерала.
     ///
     /// Возникает в следующих случаях:
-    /// 1. Число выходит за допустимый диапазон (для i32)
-    /// 2. Неправильный формат числа с плавающей точкой
-    /// 3. Пустая дробная часть (например, `123.`)
+    /// 1. Неправильный формат числа с плавающей точкой
+    /// 2. Пустая дробная часть (например, `123.`)
+    /// 3. Цифра вне основания системы счисления или неверный разделитель `_`
+    /// 4. Неизвестный суффикс
     ///
     /// # Примеры
     ///
-    /// - `9999999999` (слишком большое для i32)
     /// - `123.` (отсутствует дробная часть)
-    /// - `12.34.56` (две точки)
+    /// - `0b102` (цифра 2 в двоичной записи)
+    /// - `1__000` (двойной разделитель)
+    /// - `10px` (неизвестный суффикс)
     #[error("{position}: Некорректный числовой формат: '{lexeme}'")]
     InvalidNumber {
         /// Позиция начала числа
@@ -91,6 +93,28 @@ pub enum LexerError {
         lexeme: String,
     },
 
+    /// Значение целого литерала не помещается в его тип.
+    ///
+    /// Тип определяется суффиксом литерала; сообщение содержит
+    /// диапазон позиций литерала от первого до последнего символа.
+    ///
+    /// # Примеры
+    ///
+    /// - `2147483648` (больше максимума `int`)
+    /// - `0x1_0000_0000` (больше максимума `unsigned int`)
+    /// - `4294967296U` (больше максимума `unsigned int`)
+    #[error("{position}-{end}: Целая константа '{lexeme}' не помещается в тип {target}")]
+    IntegerOverflow {
+        /// Позиция первого символа литерала
+        position: Position,
+        /// Позиция последнего символа литерала
+        end: Position,
+        /// Лексема литерала
+        lexeme: String,
+        /// Тип, в который не помещается значение
+        target: String,
+    },
+
     /// Идентификатор превысил максимально допустимую длину.
     ///
     /// Согласно спецификации языка, максимальная длина идентификатора
@@ -123,6 +147,24 @@ pub enum LexerError {
         position: Position,
     },
 
+    /// Некорректный символьный литерал.
+    ///
+    /// Возникает, когда между апострофами нет ровно одного ASCII-символа
+    /// или литерал не завершен закрывающим апострофом.
+    ///
+    /// # Примеры
+    ///
+    /// - `''` (пустой литерал)
+    /// - `'ab'` (больше одного символа)
+    /// - `'a` (нет закрывающего апострофа)
+    #[error("{position}: Некорректный символьный литерал: '{lexeme}'")]
+    InvalidCharLiteral {
+        /// Позиция начала литерала
+        position: Position,
+        /// Прочитанная часть литерала
+        lexeme: String,
+    },
+
     /// Некорректная escape-последовательность в строковом литерале.
     ///
     /// Возникает, когда после обратного слеша следует символ,
@@ -152,6 +194,26 @@ pub enum LexerError {
     },
 }
 
+impl LexerError {
+    /// Возвращает все позиции ошибки для изменения, начиная с основной.
+    ///
+    /// Используется, чтобы перевести позиции в результате препроцессора
+    /// в места исходных файлов.
+    pub fn positions_mut(&mut self) -> Vec<&mut Position> {
+        match self {
+            LexerError::IntegerOverflow { position, end, .. } => vec![position, end],
+            LexerError::UnexpectedCharacter { position, .. }
+            | LexerError::UnterminatedString { position }
+            | LexerError::InvalidNumber { position, .. }
+            | LexerError::IdentifierTooLong { position }
+            | LexerError::UnterminatedComment { position }
+            | LexerError::InvalidCharLiteral { position, .. }
+            | LexerError::InvalidEscapeSequence { position, .. }
+            | LexerError::EmptyInput { position } => vec![position],
+        }
+    }
+}
+
 /// Тип-алиас для результатов лексического анализа.
 ///
 /// Упрощает сигнатуры функций, возвращающих результат с возможной ошибкой.
@@ -308,8 +370,10 @@ impl LexerErrorExt for LexerError {
             LexerError::UnexpectedCharacter { position, .. } => *position,
             LexerError::UnterminatedString { position } => *position,
             LexerError::InvalidNumber { position, .. } => *position,
+            LexerError::IntegerOverflow { position, .. } => *position,
             LexerError::IdentifierTooLong { position } => *position,
             LexerError::UnterminatedComment { position } => *position,
+            LexerError::InvalidCharLiteral { position, .. } => *position,
             LexerError::InvalidEscapeSequence { position, .. } => *position,
             LexerError::EmptyInput { position } => *position,
         }
@@ -324,8 +388,14 @@ impl LexerErrorExt for LexerError {
             LexerError::InvalidNumber { lexeme, .. } => {
                 format!("Некорректное число '{}'", lexeme)
             }
+            LexerError::IntegerOverflow { lexeme, target, .. } => {
+                format!("Число '{}' слишком велико для типа {}", lexeme, target)
+            }
             LexerError::IdentifierTooLong { .. } => "Слишком длинное имя переменной".to_string(),
             LexerError::UnterminatedComment { .. } => "Комментарий не закрыт".to_string(),
+            LexerError::InvalidCharLiteral { lexeme, .. } => {
+                format!("Некорректный символьный литерал {}", lexeme)
+            }
             LexerError::InvalidEscapeSequence { sequence, .. } => {
                 format!("Некорректная escape-последовательность '{}'", sequence)
             }
@@ -342,15 +412,25 @@ impl LexerErrorExt for LexerError {
             LexerError::UnterminatedString { .. } => {
                 Some("Добавьте закрывающую кавычку \" в конце строки".to_string())
             }
-            LexerError::InvalidNumber { .. } => {
-                Some("Исправьте формат числа (например, 123 или 123.45)".to_string())
-            }
+            LexerError::InvalidNumber { .. } => Some(
+                "Исправьте формат числа (например, 123, 123.45, 1e-9, 0xFF, 0o17, 0b1010 или 1_000)"
+                    .to_string(),
+            ),
+            LexerError::IntegerOverflow { target, .. } => Some(match target.as_str() {
+                "int" | "unsigned int" => {
+                    "Добавьте суффикс L или UL для 64-битной константы".to_string()
+                }
+                _ => format!("Значение должно помещаться в диапазон типа {}", target),
+            }),
             LexerError::IdentifierTooLong { .. } => {
                 Some("Сократите имя переменной до 255 символов".to_string())
             }
             LexerError::UnterminatedComment { .. } => {
                 Some("Добавьте */ в конце комментария".to_string())
             }
+            LexerError::InvalidCharLiteral { .. } => {
+                Some("Символьный литерал содержит ровно один символ: 'a' или '\\n'".to_string())
+            }
             LexerError::InvalidEscapeSequence { sequence, .. } => Some(format!(
                 "Используйте допустимые escape-последовательности: \\n, \\t, \\r, \\\\, \\\", \\' (вместо {})",
                 sequence
@@ -425,6 +505,12 @@ mod tests {
                 position: pos,
                 lexeme: "123.".to_string(),
             },
+            LexerError::IntegerOverflow {
+                position: pos,
+                end: pos.with_column_offset(9),
+                lexeme: "2147483648".to_string(),
+                target: "int".to_string(),
+            },
             LexerError::IdentifierTooLong { position: pos },
             LexerError::UnterminatedComment { position: pos },
             LexerError::InvalidEscapeSequence {
@@ -31,6 +31,35 @@ impl<'a> Scanner<'a> {
         }
     }
 
+    /// Может ли символ начинать идентификатор. Препроцессор использует
+    /// те же правила, чтобы имена макросов совпадали с токенами.
+    pub fn is_identifier_start(c: char) -> bool {
+        c.is_ascii_alphabetic() || c == '_'
+    }
+
+    /// Может ли символ продолжать идентификатор.
+    pub fn is_identifier_continue(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+
+    /// Сканирует строковый или символьный литерал в начале `source` и
+    /// возвращает его токен и длину в байтах. `None`, если `source` не
+    /// начинается с корректного литерала.
+    pub fn scan_literal(source: &str) -> Option<(TokenKind, usize)> {
+        if !source.starts_with(['"', '\'']) {
+            return None;
+        }
+        let mut scanner = Scanner::new(source);
+        let token = scanner.next_token().ok()?;
+        let rest: usize = scanner.chars.map(char::len_utf8).sum();
+        match token.kind {
+            kind @ (TokenKind::StringLiteral(_) | TokenKind::CharLiteral(_)) => {
+                Some((kind, source.len() - rest))
+            }
+            _ => None,
+        }
+    }
+
     fn advance(&mut self) -> Option<char> {
         let c = self.chars.next()?;
         match c {
@@ -217,54 +246,121 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn scan_number(&mut self) -> LexerResult<Token> {
-        let start_pos = self.start_position;
-        let mut is_float = false;
-        let mut has_digits = false;
-        let mut has_minus = false;
+    fn scan_char(&mut self) -> LexerResult<Token> {
+        self.current_lexeme.clear();
+        self.current_lexeme.push('\'');
+
+        let value = match self.next_char_in_literal() {
+            Some('\\') => {
+                let escape_position = self.current_position;
+                let c = self.next_char_in_literal();
+                match c.and_then(Self::unescape) {
+                    Some(value) => value,
+                    None => {
+                        return Err(self.error(LexerError::InvalidEscapeSequence {
+                            position: escape_position,
+                            sequence: format!("\\{}", c.map(String::from).unwrap_or_default()),
+                        }));
+                    }
+                }
+            }
+            Some(c) if c.is_ascii() && c != '\'' && c != '\n' => c,
+            _ => return Err(self.invalid_char_literal()),
+        };
+
+        if self.next_char_in_literal() != Some('\'') {
+            return Err(self.invalid_char_literal());
+        }
+        Ok(self.make_token(TokenKind::CharLiteral(value)))
+    }
 
-        if self.current_lexeme == "-" {
-            has_minus = true;
-        } else if !self.current_lexeme.is_empty() {
-            if self.current_lexeme.chars().next().unwrap().is_ascii_digit() {
-                has_digits = true;
+    /// Читает следующий символ литерала, не переходя через конец строки
+    fn next_char_in_literal(&mut self) -> Option<char> {
+        match self.chars.peek() {
+            None | Some('\n') => None,
+            Some(_) => {
+                let c = self.chars.next()?;
+                self.current_lexeme.push(c);
+                self.current_position.advance_column(1);
+                Some(c)
             }
         }
+    }
 
-        while let Some(&c) = self.peek() {
-            if c.is_ascii_digit() {
-                has_digits = true;
+    fn invalid_char_literal(&self) -> LexerError {
+        self.error(LexerError::InvalidCharLiteral {
+            position: self.start_position,
+            lexeme: self.current_lexeme.clone(),
+        })
+    }
+
+    /// Значение escape-последовательности по символу после `\`
+    fn unescape(c: char) -> Option<char> {
+        match c {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '\\' => Some('\\'),
+            '\'' => Some('\''),
+            '"' => Some('"'),
+            _ => None,
+        }
+    }
+
+    /// Байты содержимого строкового литерала с раскрытыми
+    /// escape-последовательностями (без завершающего нуля)
+    pub fn string_bytes(content: &str) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(content.len());
+        let mut chars = content.chars();
+        let mut buf = [0; 4];
+        while let Some(c) = chars.next() {
+            let c = match c {
+                '\\' => match chars.next() {
+                    Some(next) => Self::unescape(next).unwrap_or(next),
+                    None => c,
+                },
+                c => c,
+            };
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+        bytes
+    }
+
+    fn scan_number(&mut self) -> LexerResult<Token> {
+        let start_pos = self.start_position;
+        let has_minus = self.current_lexeme == "-";
+        if has_minus && self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance();
+        }
+
+        if self.current_lexeme.ends_with('0') {
+            let radix = match self.peek() {
+                Some('x' | 'X') => Some(16),
+                Some('o' | 'O') => Some(8),
+                Some('b' | 'B') => Some(2),
+                _ => None,
+            };
+            if let Some(radix) = radix {
                 self.advance();
-            } else {
-                break;
+                return self.scan_prefixed_integer(start_pos, radix, has_minus);
             }
         }
 
-        if let Some(&c) = self.peek() {
-            if c == '.' {
-                if !has_digits && !has_minus {
-                    return Err(self.error(LexerError::InvalidNumber {
-                        position: start_pos,
-                        lexeme: self.current_lexeme.clone() + ".",
-                    }));
-                }
-                is_float = true;
-                self.advance();
-                let mut has_digits_after = false;
-                while let Some(&c) = self.peek() {
-                    if c.is_ascii_digit() {
-                        has_digits_after = true;
-                        self.advance();
-                    } else {
-                        break;
-                    }
-                }
-                if !has_digits_after {
-                    return Err(self.error(LexerError::InvalidNumber {
-                        position: start_pos,
-                        lexeme: self.current_lexeme.clone(),
-                    }));
-                }
+        self.scan_digits();
+        let has_digits = self.current_lexeme.chars().any(|c| c.is_ascii_digit());
+        let mut is_float = false;
+
+        if self.peek() == Some(&'.') {
+            is_float = true;
+            self.advance();
+            let before = self.current_lexeme.len();
+            self.scan_digits();
+            if self.current_lexeme.len() == before {
+                return Err(self.error(LexerError::InvalidNumber {
+                    position: start_pos,
+                    lexeme: self.current_lexeme.clone(),
+                }));
             }
         }
 
@@ -272,7 +368,15 @@ impl<'a> Scanner<'a> {
             return Ok(self.make_token(TokenKind::Minus));
         }
 
-        if !has_digits && !is_float {
+        if self.scan_exponent() {
+            is_float = true;
+        }
+
+        let body_len = self.current_lexeme.len();
+        self.scan_number_tail();
+        let body = self.current_lexeme[..body_len].to_string();
+        let suffix = self.current_lexeme[body_len..].to_string();
+        if !Self::valid_separators(&body, 10) || (is_float && !suffix.is_empty()) {
             return Err(self.error(LexerError::InvalidNumber {
                 position: start_pos,
                 lexeme: self.current_lexeme.clone(),
@@ -280,30 +384,164 @@ impl<'a> Scanner<'a> {
         }
 
         if is_float {
-            match self.current_lexeme.parse::<f64>() {
+            return match body.replace('_', "").parse::<f64>() {
                 Ok(value) => Ok(self.make_token(TokenKind::FloatLiteral(value))),
                 Err(_) => Err(self.error(LexerError::InvalidNumber {
                     position: start_pos,
                     lexeme: self.current_lexeme.clone(),
                 })),
+            };
+        }
+
+        // Ведущий ноль, как в C, обозначает восьмеричную запись: `017`
+        let digits = body.trim_start_matches('-').replace('_', "");
+        let radix = if digits.len() > 1 && digits.starts_with('0') { 8 } else { 10 };
+        self.integer_literal(start_pos, &digits, radix, has_minus, &suffix)
+    }
+
+    /// Цифры и разделители `_` десятичной записи
+    fn scan_digits(&mut self) {
+        while let Some(&c) = self.peek() {
+            if c.is_ascii_digit() || c == '_' {
+                self.advance();
+            } else {
+                break;
             }
-        } else {
-            match self.current_lexeme.parse::<i32>() {
-                Ok(value) => Ok(self.make_token(TokenKind::IntLiteral(value))),
-                Err(_) => {
-                    if let Ok(value) = self.current_lexeme.parse::<i64>() {
-                        if value > i32::MAX as i64 || value < i32::MIN as i64 {
-                            return Err(self.error(LexerError::InvalidNumber {
-                                position: start_pos,
-                                lexeme: self.current_lexeme.clone(),
-                            }));
-                        }
-                    }
-                    Err(self.error(LexerError::InvalidNumber {
-                        position: start_pos,
-                        lexeme: self.current_lexeme.clone(),
-                    }))
-                }
+        }
+    }
+
+    /// Порядок вещественного числа `e[+-]цифры`; без цифр `e` не считается порядком
+    fn scan_exponent(&mut self) -> bool {
+        let mut ahead = self.chars.clone();
+        if !matches!(ahead.next(), Some('e' | 'E')) {
+            return false;
+        }
+        let (sign, first) = match ahead.next() {
+            Some('+' | '-') => (true, ahead.next()),
+            c => (false, c),
+        };
+        if !first.is_some_and(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        self.advance();
+        if sign {
+            self.advance();
+        }
+        self.scan_digits();
+        true
+    }
+
+    /// Буквы, цифры и `_`, примыкающие к числу: суффикс или ошибочный хвост
+    fn scan_number_tail(&mut self) {
+        while let Some(&c) = self.peek() {
+            if Self::is_identifier_continue(c) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Разделитель `_` допустим только между двумя цифрами
+    fn valid_separators(body: &str, radix: u32) -> bool {
+        let chars: Vec<char> = body.chars().collect();
+        chars.iter().enumerate().all(|(i, &c)| {
+            c != '_'
+                || (i > 0
+                    && chars[i - 1].is_digit(radix)
+                    && chars.get(i + 1).is_some_and(|n| n.is_digit(radix)))
+        })
+    }
+
+    /// Целый литерал с префиксом основания `0x`, `0o` или `0b`
+    fn scan_prefixed_integer(
+        &mut self,
+        start_pos: Position,
+        radix: u32,
+        negative: bool,
+    ) -> LexerResult<Token> {
+        let prefix_len = self.current_lexeme.len();
+        self.scan_number_tail();
+        let rest = &self.current_lexeme[prefix_len..];
+        let (digits, suffix) = rest.split_at(rest.trim_end_matches(['u', 'U', 'l', 'L']).len());
+        let valid = !digits.is_empty()
+            && digits.chars().all(|c| c.is_digit(radix) || c == '_')
+            && Self::valid_separators(digits, radix);
+        if !valid {
+            return Err(self.error(LexerError::InvalidNumber {
+                position: start_pos,
+                lexeme: self.current_lexeme.clone(),
+            }));
+        }
+        let (digits, suffix) = (digits.replace('_', ""), suffix.to_string());
+        self.integer_literal(start_pos, &digits, radix, negative, &suffix)
+    }
+
+    /// Строит токен целого литерала по цифрам, основанию и суффиксу
+    /// `L`, `U` или `UL`. Литерал без суффикса имеет тип `int`; в
+    /// шестнадцатеричной, восьмеричной и двоичной записи он, как в C,
+    /// может получить тип `unsigned int`
+    fn integer_literal(
+        &mut self,
+        start_pos: Position,
+        digits: &str,
+        radix: u32,
+        negative: bool,
+        suffix: &str,
+    ) -> LexerResult<Token> {
+        if !digits.chars().all(|c| c.is_digit(radix)) {
+            return Err(self.error(LexerError::InvalidNumber {
+                position: start_pos,
+                lexeme: self.current_lexeme.clone(),
+            }));
+        }
+        let magnitude = u64::from_str_radix(digits, radix).map(i128::from);
+        let value = magnitude.map(|m| if negative { -m } else { m });
+        let too_wide = value.is_err();
+        let prefixed = radix != 10 || digits.len() > 1 && digits.starts_with('0');
+
+        let suffix = suffix.to_ascii_lowercase();
+        let (kind, target) = match suffix.as_str() {
+            "" => {
+                let kind = value.ok().and_then(|v| match i32::try_from(v) {
+                    Ok(v) => Some(TokenKind::IntLiteral(v)),
+                    Err(_) if prefixed => u32::try_from(v).ok().map(TokenKind::UIntLiteral),
+                    Err(_) => None,
+                });
+                (kind, if prefixed { "unsigned int" } else { "int" })
+            }
+            "l" => (
+                value.ok().and_then(|v| i64::try_from(v).ok()).map(TokenKind::LongLiteral),
+                "long",
+            ),
+            "u" => (
+                value.ok().and_then(|v| u32::try_from(v).ok()).map(TokenKind::UIntLiteral),
+                "unsigned int",
+            ),
+            "ul" | "lu" => (
+                value.ok().and_then(|v| u64::try_from(v).ok()).map(TokenKind::ULongLiteral),
+                "unsigned long",
+            ),
+            _ => {
+                return Err(self.error(LexerError::InvalidNumber {
+                    position: start_pos,
+                    lexeme: self.current_lexeme.clone(),
+                }))
+            }
+        };
+
+        // Значение шире 64 бит не помещается даже в самый широкий тип
+        let target = if too_wide { "unsigned long" } else { target };
+        match kind {
+            Some(kind) => Ok(self.make_token(kind)),
+            None => {
+                let length = self.current_lexeme.chars().count();
+                Err(self.error(LexerError::IntegerOverflow {
+                    position: start_pos,
+                    end: start_pos.with_column_offset(length - 1),
+                    lexeme: self.current_lexeme.clone(),
+                    target: target.to_string(),
+                }))
             }
         }
     }
@@ -345,6 +583,12 @@ impl<'a> Scanner<'a> {
             "default" => self.make_token(TokenKind::KwDefault),
             "extern" => self.make_token(TokenKind::KwExtern),
             "char" => self.make_token(TokenKind::KwChar),
+            "long" => self.make_token(TokenKind::KwLong),
+            "unsigned" => self.make_token(TokenKind::KwUnsigned),
+            "sizeof" => self.make_token(TokenKind::KwSizeof),
+            "null" => self.make_token(TokenKind::KwNull),
+            "do" => self.make_token(TokenKind::KwDo),
+            "enum" => self.make_token(TokenKind::KwEnum),
             _ => self.make_token(TokenKind::Identifier(self.current_lexeme.clone())),
         };
 
@@ -395,6 +639,12 @@ impl<'a> Scanner<'a> {
             return self.scan_string();
         }
 
+        if let Some(&'\'') = self.peek() {
+            self.chars.next();
+            self.current_position.advance_column(1);
+            return self.scan_char();
+        }
+
         let c = self.advance().unwrap();
 
         match c {
@@ -407,6 +657,7 @@ impl<'a> Scanner<'a> {
             ';' => Ok(self.make_token(TokenKind::Semicolon)),
             ',' => Ok(self.make_token(TokenKind::Comma)),
             ':' => Ok(self.make_token(TokenKind::Colon)),
+            '?' => Ok(self.make_token(TokenKind::Question)),
             '.' => {
                 if self.matches('.') {
                     if self.matches('.') {
@@ -473,7 +724,21 @@ impl<'a> Scanner<'a> {
                     Ok(self.make_token(TokenKind::Slash))
                 }
             }
-            '%' => Ok(self.make_token(TokenKind::Percent)),
+            '%' => {
+                if self.matches('=') {
+                    Ok(self.make_token(TokenKind::PercentEq))
+                } else {
+                    Ok(self.make_token(TokenKind::Percent))
+                }
+            }
+            '^' => {
+                if self.matches('=') {
+                    Ok(self.make_token(TokenKind::CaretEq))
+                } else {
+                    Ok(self.make_token(TokenKind::Caret))
+                }
+            }
+            '~' => Ok(self.make_token(TokenKind::Tilde)),
             '!' => {
                 if self.matches('=') {
                     Ok(self.make_token(TokenKind::BangEq))
@@ -489,14 +754,26 @@ impl<'a> Scanner<'a> {
                 }
             }
             '<' => {
-                if self.matches('=') {
+                if self.matches('<') {
+                    if self.matches('=') {
+                        Ok(self.make_token(TokenKind::LtLtEq))
+                    } else {
+                        Ok(self.make_token(TokenKind::LtLt))
+                    }
+                } else if self.matches('=') {
                     Ok(self.make_token(TokenKind::LtEq))
                 } else {
                     Ok(self.make_token(TokenKind::Lt))
                 }
             }
             '>' => {
-                if self.matches('=') {
+                if self.matches('>') {
+                    if self.matches('=') {
+                        Ok(self.make_token(TokenKind::GtGtEq))
+                    } else {
+                        Ok(self.make_token(TokenKind::GtGt))
+                    }
+                } else if self.matches('=') {
                     Ok(self.make_token(TokenKind::GtEq))
                 } else {
                     Ok(self.make_token(TokenKind::Gt))
@@ -505,6 +782,8 @@ impl<'a> Scanner<'a> {
             '&' => {
                 if self.matches('&') {
                     Ok(self.make_token(TokenKind::AmpAmp))
+                } else if self.matches('=') {
+                    Ok(self.make_token(TokenKind::AmpEq))
                 } else {
                     Ok(self.make_token(TokenKind::Amp))
                 }
@@ -512,15 +791,14 @@ impl<'a> Scanner<'a> {
             '|' => {
                 if self.matches('|') {
                     Ok(self.make_token(TokenKind::PipePipe))
+                } else if self.matches('=') {
+                    Ok(self.make_token(TokenKind::PipeEq))
                 } else {
-                    Err(self.error(LexerError::UnexpectedCharacter {
-                        position: self.start_position,
-                        character: '|',
-                    }))
+                    Ok(self.make_token(TokenKind::Pipe))
                 }
             }
             c if c.is_ascii_digit() => self.scan_number(),
-            c if c.is_ascii_alphabetic() || c == '_' => self.scan_identifier_or_keyword(),
+            c if Self::is_identifier_start(c) => self.scan_identifier_or_keyword(),
             _ => Err(self.error(LexerError::UnexpectedCharacter {
                 position: self.start_position,
                 character: c,
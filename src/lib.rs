@@ -21,7 +21,7 @@ pub use parser::json_generator::JsonGenerator;
 pub use parser::pretty_printer::PrettyPrinter;
 pub use parser::visitor::{DefaultVisitor, Visitor, VisitorMut};
 pub use parser::{ParseError, ParseErrorKind, ParseErrors, ParseOutput, Parser, ast::*};
-pub use preprocessor::{Preprocessor, PreprocessorError};
+pub use preprocessor::{IncludeOptions, Preprocessor, PreprocessorError};
 
 /// Версия компилятора.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -64,7 +64,17 @@ pub mod compiler {
 
     /// Выполняет полный пайплайн компиляции.
     pub fn compile(source: &str, defines: Vec<(&str, &str)>) -> ParseOutput {
+        compile_with_includes(source, defines, &IncludeOptions::default())
+    }
+
+    /// Выполняет полный пайплайн компиляции с поиском файлов `#include`.
+    pub fn compile_with_includes(
+        source: &str,
+        defines: Vec<(&str, &str)>,
+        includes: &IncludeOptions,
+    ) -> ParseOutput {
         let mut preprocessor = Preprocessor::new(source);
+        preprocessor.set_include_options(includes);
         for (name, value) in defines {
             if let Err(e) = preprocessor.define(name, value) {
                 let mut errors = ParseErrors::new();
@@ -81,20 +91,43 @@ pub mod compiler {
                 return ParseOutput::new(None, errors);
             }
         };
+        let source_map = preprocessor.source_map().clone();
 
         let (tokens, lex_errors) = lexical_analysis(&processed);
 
-        if !lex_errors.is_empty() {
+        let mut output = if !lex_errors.is_empty() {
             let mut errors = ParseErrors::new();
-            for lex_error in lex_errors {
-                errors.add(ParseError::from_lexer_error(lex_error));
+            for mut lex_error in lex_errors {
+                // Сообщение лексера содержит позицию, поэтому она переводится
+                // до преобразования в ошибку парсера
+                let mut notes = Vec::new();
+                let mut positions = lex_error.positions_mut().into_iter();
+                if let Some(position) = positions.next() {
+                    source_map.relocate(position, &mut notes);
+                }
+                for position in positions {
+                    source_map.relocate(position, &mut Vec::new());
+                }
+                errors.add(ParseError::from_lexer_error(lex_error).with_notes(notes));
             }
-            return ParseOutput::new(None, errors);
-        }
-
-        let mut parser = Parser::new(tokens);
-        let ast = parser.parse();
-        ParseOutput::new(ast, parser.errors().clone())
+            ParseOutput::new(None, errors)
+        } else {
+            let mut parser = Parser::new(tokens);
+            let ast = parser.parse();
+            let mut errors = parser.errors().clone();
+            errors.errors = errors
+                .errors
+                .into_iter()
+                .map(|mut error| {
+                    let mut notes = Vec::new();
+                    source_map.relocate(&mut error.position, &mut notes);
+                    error.with_notes(notes)
+                })
+                .collect();
+            ParseOutput::new(ast, errors)
+        };
+        output.source_map = Some(source_map);
+        output
     }
 
     /// Проверяет, является ли исходный код синтаксически корректным.
@@ -122,7 +155,16 @@ pub mod compiler {
         source: &str,
         defines: Vec<(&str, &str)>,
     ) -> (ParseOutput, Option<crate::ir::ProgramIR>) {
-        let parse_output = compile(source, defines);
+        compile_with_ir_includes(source, defines, &IncludeOptions::default())
+    }
+
+    /// Как [`compile_with_ir`], но с поиском файлов `#include`.
+    pub fn compile_with_ir_includes(
+        source: &str,
+        defines: Vec<(&str, &str)>,
+        includes: &IncludeOptions,
+    ) -> (ParseOutput, Option<crate::ir::ProgramIR>) {
+        let mut parse_output = compile_with_includes(source, defines, includes);
 
         if !parse_output.is_valid() {
             return (parse_output, None);
@@ -132,14 +174,48 @@ pub mod compiler {
         let semantic_output = analyzer.analyze(parse_output.ast.clone().unwrap());
 
         if semantic_output.has_errors() {
+            for mut error in semantic_output.errors.errors {
+                if let Some(source_map) = &parse_output.source_map {
+                    source_map.relocate(&mut error.position, &mut error.notes);
+                }
+                parse_output.errors.add(ParseError::from_semantic_error(error));
+            }
             return (parse_output, None);
         }
 
         let mut ir_generator = crate::ir::IRGenerator::new(semantic_output.symbol_table);
-        let ir_program = ir_generator.generate(parse_output.ast.clone().unwrap());
+        let mut ir_program = ir_generator.generate(parse_output.ast.clone().unwrap());
+        ir_program.source_map = parse_output.source_map.clone();
 
         (parse_output, Some(ir_program))
     }
+
+    /// Компилирует исходный код в исполняемый файл: препроцессор, парсер,
+    /// семантика, IR, оптимизации, кодогенерация, ассемблирование и компоновка.
+    pub fn build(
+        source: &str,
+        defines: Vec<(&str, &str)>,
+        output: &std::path::Path,
+        options: &crate::codegen::BuildOptions,
+    ) -> Result<(), crate::codegen::BuildError> {
+        use crate::codegen::BuildError;
+
+        let (parse_output, ir_program) =
+            compile_with_ir_includes(source, defines, &options.includes);
+        if parse_output.has_errors() {
+            return Err(BuildError::Compile(parse_output.errors.to_string()));
+        }
+        let mut ir_program = ir_program
+            .ok_or_else(|| BuildError::Compile("семантический анализ не пройден".to_string()))?;
+
+        if options.inline {
+            let mut inline_opt =
+                crate::ir::inline_optimizer::InlineOptimizer::new().with_max_instructions(20);
+            inline_opt.optimize(&mut ir_program);
+        }
+
+        crate::codegen::build_executable(&ir_program, output, options)
+    }
 }
 
 impl ParseError {
@@ -153,6 +229,15 @@ impl ParseError {
     pub fn from_preprocessor_error(error: PreprocessorError) -> Self {
         Self::new(Position::new(1, 1), ParseErrorKind::SyntaxError).with_message(error.to_string())
     }
+
+    /// Создает ошибку парсера из ошибки семантического анализа
+    pub fn from_semantic_error(error: crate::semantic::SemanticError) -> Self {
+        let mut result = Self::new(error.position, ParseErrorKind::SemanticError)
+            .with_message(error.message)
+            .with_notes(error.notes);
+        result.suggestion = error.suggestion;
+        result
+    }
 }
 
 #[cfg(test)]
@@ -43,7 +43,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use minic::lexer::LexerErrorExt;
 use minic::parser::{DotGenerator, JsonGenerator, PrettyPrinter};
-use minic::preprocessor::Preprocessor;
+use minic::preprocessor::{IncludeOptions, Preprocessor};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -140,6 +140,10 @@ enum Commands {
         #[arg(short = 'D', long)]
         defines: Vec<String>,
 
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
         /// Применить препроцессор перед парсингом
         #[arg(long)]
         preprocess: bool,
@@ -201,6 +205,10 @@ enum Commands {
         /// Определить макросы для препроцессора
         #[arg(short = 'D', long)]
         defines: Vec<String>,
+
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
     },
 
     /// Генерация x86-64 ассемблерного кода
@@ -225,9 +233,99 @@ enum Commands {
         #[arg(short = 'D', long)]
         defines: Vec<String>,
 
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
         /// Применить инлайнинг функций
         #[arg(long)]
         inline: bool,
+
+        /// Добавить директивы %line для отладки по строкам исходника
+        #[arg(short = 'g', long)]
+        debug: bool,
+    },
+
+    /// Собрать исполняемый файл (ассемблирование и компоновка)
+    Build {
+        /// Входной файл с исходным кодом
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Исполняемый файл (по умолчанию - имя входного файла без расширения)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Подключить библиотеку при компоновке
+        #[arg(short = 'l', long = "lib")]
+        libs: Vec<String>,
+
+        /// Статическая компоновка
+        #[arg(long = "static")]
+        static_link: bool,
+
+        /// Сохранить промежуточные .asm и .o файлы
+        #[arg(long)]
+        keep_temps: bool,
+
+        /// Применить оптимизации
+        #[arg(long)]
+        optimize: bool,
+
+        /// Применить инлайнинг функций
+        #[arg(long)]
+        inline: bool,
+
+        /// Определить макросы для препроцессора
+        #[arg(short = 'D', long)]
+        defines: Vec<String>,
+
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
+        /// Генерировать отладочную информацию DWARF
+        #[arg(short = 'g', long)]
+        debug: bool,
+    },
+
+    /// Собрать программу во временный каталог и запустить ее
+    Run {
+        /// Входной файл с исходным кодом
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Сравнить stdout программы с содержимым файла
+        #[arg(long)]
+        expect_stdout: Option<PathBuf>,
+
+        /// Подключить библиотеку при компоновке
+        #[arg(short = 'l', long = "lib")]
+        libs: Vec<String>,
+
+        /// Применить оптимизации
+        #[arg(long)]
+        optimize: bool,
+
+        /// Применить инлайнинг функций
+        #[arg(long)]
+        inline: bool,
+
+        /// Определить макросы для препроцессора
+        #[arg(short = 'D', long)]
+        defines: Vec<String>,
+
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
+        /// Генерировать отладочную информацию DWARF
+        #[arg(short = 'g', long)]
+        debug: bool,
+
+        /// Аргументы программы (после `--`)
+        #[arg(last = true)]
+        args: Vec<String>,
     },
 
     /// Проверить синтаксис исходного кода
@@ -244,6 +342,10 @@ enum Commands {
         #[arg(short = 'D', long)]
         defines: Vec<String>,
 
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
         /// Применить препроцессор перед проверкой
         #[arg(long)]
         preprocess: bool,
@@ -302,6 +404,10 @@ enum Commands {
         #[arg(short = 'D', long)]
         defines: Vec<String>,
 
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
         /// Сохранять нумерацию строк
         #[arg(long)]
         preserve_lines: bool,
@@ -321,6 +427,10 @@ enum Commands {
         #[arg(short = 'D', long)]
         defines: Vec<String>,
 
+        /// Каталог поиска файлов для #include (можно указать несколько раз)
+        #[arg(short = 'I', long = "include-dir")]
+        include_dirs: Vec<PathBuf>,
+
         /// Формат вывода AST
         #[arg(long, value_enum, default_value_t = AstFormat::Text)]
         ast_format: AstFormat,
@@ -389,6 +499,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             output,
             ast_format,
             defines,
+            include_dirs,
             preprocess,
             show_metrics,
         } => handle_parse_command(
@@ -396,6 +507,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             output,
             ast_format,
             defines,
+            &include_options(&input, include_dirs),
             preprocess,
             show_metrics,
             cli.verbose,
@@ -424,6 +536,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             optimize,
             inline,
             defines,
+            include_dirs,
         } => handle_ir_command(
             &input,
             output,
@@ -432,6 +545,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             optimize,
             inline,
             defines,
+            &include_options(&input, include_dirs),
             cli.verbose,
         ),
 
@@ -442,6 +556,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             inline,
             stats,
             defines,
+            include_dirs,
+            debug,
         } => handle_codegen_command(
             &input,
             &output,
@@ -449,15 +565,80 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             inline,
             stats,
             defines,
+            &include_options(&input, include_dirs),
+            debug,
             cli.verbose,
         ),
 
+        Commands::Build {
+            input,
+            output,
+            libs,
+            static_link,
+            keep_temps,
+            optimize,
+            inline,
+            defines,
+            include_dirs,
+            debug,
+        } => {
+            let options = minic::codegen::BuildOptions {
+                optimize,
+                inline,
+                libs,
+                static_link,
+                keep_temps,
+                debug_file: debug.then(|| input.display().to_string()),
+                includes: include_options(&input, include_dirs),
+                ..Default::default()
+            };
+            handle_build_command(&input, output, &options, defines, cli.verbose)
+        }
+
+        Commands::Run {
+            input,
+            expect_stdout,
+            libs,
+            optimize,
+            inline,
+            defines,
+            include_dirs,
+            debug,
+            args,
+        } => {
+            let options = minic::codegen::BuildOptions {
+                optimize,
+                inline,
+                libs,
+                debug_file: debug.then(|| input.display().to_string()),
+                includes: include_options(&input, include_dirs),
+                ..Default::default()
+            };
+            let code = handle_run_command(
+                &input,
+                &options,
+                defines,
+                &args,
+                expect_stdout.as_deref(),
+                cli.verbose,
+            )?;
+            std::process::exit(code);
+        }
+
         Commands::Check {
             input,
             strict,
             defines,
+            include_dirs,
+            preprocess,
+        } => handle_check_command(
+            &input,
+            strict,
+            defines,
+            &include_options(&input, include_dirs),
             preprocess,
-        } => handle_check_command(&input, strict, defines, preprocess, cli.verbose),
+            cli.verbose,
+        ),
 
         Commands::Test {
             unit,
@@ -477,19 +658,30 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             input,
             output,
             defines,
+            include_dirs,
+            preserve_lines,
+            show,
+        } => handle_preprocess_command(
+            &input,
+            output,
+            defines,
+            &include_options(&input, include_dirs),
             preserve_lines,
             show,
-        } => handle_preprocess_command(&input, output, defines, preserve_lines, show, cli.verbose),
+            cli.verbose,
+        ),
 
         Commands::Full {
             input,
             defines,
+            include_dirs,
             ast_format,
             output,
             show_metrics,
         } => handle_full_command(
             &input,
             defines,
+            &include_options(&input, include_dirs),
             ast_format,
             output,
             show_metrics,
@@ -510,6 +702,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+/// Настройки `#include` для входного файла и каталогов `-I`.
+fn include_options(input: &Path, include_dirs: Vec<PathBuf>) -> IncludeOptions {
+    IncludeOptions {
+        source_file: Some(input.to_path_buf()),
+        search_paths: include_dirs,
+    }
+}
+
 /// Обрабатывает команду лексического анализа.
 fn handle_lex_command(
     input: Option<PathBuf>,
@@ -588,6 +788,7 @@ fn handle_parse_command(
     output: Option<PathBuf>,
     ast_format: AstFormat,
     defines: Vec<String>,
+    includes: &IncludeOptions,
     preprocess: bool,
     show_metrics: bool,
     verbose: bool,
@@ -617,7 +818,7 @@ fn handle_parse_command(
             })
             .collect();
 
-        compiler::compile(&source, defines_vec)
+        compiler::compile_with_includes(&source, defines_vec, includes)
     } else {
         compiler::syntactic_analysis(&source)
     };
@@ -676,6 +877,7 @@ fn handle_check_command(
     input: &Path,
     strict: bool,
     defines: Vec<String>,
+    includes: &IncludeOptions,
     preprocess: bool,
     verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -698,7 +900,7 @@ fn handle_check_command(
             })
             .collect();
 
-        compiler::compile(&source, defines_vec)
+        compiler::compile_with_includes(&source, defines_vec, includes)
     } else {
         compiler::syntactic_analysis(&source)
     };
@@ -725,6 +927,7 @@ fn handle_check_command(
 fn handle_full_command(
     input: &Path,
     defines: Vec<String>,
+    includes: &IncludeOptions,
     ast_format: AstFormat,
     output: Option<PathBuf>,
     show_metrics: bool,
@@ -752,7 +955,7 @@ fn handle_full_command(
         })
         .collect();
 
-    let parse_output = compiler::compile(&source, defines_vec);
+    let parse_output = compiler::compile_with_includes(&source, defines_vec, includes);
 
     if verbose {
         println!("Шаг 2: Лексический анализ...");
@@ -802,7 +1005,13 @@ fn handle_full_command(
     if parse_output.is_valid() {
         if let Some(ast) = &parse_output.ast {
             let mut analyzer = minic::semantic::SemanticAnalyzer::new();
-            let semantic_output = analyzer.analyze(ast.clone());
+            let mut semantic_output = analyzer.analyze(ast.clone());
+            for warning in &mut semantic_output.errors.warnings {
+                if let Some(source_map) = &parse_output.source_map {
+                    source_map.relocate(&mut warning.position, &mut warning.notes);
+                }
+                eprintln!("{}", warning);
+            }
 
             if !semantic_output.has_errors() {
                 let mut ir_generator = minic::ir::IRGenerator::new(semantic_output.symbol_table);
@@ -1243,6 +1452,7 @@ fn handle_preprocess_command(
     input: &Path,
     output: Option<PathBuf>,
     defines: Vec<String>,
+    includes: &IncludeOptions,
     preserve_lines: bool,
     show: bool,
     verbose: bool,
@@ -1257,6 +1467,7 @@ fn handle_preprocess_command(
     let source = utils::read_file_with_limit(input)?;
     let mut preprocessor = Preprocessor::new(&source);
     preprocessor.preserve_line_numbers(preserve_lines);
+    preprocessor.set_include_options(includes);
 
     for define in defines {
         let parts: Vec<&str> = define.splitn(2, '=').collect();
@@ -1314,8 +1525,13 @@ fn handle_semantic_command(
 
     if semantic_output.has_errors() {
         output_text.push_str(&semantic_output.errors.to_string());
-    } else if verbose {
-        output_text.push_str("Семантических ошибок не найдено.\n");
+    } else {
+        for warning in &semantic_output.errors.warnings {
+            output_text.push_str(&format!("{}\n", warning));
+        }
+        if verbose {
+            output_text.push_str("Семантических ошибок не найдено.\n");
+        }
     }
 
     if show_symbols {
@@ -1360,6 +1576,7 @@ fn handle_ir_command(
     optimize: bool,
     inline: bool,
     defines: Vec<String>,
+    includes: &IncludeOptions,
     verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if verbose {
@@ -1389,7 +1606,8 @@ fn handle_ir_command(
         })
         .collect();
 
-    let (parse_output, ir_program) = minic::compiler::compile_with_ir(&source, defines_vec);
+    let (parse_output, ir_program) =
+        minic::compiler::compile_with_ir_includes(&source, defines_vec, includes);
 
     if parse_output.has_errors() {
         eprintln!("Найдено ошибок: {}", parse_output.errors.len());
@@ -1627,6 +1845,8 @@ fn handle_codegen_command(
     inline: bool,
     stats: bool,
     defines: Vec<String>,
+    includes: &IncludeOptions,
+    debug: bool,
     verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if verbose {
@@ -1653,7 +1873,8 @@ fn handle_codegen_command(
         })
         .collect();
 
-    let (parse_output, ir_program) = minic::compiler::compile_with_ir(&source, defines_vec);
+    let (parse_output, ir_program) =
+        minic::compiler::compile_with_ir_includes(&source, defines_vec, includes);
 
     if parse_output.has_errors() {
         eprintln!("Ошибки при компиляции:");
@@ -1682,17 +1903,12 @@ fn handle_codegen_command(
         }
     }
 
-    if optimize {
-        if verbose {
-            println!("Применение оптимизаций IR...");
-        }
-        let report = minic::ir::PeepholeOptimizer::optimize(&mut ir_program);
-        if verbose {
-            println!("Оптимизации: {} изменений", report.changes_made);
-        }
-    }
-
-    let result = minic::codegen::generate_assembly(&ir_program, optimize);
+    let result = if debug {
+        let source_file = input.display().to_string();
+        minic::codegen::generate_debug_assembly(&ir_program, optimize, &source_file)
+    } else {
+        minic::codegen::generate_assembly(&ir_program, optimize)
+    };
 
     if stats {
         println!("Статистика кодогенерации:");
@@ -1711,3 +1927,128 @@ fn handle_codegen_command(
 
     Ok(())
 }
+
+fn handle_build_command(
+    input: &Path,
+    output: Option<PathBuf>,
+    options: &minic::codegen::BuildOptions,
+    defines: Vec<String>,
+    verbose: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = output.unwrap_or_else(|| input.with_extension(""));
+
+    if verbose {
+        println!("Сборка {} → {}", input.display(), output.display());
+    }
+
+    let source = utils::read_file_with_limit(input)?;
+
+    let defines_vec: Vec<(&str, &str)> = defines
+        .iter()
+        .map(|d| d.split_once('=').unwrap_or((d.as_str(), "")))
+        .collect();
+
+    if let Err(e) = minic::compiler::build(&source, defines_vec, &output, options) {
+        eprintln!("{}", e);
+        return Err("Сборка не удалась".into());
+    }
+
+    if verbose {
+        println!("Исполняемый файл записан в: {}", output.display());
+        if options.keep_temps {
+            println!(
+                "Промежуточные файлы: {}, {}",
+                output.with_extension("asm").display(),
+                output.with_extension("o").display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Собирает программу во временный каталог, запускает ее и возвращает код
+/// завершения. stdin и stderr передаются программе напрямую; stdout
+/// перехватывается только при сравнении с ожидаемым файлом.
+fn handle_run_command(
+    input: &Path,
+    options: &minic::codegen::BuildOptions,
+    defines: Vec<String>,
+    args: &[String],
+    expect_stdout: Option<&Path>,
+    verbose: bool,
+) -> Result<i32, Box<dyn std::error::Error>> {
+    use std::process::{Command, Stdio};
+
+    let expected = match expect_stdout {
+        Some(path) => Some(utils::read_file_with_limit(path)?),
+        None => None,
+    };
+    let source = utils::read_file_with_limit(input)?;
+
+    let defines_vec: Vec<(&str, &str)> = defines
+        .iter()
+        .map(|d| d.split_once('=').unwrap_or((d.as_str(), "")))
+        .collect();
+
+    let temp_dir = std::env::temp_dir().join(format!("minic-run-{}", std::process::id()));
+    std::fs::create_dir_all(&temp_dir)?;
+    let stem = input.file_stem().unwrap_or(std::ffi::OsStr::new("program"));
+    let exe = temp_dir.join(stem);
+
+    if let Err(e) = minic::compiler::build(&source, defines_vec, &exe, options) {
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        eprintln!("{}", e);
+        return Err("Сборка не удалась".into());
+    }
+
+    if verbose {
+        println!("Запуск: {} {}", exe.display(), args.join(" "));
+    }
+
+    let stdout = if expected.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::inherit()
+    };
+    let result = Command::new(&exe)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(stdout)
+        .stderr(Stdio::inherit())
+        .output();
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    let output = result?;
+
+    let code = match output.status.code() {
+        Some(code) => code,
+        None => {
+            eprintln!("Программа завершена сигналом: {}", output.status);
+            1
+        }
+    };
+
+    if let Some(expected) = expected {
+        let actual = String::from_utf8_lossy(&output.stdout);
+        print!("{}", actual);
+        if actual != expected {
+            eprintln!("stdout не совпадает с ожидаемым:");
+            for (i, (want, got)) in expected.lines().zip(actual.lines()).enumerate() {
+                if want != got {
+                    eprintln!("  строка {}: ожидалось {:?}, получено {:?}", i + 1, want, got);
+                }
+            }
+            let (want, got) = (expected.lines().count(), actual.lines().count());
+            if want != got {
+                eprintln!("  строк: ожидалось {}, получено {}", want, got);
+            }
+            return Err("Вывод программы не совпадает с ожидаемым".into());
+        }
+    }
+
+    if verbose {
+        println!("Код завершения: {}", code);
+    }
+
+    Ok(code)
+}
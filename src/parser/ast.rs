@@ -44,6 +44,7 @@ pub enum Declaration {
     Function(FunctionDecl),
     ExternFunction(ExternFunctionDecl),
     Struct(StructDecl),
+    Enum(EnumDecl),
     Variable(VarDecl),
 }
 
@@ -156,16 +157,56 @@ impl StructDecl {
     }
 }
 
+/// Объявление перечисления: enum Name { A, B = 5, C }
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDecl {
+    pub node: Node,
+    pub name: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+impl EnumDecl {
+    pub fn new(name: String, variants: Vec<EnumVariant>, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            name,
+            variants,
+        }
+    }
+}
+
+/// Константа перечисления; без явного значения берется предыдущее + 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    pub node: Node,
+    pub name: String,
+    pub value: Option<i64>,
+}
+
+impl EnumVariant {
+    pub fn new(name: String, value: Option<i64>, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            name,
+            value,
+        }
+    }
+}
+
 /// Типы данных
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Int,
+    Long,
+    UInt,
+    ULong,
     Float,
     Bool,
     Void,
     String,
     Char,
     Struct(String),
+    Enum(String),
     Inferred,
     Pointer(Box<Type>),
     Array(Box<Type>, Option<i32>),
@@ -175,20 +216,30 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Type::Int => write!(f, "int"),
+            Type::Long => write!(f, "long"),
+            Type::UInt => write!(f, "unsigned int"),
+            Type::ULong => write!(f, "unsigned long"),
             Type::Float => write!(f, "float"),
             Type::Bool => write!(f, "bool"),
             Type::Void => write!(f, "void"),
             Type::String => write!(f, "string"),
             Type::Char => write!(f, "char"),
             Type::Struct(name) => write!(f, "struct {}", name),
+            Type::Enum(name) => write!(f, "enum {}", name),
             Type::Inferred => write!(f, "var"),
             Type::Pointer(inner) => write!(f, "{}*", inner),
-            Type::Array(inner, size) => {
-                if let Some(s) = size {
-                    write!(f, "{}[{}]", inner, s)
-                } else {
-                    write!(f, "{}[]", inner)
+            Type::Array(..) => {
+                // Размерности печатаются от внешней к внутренней: int[3][4]
+                let mut element = self;
+                let mut dims = String::new();
+                while let Type::Array(inner, size) = element {
+                    match size {
+                        Some(s) => dims.push_str(&format!("[{}]", s)),
+                        None => dims.push_str("[]"),
+                    }
+                    element = inner;
                 }
+                write!(f, "{}{}", element, dims)
             }
         }
     }
@@ -200,11 +251,14 @@ impl Type {
     }
 
     pub fn is_numeric(&self) -> bool {
-        matches!(self, Type::Int | Type::Float)
+        matches!(self, Type::Int | Type::Long | Type::UInt | Type::ULong | Type::Float)
     }
 
     pub fn is_integer(&self) -> bool {
-        matches!(self, Type::Int | Type::Char)
+        matches!(
+            self,
+            Type::Int | Type::Long | Type::UInt | Type::ULong | Type::Char | Type::Enum(_)
+        )
     }
 
     pub fn is_boolean(&self) -> bool {
@@ -234,6 +288,26 @@ pub enum Statement {
     Break(BreakStmt),
     Continue(ContinueStmt),
     Switch(SwitchStmt),
+    DoWhile(DoWhileStmt),
+}
+
+impl Statement {
+    pub fn node_position(&self) -> Position {
+        match self {
+            Statement::VariableDecl(decl) => decl.node.position(),
+            Statement::Expression(expr) => expr.node.position(),
+            Statement::If(stmt) => stmt.node.position(),
+            Statement::While(stmt) => stmt.node.position(),
+            Statement::For(stmt) => stmt.node.position(),
+            Statement::Return(stmt) => stmt.node.position(),
+            Statement::Block(stmt) => stmt.node.position(),
+            Statement::Empty(stmt) => stmt.node.position(),
+            Statement::Break(stmt) => stmt.node.position(),
+            Statement::Continue(stmt) => stmt.node.position(),
+            Statement::Switch(stmt) => stmt.node.position(),
+            Statement::DoWhile(stmt) => stmt.node.position(),
+        }
+    }
 }
 
 /// Объявление переменной
@@ -352,6 +426,24 @@ impl WhileStmt {
     }
 }
 
+/// Цикл с постусловием: `do body while (condition);`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoWhileStmt {
+    pub node: Node,
+    pub body: Box<Statement>,
+    pub condition: Box<Expression>,
+}
+
+impl DoWhileStmt {
+    pub fn new(body: Statement, condition: Expression, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            body: Box::new(body),
+            condition: Box::new(condition),
+        }
+    }
+}
+
 /// Цикл for
 #[derive(Debug, Clone, PartialEq)]
 pub struct ForStmt {
@@ -451,22 +543,31 @@ impl SwitchStmt {
     }
 }
 
-/// case
+/// case; значение - литерал или константа перечисления
 #[derive(Debug, Clone, PartialEq)]
 pub struct CaseStmt {
     pub node: Node,
-    pub value: Literal,
+    pub value: Expression,
     pub body: Box<Statement>,
 }
 
 impl CaseStmt {
-    pub fn new(value: Literal, body: Statement, line: usize, column: usize) -> Self {
+    pub fn new(value: Expression, body: Statement, line: usize, column: usize) -> Self {
         Self {
             node: Node::new(line, column),
             value,
             body: Box::new(body),
         }
     }
+
+    /// Текстовая метка значения case для печати AST
+    pub fn value_label(&self) -> String {
+        match &self.value {
+            Expression::Literal(lit) => lit.value.to_string(),
+            Expression::Identifier(id) => id.name.clone(),
+            _ => "?".to_string(),
+        }
+    }
 }
 
 /// Выражения
@@ -482,6 +583,10 @@ pub enum Expression {
     ArrayAccess(ArrayAccessExpr),
     Grouped(GroupedExpr),
     ArrayInitializer(ArrayInitializerExpr),
+    Cast(CastExpr),
+    Sizeof(SizeofExpr),
+    Conditional(ConditionalExpr),
+    Comma(CommaExpr),
 }
 
 impl Expression {
@@ -497,6 +602,10 @@ impl Expression {
             Expression::ArrayAccess(access) => access.node.position(),
             Expression::Grouped(grouped) => grouped.node.position(),
             Expression::ArrayInitializer(init) => init.node.position(),
+            Expression::Cast(cast) => cast.node.position(),
+            Expression::Sizeof(sizeof) => sizeof.node.position(),
+            Expression::Conditional(cond) => cond.node.position(),
+            Expression::Comma(comma) => comma.node.position(),
         }
     }
 }
@@ -521,18 +630,28 @@ impl Literal {
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralValue {
     Int(i32),
+    Long(i64),
+    UInt(u32),
+    ULong(u64),
     Float(f64),
     Bool(bool),
     String(String),
+    Char(char),
+    Null,
 }
 
 impl fmt::Display for LiteralValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LiteralValue::Int(i) => write!(f, "{}", i),
+            LiteralValue::Long(i) => write!(f, "{}L", i),
+            LiteralValue::UInt(i) => write!(f, "{}U", i),
+            LiteralValue::ULong(i) => write!(f, "{}UL", i),
             LiteralValue::Float(fl) => write!(f, "{}", fl),
             LiteralValue::Bool(b) => write!(f, "{}", b),
             LiteralValue::String(s) => write!(f, "\"{}\"", s),
+            LiteralValue::Char(c) => write!(f, "'{}'", c.escape_default()),
+            LiteralValue::Null => write!(f, "null"),
         }
     }
 }
@@ -641,20 +760,38 @@ impl CallExpr {
     }
 }
 
-/// Доступ к полю структуры
+/// Доступ к полю структуры: `s.field` или через указатель `p->field`
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructAccessExpr {
     pub node: Node,
     pub object: Box<Expression>,
     pub field: String,
+    /// Объект - указатель на структуру (`->`)
+    pub is_arrow: bool,
 }
 
 impl StructAccessExpr {
-    pub fn new(object: Expression, field: String, line: usize, column: usize) -> Self {
+    pub fn new(
+        object: Expression,
+        field: String,
+        is_arrow: bool,
+        line: usize,
+        column: usize,
+    ) -> Self {
         Self {
             node: Node::new(line, column),
             object: Box::new(object),
             field,
+            is_arrow,
+        }
+    }
+
+    /// Оператор доступа в исходном виде
+    pub fn operator(&self) -> &'static str {
+        if self.is_arrow {
+            "->"
+        } else {
+            "."
         }
     }
 }
@@ -709,6 +846,91 @@ impl ArrayInitializerExpr {
     }
 }
 
+/// Явное приведение типа: `(Type)expr`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastExpr {
+    pub node: Node,
+    pub target_type: Type,
+    pub expr: Box<Expression>,
+}
+
+impl CastExpr {
+    pub fn new(target_type: Type, expr: Expression, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            target_type,
+            expr: Box::new(expr),
+        }
+    }
+}
+
+/// Операнд `sizeof`: тип или выражение
+#[derive(Debug, Clone, PartialEq)]
+pub enum SizeofOperand {
+    Type(Type),
+    Expr(Box<Expression>),
+}
+
+/// Размер типа или выражения: `sizeof(Type)`, `sizeof expr`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeofExpr {
+    pub node: Node,
+    pub operand: SizeofOperand,
+}
+
+impl SizeofExpr {
+    pub fn new(operand: SizeofOperand, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            operand,
+        }
+    }
+}
+
+/// Условное выражение: `condition ? then_expr : else_expr`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalExpr {
+    pub node: Node,
+    pub condition: Box<Expression>,
+    pub then_expr: Box<Expression>,
+    pub else_expr: Box<Expression>,
+}
+
+impl ConditionalExpr {
+    pub fn new(
+        condition: Expression,
+        then_expr: Expression,
+        else_expr: Expression,
+        line: usize,
+        column: usize,
+    ) -> Self {
+        Self {
+            node: Node::new(line, column),
+            condition: Box::new(condition),
+            then_expr: Box::new(then_expr),
+            else_expr: Box::new(else_expr),
+        }
+    }
+}
+
+/// Оператор запятая: `left, right` - вычисляет оба операнда, значение - правый
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommaExpr {
+    pub node: Node,
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+}
+
+impl CommaExpr {
+    pub fn new(left: Expression, right: Expression, line: usize, column: usize) -> Self {
+        Self {
+            node: Node::new(line, column),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
 /// Бинарные операторы
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BinaryOp {
@@ -725,6 +947,11 @@ pub enum BinaryOp {
     Ge,
     And,
     Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
 }
 
 impl fmt::Display for BinaryOp {
@@ -743,6 +970,11 @@ impl fmt::Display for BinaryOp {
             BinaryOp::Ge => write!(f, ">="),
             BinaryOp::And => write!(f, "&&"),
             BinaryOp::Or => write!(f, "||"),
+            BinaryOp::BitAnd => write!(f, "&"),
+            BinaryOp::BitOr => write!(f, "|"),
+            BinaryOp::BitXor => write!(f, "^"),
+            BinaryOp::Shl => write!(f, "<<"),
+            BinaryOp::Shr => write!(f, ">>"),
         }
     }
 }
@@ -759,6 +991,7 @@ pub enum UnaryOp {
     PostDecrement,
     Deref,
     AddrOf,
+    BitNot,
 }
 
 impl fmt::Display for UnaryOp {
@@ -773,6 +1006,7 @@ impl fmt::Display for UnaryOp {
             UnaryOp::PostDecrement => write!(f, "-- (postfix)"),
             UnaryOp::Deref => write!(f, "*"),
             UnaryOp::AddrOf => write!(f, "&"),
+            UnaryOp::BitNot => write!(f, "~"),
         }
     }
 }
@@ -785,6 +1019,31 @@ pub enum AssignmentOp {
     SubAssign,
     MulAssign,
     DivAssign,
+    ModAssign,
+    AndAssign,
+    OrAssign,
+    XorAssign,
+    ShlAssign,
+    ShrAssign,
+}
+
+impl AssignmentOp {
+    /// Бинарная операция составного присваивания (`None` для `=`)
+    pub fn binary_op(&self) -> Option<BinaryOp> {
+        match self {
+            AssignmentOp::Assign => None,
+            AssignmentOp::AddAssign => Some(BinaryOp::Add),
+            AssignmentOp::SubAssign => Some(BinaryOp::Sub),
+            AssignmentOp::MulAssign => Some(BinaryOp::Mul),
+            AssignmentOp::DivAssign => Some(BinaryOp::Div),
+            AssignmentOp::ModAssign => Some(BinaryOp::Mod),
+            AssignmentOp::AndAssign => Some(BinaryOp::BitAnd),
+            AssignmentOp::OrAssign => Some(BinaryOp::BitOr),
+            AssignmentOp::XorAssign => Some(BinaryOp::BitXor),
+            AssignmentOp::ShlAssign => Some(BinaryOp::Shl),
+            AssignmentOp::ShrAssign => Some(BinaryOp::Shr),
+        }
+    }
 }
 
 impl fmt::Display for AssignmentOp {
@@ -795,6 +1054,12 @@ impl fmt::Display for AssignmentOp {
             AssignmentOp::SubAssign => write!(f, "-="),
             AssignmentOp::MulAssign => write!(f, "*="),
             AssignmentOp::DivAssign => write!(f, "/="),
+            AssignmentOp::ModAssign => write!(f, "%="),
+            AssignmentOp::AndAssign => write!(f, "&="),
+            AssignmentOp::OrAssign => write!(f, "|="),
+            AssignmentOp::XorAssign => write!(f, "^="),
+            AssignmentOp::ShlAssign => write!(f, "<<="),
+            AssignmentOp::ShrAssign => write!(f, ">>="),
         }
     }
 }
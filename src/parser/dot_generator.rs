@@ -160,6 +160,10 @@ impl DotGenerator {
                 }
                 id
             }
+            Expression::Cast(c) => self.visit_cast(c),
+            Expression::Sizeof(s) => self.visit_sizeof(s),
+            Expression::Conditional(c) => self.visit_conditional(c),
+            Expression::Comma(c) => self.visit_comma(c),
         }
     }
 
@@ -187,6 +191,7 @@ impl DotGenerator {
                 id
             }
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
     }
 }
@@ -205,6 +210,7 @@ impl Visitor<usize> for DotGenerator {
                     self.visit_function_decl(&func)
                 }
                 Declaration::Struct(s) => self.visit_struct_decl(s),
+                Declaration::Enum(e) => self.visit_enum_decl(e),
                 Declaration::Variable(v) => self.visit_var_decl(v),
             };
             self.output
@@ -241,6 +247,27 @@ impl Visitor<usize> for DotGenerator {
         node_id
     }
 
+    fn visit_enum_decl(&mut self, enum_decl: &EnumDecl) -> usize {
+        let node_id = self.next_node_id();
+        let variants: Vec<String> = enum_decl
+            .variants
+            .iter()
+            .map(|v| match v.value {
+                Some(value) => format!("{} = {}", v.name, value),
+                None => v.name.clone(),
+            })
+            .collect();
+        let label = format!(
+            "Enum {} [line {}]\\n{}",
+            enum_decl.name,
+            enum_decl.node.line,
+            variants.join(", ")
+        );
+        let node_str = self.format_node(node_id, &label, &self.colors.struct_node);
+        self.output.push_str(&node_str);
+        node_id
+    }
+
     fn visit_struct_decl(&mut self, struct_decl: &StructDecl) -> usize {
         let node_id = self.next_node_id();
         let label = format!(
@@ -346,6 +373,20 @@ impl Visitor<usize> for DotGenerator {
         node_id
     }
 
+    fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt) -> usize {
+        let node_id = self.next_node_id();
+        let label = format!("DoWhileStmt [line {}]", do_while.node.line);
+        let node_str = self.format_node(node_id, &label, &self.colors.statement);
+        self.output.push_str(&node_str);
+        let body_id = self.visit_statement(&do_while.body);
+        self.output
+            .push_str(&self.format_edge(node_id, body_id, Some("body")));
+        let cond_id = self.visit_expression(&do_while.condition);
+        self.output
+            .push_str(&self.format_edge(node_id, cond_id, Some("cond")));
+        node_id
+    }
+
     fn visit_for_stmt(&mut self, for_stmt: &ForStmt) -> usize {
         let node_id = self.next_node_id();
         let label = format!("ForStmt [line {}]", for_stmt.node.line);
@@ -430,7 +471,7 @@ impl Visitor<usize> for DotGenerator {
 
     fn visit_case_stmt(&mut self, case_stmt: &CaseStmt) -> usize {
         let node_id = self.next_node_id();
-        let label = format!("Case {}", case_stmt.value.value);
+        let label = format!("Case {}", case_stmt.value_label());
         let node_str = self.format_node(node_id, &label, &self.colors.statement);
         self.output.push_str(&node_str);
         let body_id = self.visit_statement(&case_stmt.body);
@@ -443,9 +484,14 @@ impl Visitor<usize> for DotGenerator {
         let node_id = self.next_node_id();
         let value_str = match &literal.value {
             LiteralValue::String(s) => format!("\\\"{}\\\"", self.escape_label(s)),
+            LiteralValue::Char(c) => format!("'{}'", self.escape_label(&c.escape_default().to_string())),
             LiteralValue::Int(i) => format!("{}", i),
+            LiteralValue::Long(_) | LiteralValue::UInt(_) | LiteralValue::ULong(_) => {
+                literal.value.to_string()
+            }
             LiteralValue::Float(f) => format!("{}", f),
             LiteralValue::Bool(b) => format!("{}", b),
+            LiteralValue::Null => "null".to_string(),
         };
         let label = format!("Literal\\n{}", value_str);
         let node_str = self.format_node(node_id, &label, &self.colors.literal);
@@ -526,7 +572,7 @@ impl Visitor<usize> for DotGenerator {
 
     fn visit_struct_access(&mut self, access: &StructAccessExpr) -> usize {
         let node_id = self.next_node_id();
-        let label = format!("StructAccess\\n.{}", access.field);
+        let label = format!("StructAccess\\n{}{}", access.operator(), access.field);
         let node_str = self.format_node(node_id, &label, &self.colors.expression);
         self.output.push_str(&node_str);
         let object_id = self.visit_expression(&access.object);
@@ -557,4 +603,63 @@ impl Visitor<usize> for DotGenerator {
             .push_str(&self.format_node(id, "ArrayAccess", &self.colors.expression));
         id
     }
+
+    fn visit_cast(&mut self, cast: &CastExpr) -> usize {
+        let node_id = self.next_node_id();
+        let label = format!("Cast\\n({})", cast.target_type);
+        let node_str = self.format_node(node_id, &label, &self.colors.expression);
+        self.output.push_str(&node_str);
+        let expr_id = self.visit_expression(&cast.expr);
+        self.output
+            .push_str(&self.format_edge(node_id, expr_id, Some("expr")));
+        node_id
+    }
+
+    fn visit_sizeof(&mut self, sizeof: &SizeofExpr) -> usize {
+        let node_id = self.next_node_id();
+        match &sizeof.operand {
+            SizeofOperand::Type(t) => {
+                let label = format!("Sizeof\\n({})", t);
+                let node_str = self.format_node(node_id, &label, &self.colors.expression);
+                self.output.push_str(&node_str);
+            }
+            SizeofOperand::Expr(e) => {
+                let node_str = self.format_node(node_id, "Sizeof", &self.colors.expression);
+                self.output.push_str(&node_str);
+                let expr_id = self.visit_expression(e);
+                self.output
+                    .push_str(&self.format_edge(node_id, expr_id, Some("expr")));
+            }
+        }
+        node_id
+    }
+
+    fn visit_conditional(&mut self, cond: &ConditionalExpr) -> usize {
+        let node_id = self.next_node_id();
+        let node_str = self.format_node(node_id, "Conditional\\n(?:)", &self.colors.expression);
+        self.output.push_str(&node_str);
+        let cond_id = self.visit_expression(&cond.condition);
+        self.output
+            .push_str(&self.format_edge(node_id, cond_id, Some("cond")));
+        let then_id = self.visit_expression(&cond.then_expr);
+        self.output
+            .push_str(&self.format_edge(node_id, then_id, Some("then")));
+        let else_id = self.visit_expression(&cond.else_expr);
+        self.output
+            .push_str(&self.format_edge(node_id, else_id, Some("else")));
+        node_id
+    }
+
+    fn visit_comma(&mut self, comma: &CommaExpr) -> usize {
+        let node_id = self.next_node_id();
+        let node_str = self.format_node(node_id, "Comma\\n(,)", &self.colors.expression);
+        self.output.push_str(&node_str);
+        let left_id = self.visit_expression(&comma.left);
+        self.output
+            .push_str(&self.format_edge(node_id, left_id, Some("left")));
+        let right_id = self.visit_expression(&comma.right);
+        self.output
+            .push_str(&self.format_edge(node_id, right_id, Some("right")));
+        node_id
+    }
 }
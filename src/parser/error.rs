@@ -50,6 +50,8 @@ pub enum ParseErrorKind {
     RecoveryError,
     /// Каскадная ошибка (предотвращена)
     CascadingErrorPrevented,
+    /// Ошибка семантического анализа
+    SemanticError,
 }
 
 impl fmt::Display for ParseErrorKind {
@@ -76,6 +78,7 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::SyntaxError => write!(f, "синтаксическая ошибка"),
             ParseErrorKind::RecoveryError => write!(f, "ошибка восстановления"),
             ParseErrorKind::CascadingErrorPrevented => write!(f, "предотвращена каскадная ошибка"),
+            ParseErrorKind::SemanticError => write!(f, "семантическая ошибка"),
         }
     }
 }
@@ -137,6 +140,19 @@ impl ParseError {
         self
     }
 
+    /// Добавляет к сообщению примечания о месте ошибки (включенный файл,
+    /// раскрытые макросы)
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        if !notes.is_empty() {
+            let notes = notes.join("; ");
+            self.message = Some(match self.message {
+                Some(message) => format!("{}; {}", message, notes),
+                None => notes,
+            });
+        }
+        self
+    }
+
     /// Помечает ошибку как каскадную
     pub fn as_cascading(mut self) -> Self {
         self.is_cascading = true;
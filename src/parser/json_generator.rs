@@ -51,6 +51,10 @@ impl JsonGenerator {
                     .collect();
                 json!({ "type": "ArrayInitializer", "line": arr.node.line, "column": arr.node.column, "elements": elements })
             }
+            Expression::Cast(c) => self.visit_cast(c),
+            Expression::Sizeof(s) => self.visit_sizeof(s),
+            Expression::Conditional(c) => self.visit_conditional(c),
+            Expression::Comma(c) => self.visit_comma(c),
         }
     }
 
@@ -67,6 +71,7 @@ impl JsonGenerator {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
     }
 }
@@ -89,6 +94,7 @@ impl Visitor<Value> for JsonGenerator {
                     })
                 }
                 Declaration::Struct(s) => self.visit_struct_decl(s),
+                Declaration::Enum(e) => self.visit_enum_decl(e),
                 Declaration::Variable(v) => self.visit_var_decl(v),
             });
         }
@@ -111,6 +117,15 @@ impl Visitor<Value> for JsonGenerator {
         json!({ "type": "StructDecl", "line": struct_decl.node.line, "column": struct_decl.node.column, "name": struct_decl.name, "fields": fields })
     }
 
+    fn visit_enum_decl(&mut self, enum_decl: &EnumDecl) -> Value {
+        let variants: Vec<Value> = enum_decl
+            .variants
+            .iter()
+            .map(|v| json!({ "name": v.name, "value": v.value, "line": v.node.line, "column": v.node.column }))
+            .collect();
+        json!({ "type": "EnumDecl", "line": enum_decl.node.line, "column": enum_decl.node.column, "name": enum_decl.name, "variants": variants })
+    }
+
     fn visit_var_decl(&mut self, var_decl: &VarDecl) -> Value {
         json!({ "type": "VarDecl", "line": var_decl.node.line, "column": var_decl.node.column, "var_type": var_decl.var_type.to_string(), "name": var_decl.name, "initializer": var_decl.initializer.as_ref().map(|init| self.visit_expression(init)) })
     }
@@ -140,13 +155,17 @@ impl Visitor<Value> for JsonGenerator {
     }
 
     fn visit_case_stmt(&mut self, case_stmt: &CaseStmt) -> Value {
-        json!({ "type": "CaseStmt", "line": case_stmt.node.line, "column": case_stmt.node.column, "value": self.visit_literal(&case_stmt.value), "body": self.visit_statement(&case_stmt.body) })
+        json!({ "type": "CaseStmt", "line": case_stmt.node.line, "column": case_stmt.node.column, "value": self.visit_expression(&case_stmt.value), "body": self.visit_statement(&case_stmt.body) })
     }
 
     fn visit_while_stmt(&mut self, while_stmt: &WhileStmt) -> Value {
         json!({ "type": "WhileStmt", "line": while_stmt.node.line, "column": while_stmt.node.column, "condition": self.visit_expression(&while_stmt.condition), "body": self.visit_statement(&while_stmt.body) })
     }
 
+    fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt) -> Value {
+        json!({ "type": "DoWhileStmt", "line": do_while.node.line, "column": do_while.node.column, "body": self.visit_statement(&do_while.body), "condition": self.visit_expression(&do_while.condition) })
+    }
+
     fn visit_for_stmt(&mut self, for_stmt: &ForStmt) -> Value {
         json!({ "type": "ForStmt", "line": for_stmt.node.line, "column": for_stmt.node.column, "init": for_stmt.init.as_ref().map(|i| self.visit_statement(i)), "condition": for_stmt.condition.as_ref().map(|c| self.visit_expression(c)), "update": for_stmt.update.as_ref().map(|u| self.visit_expression(u)), "body": self.visit_statement(&for_stmt.body) })
     }
@@ -174,9 +193,14 @@ impl Visitor<Value> for JsonGenerator {
     fn visit_literal(&mut self, literal: &Literal) -> Value {
         let value = match &literal.value {
             LiteralValue::Int(i) => json!({ "int": i }),
+            LiteralValue::Long(i) => json!({ "long": i }),
+            LiteralValue::UInt(i) => json!({ "uint": i }),
+            LiteralValue::ULong(i) => json!({ "ulong": i }),
             LiteralValue::Float(f) => json!({ "float": f }),
             LiteralValue::Bool(b) => json!({ "bool": b }),
             LiteralValue::String(s) => json!({ "string": s }),
+            LiteralValue::Char(c) => json!({ "char": c.to_string() }),
+            LiteralValue::Null => json!({ "null": null }),
         };
         json!({ "type": "Literal", "line": literal.node.line, "column": literal.node.column, "value": value })
     }
@@ -206,7 +230,7 @@ impl Visitor<Value> for JsonGenerator {
     }
 
     fn visit_struct_access(&mut self, access: &StructAccessExpr) -> Value {
-        json!({ "type": "StructAccessExpr", "line": access.node.line, "column": access.node.column, "object": self.visit_expression(&access.object), "field": access.field })
+        json!({ "type": "StructAccessExpr", "line": access.node.line, "column": access.node.column, "object": self.visit_expression(&access.object), "field": access.field, "arrow": access.is_arrow })
     }
 
     fn visit_grouped(&mut self, grouped: &GroupedExpr) -> Value {
@@ -222,4 +246,24 @@ impl Visitor<Value> for JsonGenerator {
             "index": self.visit_expression(&access.index)
         })
     }
+
+    fn visit_cast(&mut self, cast: &CastExpr) -> Value {
+        json!({ "type": "CastExpr", "line": cast.node.line, "column": cast.node.column, "target_type": cast.target_type.to_string(), "expression": self.visit_expression(&cast.expr) })
+    }
+
+    fn visit_sizeof(&mut self, sizeof: &SizeofExpr) -> Value {
+        let operand = match &sizeof.operand {
+            SizeofOperand::Type(t) => json!({ "type_name": t.to_string() }),
+            SizeofOperand::Expr(e) => self.visit_expression(e),
+        };
+        json!({ "type": "SizeofExpr", "line": sizeof.node.line, "column": sizeof.node.column, "operand": operand })
+    }
+
+    fn visit_conditional(&mut self, cond: &ConditionalExpr) -> Value {
+        json!({ "type": "ConditionalExpr", "line": cond.node.line, "column": cond.node.column, "condition": self.visit_expression(&cond.condition), "then": self.visit_expression(&cond.then_expr), "else": self.visit_expression(&cond.else_expr) })
+    }
+
+    fn visit_comma(&mut self, comma: &CommaExpr) -> Value {
+        json!({ "type": "CommaExpr", "line": comma.node.line, "column": comma.node.column, "left": self.visit_expression(&comma.left), "right": self.visit_expression(&comma.right) })
+    }
 }
@@ -23,6 +23,8 @@ pub use parser::Parser;
 pub use pretty_printer::PrettyPrinter;
 pub use visitor::{DefaultVisitor, Visitor, VisitorMut};
 
+use crate::preprocessor::SourceMap;
+
 /// Результат парсинга с AST и возможными ошибками
 #[derive(Debug)]
 pub struct ParseOutput {
@@ -30,11 +32,17 @@ pub struct ParseOutput {
     pub ast: Option<Program>,
     /// Ошибки, обнаруженные во время парсинга
     pub errors: ParseErrors,
+    /// Карта результата препроцессора, если он выполнялся
+    pub source_map: Option<SourceMap>,
 }
 
 impl ParseOutput {
     pub fn new(ast: Option<Program>, errors: ParseErrors) -> Self {
-        Self { ast, errors }
+        Self {
+            ast,
+            errors,
+            source_map: None,
+        }
     }
 
     /// Была ли программа успешно разобрана (без фатальных ошибок)
@@ -230,10 +230,12 @@ impl Parser {
                     match &next.kind {
                         TokenKind::KwIf
                         | TokenKind::KwWhile
+                        | TokenKind::KwDo
                         | TokenKind::KwFor
                         | TokenKind::KwReturn
                         | TokenKind::KwFn
                         | TokenKind::KwStruct
+                        | TokenKind::KwEnum
                         | TokenKind::RBrace => {
                             self.errors.metrics.mark_recovered();
                             return true;
@@ -310,10 +312,11 @@ impl Parser {
         match &self.peek().kind {
             TokenKind::Semicolon => true,
 
-            TokenKind::KwFn | TokenKind::KwStruct => true,
+            TokenKind::KwFn | TokenKind::KwStruct | TokenKind::KwEnum => true,
 
             TokenKind::KwIf
             | TokenKind::KwWhile
+            | TokenKind::KwDo
             | TokenKind::KwFor
             | TokenKind::KwReturn
             | TokenKind::LBrace
@@ -449,7 +452,12 @@ impl Parser {
                     )))
                 }
             }
-            TokenKind::KwStruct => Ok(Declaration::Struct(self.parse_struct_decl()?)),
+            TokenKind::KwStruct if !self.is_struct_variable_decl() => {
+                Ok(Declaration::Struct(self.parse_struct_decl()?))
+            }
+            TokenKind::KwEnum if self.is_enum_decl() => {
+                Ok(Declaration::Enum(self.parse_enum_decl()?))
+            }
             _ => {
                 if self.is_type_start() {
                     let start_pos = self.current_position();
@@ -517,23 +525,7 @@ impl Parser {
         name: String,
         start_pos: Position,
     ) -> ParseResult<VarDecl> {
-        let full_type = if self.check(&TokenKind::LBracket) {
-            self.advance();
-            let size = if let TokenKind::IntLiteral(s) = self.peek().kind {
-                self.advance();
-                Some(s)
-            } else {
-                None
-            };
-            self.consume(
-                &TokenKind::RBracket,
-                ParseErrorKind::MissingCloseParen,
-                "ожидалось ']'",
-            )?;
-            Type::Array(Box::new(var_type), size)
-        } else {
-            var_type
-        };
+        let full_type = self.parse_array_dims(var_type)?;
 
         let initializer = if self.match_token(&TokenKind::Eq) {
             if self.check(&TokenKind::LBrace) {
@@ -581,17 +573,56 @@ impl Parser {
         }
     }
 
+    /// Проверяет, начинается ли с `struct Имя` объявление переменной, а не структуры
+    fn is_struct_variable_decl(&self) -> bool {
+        matches!(
+            self.tokens.get(self.current + 2).map(|t| &t.kind),
+            Some(TokenKind::Identifier(_)) | Some(TokenKind::Asterisk)
+        )
+    }
+
+    /// Проверяет, начинается ли с `enum Имя {` объявление перечисления
+    fn is_enum_decl(&self) -> bool {
+        matches!(
+            self.tokens.get(self.current + 2).map(|t| &t.kind),
+            Some(TokenKind::LBrace)
+        )
+    }
+
+    /// Проверяет, начинается ли с текущей '(' приведение типа `(Type)expr`
+    fn is_cast_start(&self) -> bool {
+        self.check(&TokenKind::LParen)
+            && matches!(
+                self.tokens.get(self.current + 1).map(|t| &t.kind),
+                Some(
+                    TokenKind::KwInt
+                        | TokenKind::KwLong
+                        | TokenKind::KwUnsigned
+                        | TokenKind::KwFloat
+                        | TokenKind::KwBool
+                        | TokenKind::KwVoid
+                        | TokenKind::KwString
+                        | TokenKind::KwChar
+                        | TokenKind::KwStruct
+                        | TokenKind::KwEnum
+                )
+            )
+    }
+
     /// Проверяет, начинается ли токен с типа
     pub fn is_type_start(&self) -> bool {
         matches!(
             &self.peek().kind,
             TokenKind::KwInt
+                | TokenKind::KwLong
+                | TokenKind::KwUnsigned
                 | TokenKind::KwFloat
                 | TokenKind::KwBool
                 | TokenKind::KwVoid
                 | TokenKind::KwString
                 | TokenKind::KwChar
                 | TokenKind::KwStruct
+                | TokenKind::KwEnum
         ) || {
             if let TokenKind::Identifier(name) = &self.peek().kind {
                 name == "var"
@@ -745,14 +776,7 @@ impl Parser {
             }
         };
 
-        if self.match_token(&TokenKind::LBracket) {
-            self.consume(
-                &TokenKind::RBracket,
-                ParseErrorKind::MissingCloseParen,
-                "ожидалось ']'",
-            )?;
-            param_type = Type::Array(Box::new(param_type), None);
-        }
+        let param_type = self.parse_array_dims(param_type)?;
 
         Ok(Param::new(
             param_type,
@@ -823,6 +847,99 @@ impl Parser {
         ))
     }
 
+    /// Парсит объявление перечисления: enum Name { A, B = 5, C }
+    pub fn parse_enum_decl(&mut self) -> ParseResult<EnumDecl> {
+        let start_pos = self.current_position();
+
+        self.consume(
+            &TokenKind::KwEnum,
+            ParseErrorKind::ExpectedToken,
+            "ожидалось 'enum'",
+        )?;
+
+        let name = match self.advance() {
+            token if matches!(token.kind, TokenKind::Identifier(_)) => token.lexeme.clone(),
+            token => {
+                return Err(
+                    ParseError::new(token.position, ParseErrorKind::ExpectedToken)
+                        .with_found(token.lexeme.clone())
+                        .with_suggestion(
+                            "Имя перечисления должно быть идентификатором".to_string(),
+                        ),
+                );
+            }
+        };
+
+        self.consume(
+            &TokenKind::LBrace,
+            ParseErrorKind::MissingOpenBrace,
+            "ожидалось '{{' после имени перечисления",
+        )?;
+
+        let mut variants = Vec::new();
+        while !self.check(&TokenKind::RBrace) && !self.is_at_end() {
+            let token = self.advance().clone();
+            if !matches!(token.kind, TokenKind::Identifier(_)) {
+                return Err(
+                    ParseError::new(token.position, ParseErrorKind::ExpectedToken)
+                        .with_found(token.lexeme)
+                        .with_suggestion("ожидалось имя константы перечисления".to_string()),
+                );
+            }
+
+            let value = if self.match_token(&TokenKind::Eq) {
+                let negative = self.match_token(&TokenKind::Minus);
+                match self.peek().kind {
+                    TokenKind::IntLiteral(v) => {
+                        self.advance();
+                        Some(if negative { -(v as i64) } else { v as i64 })
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            self.current_position(),
+                            ParseErrorKind::InvalidExpression,
+                        )
+                        .with_found(self.peek().lexeme.clone())
+                        .with_message(
+                            "Значение константы перечисления должно быть целым литералом"
+                                .to_string(),
+                        ));
+                    }
+                }
+            } else {
+                None
+            };
+
+            variants.push(EnumVariant::new(
+                token.lexeme,
+                value,
+                token.position.line,
+                token.position.column,
+            ));
+
+            if !self.match_token(&TokenKind::Comma) {
+                break;
+            }
+        }
+
+        self.consume(
+            &TokenKind::RBrace,
+            ParseErrorKind::MissingCloseBrace,
+            "ожидалось '}}' в конце перечисления",
+        )?;
+
+        if self.check(&TokenKind::Semicolon) {
+            self.advance();
+        }
+
+        Ok(EnumDecl::new(
+            name,
+            variants,
+            start_pos.line,
+            start_pos.column,
+        ))
+    }
+
     /// Парсит объявление переменной: Type name [= Expression];
     pub fn parse_var_decl(&mut self) -> ParseResult<VarDecl> {
         let start_pos = self.current_position();
@@ -840,7 +957,8 @@ impl Parser {
         self.parse_var_decl_after_type(var_type, name, start_pos)
     }
 
-    /// Парсит инициализатор массива: { expr, expr, ... }
+    /// Парсит инициализатор массива: { expr, expr, ... }; строки
+    /// многомерного массива задаются вложенными списками { {..}, {..} }
     pub fn parse_array_initializer(&mut self) -> ParseResult<ArrayInitializerExpr> {
         let start_pos = self.current_position();
 
@@ -854,7 +972,11 @@ impl Parser {
 
         if !self.check(&TokenKind::RBrace) {
             loop {
-                let elem = self.parse_expression()?;
+                let elem = if self.check(&TokenKind::LBrace) {
+                    Expression::ArrayInitializer(self.parse_array_initializer()?)
+                } else {
+                    self.parse_expression()?
+                };
                 elements.push(elem);
 
                 if !self.match_token(&TokenKind::Comma) {
@@ -889,6 +1011,17 @@ impl Parser {
                 self.advance();
                 Type::Int
             }
+            TokenKind::KwLong => {
+                self.advance();
+                self.match_token(&TokenKind::KwInt);
+                Type::Long
+            }
+            TokenKind::KwUnsigned => {
+                self.advance();
+                let long = self.match_token(&TokenKind::KwLong);
+                self.match_token(&TokenKind::KwInt);
+                if long { Type::ULong } else { Type::UInt }
+            }
             TokenKind::KwFloat => {
                 self.advance();
                 Type::Float
@@ -933,11 +1066,31 @@ impl Parser {
                     }
                 }
             }
+            TokenKind::KwEnum => {
+                self.advance();
+                match self.peek() {
+                    token if matches!(token.kind, TokenKind::Identifier(_)) => {
+                        let name = token.lexeme.clone();
+                        self.advance();
+                        Type::Enum(name)
+                    }
+                    token => {
+                        return Err(ParseError::new(
+                            token.position,
+                            ParseErrorKind::ExpectedToken,
+                        )
+                        .with_found(token.lexeme.clone())
+                        .with_suggestion(
+                            "После 'enum' должно следовать имя перечисления".to_string(),
+                        ));
+                    }
+                }
+            }
             _ => {
                 return Err(
                     ParseError::new(pos, ParseErrorKind::UnknownType)
                         .with_found(token.lexeme)
-                        .with_suggestion("Используйте один из встроенных типов: int, float, bool, void, string, char, struct Имя, или var".to_string()),
+                        .with_suggestion("Используйте один из встроенных типов: int, long, unsigned, float, bool, void, string, char, struct Имя, enum Имя, или var".to_string()),
                 );
             }
         };
@@ -946,6 +1099,13 @@ impl Parser {
             typ = Type::Pointer(Box::new(typ));
         }
 
+        self.parse_array_dims(typ)
+    }
+
+    /// Парсит размерности массива `[N][M]...` после типа элемента.
+    /// Первая размерность внешняя: `int[3][4]` - три строки по четыре `int`
+    fn parse_array_dims(&mut self, element: Type) -> ParseResult<Type> {
+        let mut dims = Vec::new();
         while self.match_token(&TokenKind::LBracket) {
             let size = if let TokenKind::IntLiteral(s) = self.peek().kind {
                 self.advance();
@@ -958,10 +1118,12 @@ impl Parser {
                 ParseErrorKind::MissingCloseParen,
                 "ожидалось ']'",
             )?;
-            typ = Type::Array(Box::new(typ), size);
+            dims.push(size);
         }
-
-        Ok(typ)
+        Ok(dims
+            .into_iter()
+            .rev()
+            .fold(element, |typ, size| Type::Array(Box::new(typ), size)))
     }
 
     /// Парсит инструкцию
@@ -978,6 +1140,7 @@ impl Parser {
         let result = match &self.peek().kind {
             TokenKind::KwIf => Ok(Statement::If(self.parse_if_stmt()?)),
             TokenKind::KwWhile => Ok(Statement::While(self.parse_while_stmt()?)),
+            TokenKind::KwDo => Ok(Statement::DoWhile(self.parse_do_while_stmt()?)),
             TokenKind::KwFor => Ok(Statement::For(self.parse_for_stmt()?)),
             TokenKind::KwReturn => Ok(Statement::Return(self.parse_return_stmt()?)),
             TokenKind::LBrace => Ok(Statement::Block(self.parse_block()?)),
@@ -1022,7 +1185,7 @@ impl Parser {
     /// Парсит инструкцию-выражение: Expression;
     pub fn parse_expr_stmt(&mut self) -> ParseResult<ExprStmt> {
         let start_pos = self.current_position();
-        let expr = self.parse_expression()?;
+        let expr = self.parse_comma_expression()?;
 
         if self.check(&TokenKind::Semicolon) {
             self.advance();
@@ -1197,6 +1360,49 @@ impl Parser {
         ))
     }
 
+    /// Парсит do-while: do statement while (condition);
+    pub fn parse_do_while_stmt(&mut self) -> ParseResult<DoWhileStmt> {
+        let start_pos = self.current_position();
+
+        self.consume(&TokenKind::KwDo, ParseErrorKind::ExpectedToken, "ожидалось 'do'")?;
+
+        let body = self.parse_statement()?;
+
+        self.consume(
+            &TokenKind::KwWhile,
+            ParseErrorKind::ExpectedToken,
+            "ожидалось 'while' после тела do",
+        )?;
+
+        let has_paren = self.check(&TokenKind::LParen);
+        if has_paren {
+            self.advance();
+        }
+
+        let condition = self.parse_expression()?;
+
+        if has_paren && self.check(&TokenKind::RParen) {
+            self.advance();
+        }
+
+        if self.check(&TokenKind::Semicolon) {
+            self.advance();
+        } else if !self.check(&TokenKind::RBrace) && !self.is_at_end() {
+            let error = ParseError::new(self.current_position(), ParseErrorKind::MissingSemicolon)
+                .with_found(self.peek().lexeme.clone())
+                .with_suggestion("Добавьте ';' после условия do-while".to_string());
+            self.errors.add(error);
+            self.errors.metrics.mark_recovered();
+        }
+
+        Ok(DoWhileStmt::new(
+            body,
+            condition,
+            start_pos.line,
+            start_pos.column,
+        ))
+    }
+
     /// Парсит for: for (init; condition; update) statement
     pub fn parse_for_stmt(&mut self) -> ParseResult<ForStmt> {
         let start_pos = self.current_position();
@@ -1283,7 +1489,7 @@ impl Parser {
         };
 
         let update = if !self.check(&TokenKind::RParen) && !self.is_at_end() {
-            match self.parse_expression() {
+            match self.parse_comma_expression() {
                 Ok(expr) => Some(expr),
                 Err(e) => {
                     self.errors.add(e);
@@ -1360,9 +1566,25 @@ impl Parser {
         result
     }
 
-    /// Уровень 9: Присваивание (правоассоциативное)
+    /// Уровень 15: Запятая (левоассоциативная).
+    ///
+    /// Допускается только там, где запятая не разделяет элементы списка:
+    /// в инструкции-выражении, заголовке for и в скобках.
+    pub fn parse_comma_expression(&mut self) -> ParseResult<Expression> {
+        let mut expr = self.parse_expression()?;
+
+        while self.match_token(&TokenKind::Comma) {
+            let pos = self.previous().position;
+            let right = self.parse_expression()?;
+            expr = Expression::Comma(CommaExpr::new(expr, right, pos.line, pos.column));
+        }
+
+        Ok(expr)
+    }
+
+    /// Уровень 14: Присваивание (правоассоциативное)
     fn parse_assignment(&mut self) -> ParseResult<Expression> {
-        let expr = self.parse_logical_or()?;
+        let expr = self.parse_conditional()?;
 
         if self.match_any(&[
             TokenKind::Eq,
@@ -1370,6 +1592,12 @@ impl Parser {
             TokenKind::MinusEq,
             TokenKind::AsteriskEq,
             TokenKind::SlashEq,
+            TokenKind::PercentEq,
+            TokenKind::AmpEq,
+            TokenKind::PipeEq,
+            TokenKind::CaretEq,
+            TokenKind::LtLtEq,
+            TokenKind::GtGtEq,
         ]) {
             let operator = match self.previous().kind {
                 TokenKind::Eq => AssignmentOp::Assign,
@@ -1377,6 +1605,12 @@ impl Parser {
                 TokenKind::MinusEq => AssignmentOp::SubAssign,
                 TokenKind::AsteriskEq => AssignmentOp::MulAssign,
                 TokenKind::SlashEq => AssignmentOp::DivAssign,
+                TokenKind::PercentEq => AssignmentOp::ModAssign,
+                TokenKind::AmpEq => AssignmentOp::AndAssign,
+                TokenKind::PipeEq => AssignmentOp::OrAssign,
+                TokenKind::CaretEq => AssignmentOp::XorAssign,
+                TokenKind::LtLtEq => AssignmentOp::ShlAssign,
+                TokenKind::GtGtEq => AssignmentOp::ShrAssign,
                 _ => unreachable!(),
             };
 
@@ -1408,7 +1642,29 @@ impl Parser {
         }
     }
 
-    /// Уровень 8: Логическое ИЛИ (левоассоциативное)
+    /// Уровень 13: Условный оператор `c ? a : b` (правоассоциативный)
+    fn parse_conditional(&mut self) -> ParseResult<Expression> {
+        let condition = self.parse_logical_or()?;
+
+        if !self.match_token(&TokenKind::Question) {
+            return Ok(condition);
+        }
+
+        let pos = self.previous().position;
+        let then_expr = self.parse_comma_expression()?;
+        self.consume(
+            &TokenKind::Colon,
+            ParseErrorKind::ExpectedToken,
+            "ожидалось ':' в условном выражении",
+        )?;
+        let else_expr = self.parse_conditional()?;
+
+        Ok(Expression::Conditional(ConditionalExpr::new(
+            condition, then_expr, else_expr, pos.line, pos.column,
+        )))
+    }
+
+    /// Уровень 12: Логическое ИЛИ (левоассоциативное)
     fn parse_logical_or(&mut self) -> ParseResult<Expression> {
         let mut expr = self.parse_logical_and()?;
 
@@ -1423,13 +1679,13 @@ impl Parser {
         Ok(expr)
     }
 
-    /// Уровень 7: Логическое И (левоассоциативное)
+    /// Уровень 11: Логическое И (левоассоциативное)
     fn parse_logical_and(&mut self) -> ParseResult<Expression> {
-        let mut expr = self.parse_equality()?;
+        let mut expr = self.parse_bitwise_or()?;
 
         while self.match_token(&TokenKind::AmpAmp) {
             let operator = BinaryOp::And;
-            let right = self.parse_equality()?;
+            let right = self.parse_bitwise_or()?;
             let pos = self.previous().position.clone();
 
             expr = Expression::Binary(BinaryExpr::new(expr, operator, right, pos.line, pos.column));
@@ -1438,7 +1694,67 @@ impl Parser {
         Ok(expr)
     }
 
-    /// Уровень 6: Равенство (неассоциативное)
+    /// Уровень 10: Побитовое ИЛИ (левоассоциативное)
+    fn parse_bitwise_or(&mut self) -> ParseResult<Expression> {
+        let mut expr = self.parse_bitwise_xor()?;
+
+        while self.match_token(&TokenKind::Pipe) {
+            let op_pos = self.previous().position;
+            let right = self.parse_bitwise_xor()?;
+
+            expr = Expression::Binary(BinaryExpr::new(
+                expr,
+                BinaryOp::BitOr,
+                right,
+                op_pos.line,
+                op_pos.column,
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    /// Уровень 9: Побитовое исключающее ИЛИ (левоассоциативное)
+    fn parse_bitwise_xor(&mut self) -> ParseResult<Expression> {
+        let mut expr = self.parse_bitwise_and()?;
+
+        while self.match_token(&TokenKind::Caret) {
+            let op_pos = self.previous().position;
+            let right = self.parse_bitwise_and()?;
+
+            expr = Expression::Binary(BinaryExpr::new(
+                expr,
+                BinaryOp::BitXor,
+                right,
+                op_pos.line,
+                op_pos.column,
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    /// Уровень 8: Побитовое И (левоассоциативное)
+    fn parse_bitwise_and(&mut self) -> ParseResult<Expression> {
+        let mut expr = self.parse_equality()?;
+
+        while self.match_token(&TokenKind::Amp) {
+            let op_pos = self.previous().position;
+            let right = self.parse_equality()?;
+
+            expr = Expression::Binary(BinaryExpr::new(
+                expr,
+                BinaryOp::BitAnd,
+                right,
+                op_pos.line,
+                op_pos.column,
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    /// Уровень 7: Равенство (неассоциативное)
     fn parse_equality(&mut self) -> ParseResult<Expression> {
         let mut expr = self.parse_comparison()?;
 
@@ -1457,9 +1773,9 @@ impl Parser {
         Ok(expr)
     }
 
-    /// Уровень 5: Сравнение (неассоциативное)
+    /// Уровень 6: Сравнение (неассоциативное)
     fn parse_comparison(&mut self) -> ParseResult<Expression> {
-        let mut expr = self.parse_additive()?;
+        let mut expr = self.parse_shift()?;
 
         while self.match_any(&[
             TokenKind::Lt,
@@ -1475,6 +1791,31 @@ impl Parser {
                 _ => unreachable!(),
             };
             let op_pos = self.previous().position.clone();
+            let right = self.parse_shift()?;
+
+            expr = Expression::Binary(BinaryExpr::new(
+                expr,
+                operator,
+                right,
+                op_pos.line,
+                op_pos.column,
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    /// Уровень 5: Сдвиги (левоассоциативные)
+    fn parse_shift(&mut self) -> ParseResult<Expression> {
+        let mut expr = self.parse_additive()?;
+
+        while self.match_any(&[TokenKind::LtLt, TokenKind::GtGt]) {
+            let operator = match self.previous().kind {
+                TokenKind::LtLt => BinaryOp::Shl,
+                TokenKind::GtGt => BinaryOp::Shr,
+                _ => unreachable!(),
+            };
+            let op_pos = self.previous().position;
             let right = self.parse_additive()?;
 
             expr = Expression::Binary(BinaryExpr::new(
@@ -1548,6 +1889,7 @@ impl Parser {
             TokenKind::Plus,
             TokenKind::Asterisk,
             TokenKind::Amp,
+            TokenKind::Tilde,
         ]) {
             let operator = match self.previous().kind {
                 TokenKind::Minus => UnaryOp::Neg,
@@ -1555,6 +1897,7 @@ impl Parser {
                 TokenKind::Plus => UnaryOp::Plus,
                 TokenKind::Asterisk => UnaryOp::Deref,
                 TokenKind::Amp => UnaryOp::AddrOf,
+                TokenKind::Tilde => UnaryOp::BitNot,
                 _ => unreachable!(),
             };
             let pos = self.previous().position.clone();
@@ -1563,6 +1906,40 @@ impl Parser {
             Ok(Expression::Unary(UnaryExpr::new(
                 operator, expr, pos.line, pos.column,
             )))
+        } else if self.is_cast_start() {
+            let pos = self.advance().position;
+            let target_type = self.parse_type()?;
+            self.consume(
+                &TokenKind::RParen,
+                ParseErrorKind::MissingCloseParen,
+                "ожидалось ')' после типа в приведении",
+            )?;
+            let expr = self.parse_unary()?;
+
+            Ok(Expression::Cast(CastExpr::new(
+                target_type,
+                expr,
+                pos.line,
+                pos.column,
+            )))
+        } else if self.match_token(&TokenKind::KwSizeof) {
+            let pos = self.previous().position;
+            let operand = if self.is_cast_start() {
+                self.advance();
+                let typ = self.parse_type()?;
+                self.consume(
+                    &TokenKind::RParen,
+                    ParseErrorKind::MissingCloseParen,
+                    "ожидалось ')' после типа в sizeof",
+                )?;
+                SizeofOperand::Type(typ)
+            } else {
+                SizeofOperand::Expr(Box::new(self.parse_unary()?))
+            };
+
+            Ok(Expression::Sizeof(SizeofExpr::new(
+                operand, pos.line, pos.column,
+            )))
         } else if self.match_any(&[TokenKind::PlusPlus, TokenKind::MinusMinus]) {
             let operator = match self.previous().kind {
                 TokenKind::PlusPlus => UnaryOp::PreIncrement,
@@ -1609,6 +1986,9 @@ impl Parser {
                         op_pos.line,
                         op_pos.column,
                     ));
+                } else if self.match_any(&[TokenKind::Dot, TokenKind::Arrow]) {
+                    let op_pos = self.previous().position;
+                    expr = self.parse_struct_access(expr, op_pos)?;
                 } else {
                     break;
                 }
@@ -1640,6 +2020,33 @@ impl Parser {
                 )))
             }
 
+            TokenKind::LongLiteral(value) => {
+                self.advance();
+                Ok(Expression::Literal(Literal::new(
+                    LiteralValue::Long(*value),
+                    pos.line,
+                    pos.column,
+                )))
+            }
+
+            TokenKind::UIntLiteral(value) => {
+                self.advance();
+                Ok(Expression::Literal(Literal::new(
+                    LiteralValue::UInt(*value),
+                    pos.line,
+                    pos.column,
+                )))
+            }
+
+            TokenKind::ULongLiteral(value) => {
+                self.advance();
+                Ok(Expression::Literal(Literal::new(
+                    LiteralValue::ULong(*value),
+                    pos.line,
+                    pos.column,
+                )))
+            }
+
             TokenKind::FloatLiteral(value) => {
                 self.advance();
                 Ok(Expression::Literal(Literal::new(
@@ -1658,6 +2065,15 @@ impl Parser {
                 )))
             }
 
+            TokenKind::CharLiteral(value) => {
+                self.advance();
+                Ok(Expression::Literal(Literal::new(
+                    LiteralValue::Char(*value),
+                    pos.line,
+                    pos.column,
+                )))
+            }
+
             TokenKind::KwTrue => {
                 self.advance();
                 Ok(Expression::Literal(Literal::new(
@@ -1667,6 +2083,15 @@ impl Parser {
                 )))
             }
 
+            TokenKind::KwNull => {
+                self.advance();
+                Ok(Expression::Literal(Literal::new(
+                    LiteralValue::Null,
+                    pos.line,
+                    pos.column,
+                )))
+            }
+
             TokenKind::KwFalse => {
                 self.advance();
                 Ok(Expression::Literal(Literal::new(
@@ -1698,7 +2123,7 @@ impl Parser {
                         pos.line,
                         pos.column,
                     )))
-                } else if self.match_token(&TokenKind::Dot) {
+                } else if self.match_any(&[TokenKind::Dot, TokenKind::Arrow]) {
                     self.parse_struct_access(
                         Expression::Identifier(IdentifierExpr::new(name, pos.line, pos.column)),
                         pos,
@@ -1712,7 +2137,7 @@ impl Parser {
 
             TokenKind::LParen => {
                 self.advance();
-                let expr = self.parse_expression()?;
+                let expr = self.parse_comma_expression()?;
                 if !self.check(&TokenKind::RParen) {
                     let pos = self.current_position();
                     let error = ParseError::new(pos, ParseErrorKind::MissingCloseParen)
@@ -1743,7 +2168,8 @@ impl Parser {
         }
     }
 
-    /// Парсит доступ к полю структуры: object.field [.field...]
+    /// Парсит доступ к полю структуры: object.field или object->field,
+    /// цепочкой [.field | ->field ...]
     fn parse_struct_access(
         &mut self,
         object: Expression,
@@ -1752,6 +2178,7 @@ impl Parser {
         let mut current_obj = object;
 
         loop {
+            let is_arrow = matches!(self.previous().kind, TokenKind::Arrow);
             let field = match self.advance() {
                 token if matches!(token.kind, TokenKind::Identifier(_)) => token.lexeme.clone(),
                 token => {
@@ -1760,7 +2187,7 @@ impl Parser {
                         ParseErrorKind::ExpectedToken,
                     )
                     .with_found(token.lexeme.clone())
-                    .with_suggestion("После '.' должно следовать имя поля".to_string()));
+                    .with_suggestion("После '.' или '->' должно следовать имя поля".to_string()));
                 }
             };
             let field_pos = self.previous().position.clone();
@@ -1768,11 +2195,12 @@ impl Parser {
             current_obj = Expression::StructAccess(StructAccessExpr::new(
                 current_obj,
                 field,
+                is_arrow,
                 field_pos.line,
                 field_pos.column,
             ));
 
-            if !self.match_token(&TokenKind::Dot) {
+            if !self.match_any(&[TokenKind::Dot, TokenKind::Arrow]) {
                 break;
             }
         }
@@ -1821,7 +2249,7 @@ impl Parser {
 
         let call_expr = Expression::Call(CallExpr::new(callee, arguments, pos.line, pos.column));
 
-        if self.match_token(&TokenKind::Dot) {
+        if self.match_any(&[TokenKind::Dot, TokenKind::Arrow]) {
             self.parse_struct_access(call_expr, pos)
         } else {
             Ok(call_expr)
@@ -1861,13 +2289,15 @@ impl Parser {
         while !self.check(&TokenKind::RBrace) && !self.is_at_end() {
             if self.match_token(&TokenKind::KwCase) {
                 let value = match self.parse_primary() {
-                    Ok(Expression::Literal(lit)) => lit,
+                    Ok(value @ (Expression::Literal(_) | Expression::Identifier(_))) => value,
                     Ok(_) => {
                         return Err(ParseError::new(
                             self.current_position(),
                             ParseErrorKind::InvalidExpression,
                         )
-                        .with_message("Ожидался литерал в case".to_string()));
+                        .with_message(
+                            "Ожидался литерал или константа перечисления в case".to_string(),
+                        ));
                     }
                     Err(e) => return Err(e),
                 };
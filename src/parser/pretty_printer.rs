@@ -104,6 +104,24 @@ impl PrettyPrinter {
                 }
                 self.output.push('}');
             }
+            Expression::Cast(c) => {
+                self.output.push('(');
+                self.visit_cast(c);
+                self.output.push(')');
+            }
+            Expression::Sizeof(s) => {
+                self.visit_sizeof(s);
+            }
+            Expression::Conditional(c) => {
+                self.output.push('(');
+                self.visit_conditional(c);
+                self.output.push(')');
+            }
+            Expression::Comma(c) => {
+                self.output.push('(');
+                self.visit_comma(c);
+                self.output.push(')');
+            }
         }
     }
 }
@@ -140,6 +158,7 @@ impl Visitor<()> for PrettyPrinter {
                         self.dedent();
                     }
                     Declaration::Struct(s) => self.visit_struct_decl(s),
+                    Declaration::Enum(e) => self.visit_enum_decl(e),
                     Declaration::Variable(v) => self.visit_var_decl(v),
                 }
             }
@@ -167,6 +186,21 @@ impl Visitor<()> for PrettyPrinter {
         self.dedent();
     }
 
+    fn visit_enum_decl(&mut self, enum_decl: &EnumDecl) {
+        self.writeln(&format!(
+            "EnumDecl: {} [line {}]:",
+            enum_decl.name, enum_decl.node.line
+        ));
+        self.indent();
+        for variant in &enum_decl.variants {
+            match variant.value {
+                Some(value) => self.writeln(&format!("{} = {}", variant.name, value)),
+                None => self.writeln(&variant.name),
+            }
+        }
+        self.dedent();
+    }
+
     fn visit_struct_decl(&mut self, struct_decl: &StructDecl) {
         self.writeln(&format!(
             "StructDecl: {} [line {}]:",
@@ -214,6 +248,7 @@ impl Visitor<()> for PrettyPrinter {
                     Statement::Break(b) => self.visit_break_stmt(b),
                     Statement::Continue(c) => self.visit_continue_stmt(c),
                     Statement::Switch(s) => self.visit_switch_stmt(s),
+                    Statement::DoWhile(d) => self.visit_do_while_stmt(d),
                 }
             }
         }
@@ -241,6 +276,7 @@ impl Visitor<()> for PrettyPrinter {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
         self.dedent();
         if let Some(else_branch) = &if_stmt.else_branch {
@@ -258,6 +294,7 @@ impl Visitor<()> for PrettyPrinter {
                 Statement::Break(b) => self.visit_break_stmt(b),
                 Statement::Continue(c) => self.visit_continue_stmt(c),
                 Statement::Switch(s) => self.visit_switch_stmt(s),
+                Statement::DoWhile(d) => self.visit_do_while_stmt(d),
             }
             self.dedent();
         }
@@ -289,6 +326,7 @@ impl Visitor<()> for PrettyPrinter {
                 Statement::Break(b) => self.visit_break_stmt(b),
                 Statement::Continue(c) => self.visit_continue_stmt(c),
                 Statement::Switch(s) => self.visit_switch_stmt(s),
+                Statement::DoWhile(d) => self.visit_do_while_stmt(d),
             }
             self.dedent();
         }
@@ -296,7 +334,7 @@ impl Visitor<()> for PrettyPrinter {
     }
 
     fn visit_case_stmt(&mut self, case_stmt: &CaseStmt) {
-        self.writeln(&format!("Case {}:", case_stmt.value.value));
+        self.writeln(&format!("Case {}:", case_stmt.value_label()));
         self.indent();
         match case_stmt.body.as_ref() {
             Statement::VariableDecl(v) => self.visit_var_decl(v),
@@ -310,6 +348,7 @@ impl Visitor<()> for PrettyPrinter {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
         self.dedent();
     }
@@ -335,11 +374,39 @@ impl Visitor<()> for PrettyPrinter {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
         self.dedent();
         self.dedent();
     }
 
+    fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt) {
+        self.writeln(&format!("DoWhileStmt [line {}]:", do_while.node.line));
+        self.indent();
+        self.writeln("Body:");
+        self.indent();
+        match do_while.body.as_ref() {
+            Statement::VariableDecl(v) => self.visit_var_decl(v),
+            Statement::Expression(e) => self.visit_expr_stmt(e),
+            Statement::If(i) => self.visit_if_stmt(i),
+            Statement::While(w) => self.visit_while_stmt(w),
+            Statement::For(f) => self.visit_for_stmt(f),
+            Statement::Return(r) => self.visit_return_stmt(r),
+            Statement::Block(b) => self.visit_block(b),
+            Statement::Empty(e) => self.visit_empty_stmt(e),
+            Statement::Break(b) => self.visit_break_stmt(b),
+            Statement::Continue(c) => self.visit_continue_stmt(c),
+            Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
+        }
+        self.dedent();
+        self.write_indent();
+        self.output.push_str("Condition: ");
+        self.format_expression_str(&do_while.condition);
+        self.output.push('\n');
+        self.dedent();
+    }
+
     fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
         self.writeln(&format!("ForStmt [line {}]:", for_stmt.node.line));
         self.indent();
@@ -358,6 +425,7 @@ impl Visitor<()> for PrettyPrinter {
                 Statement::Break(b) => self.visit_break_stmt(b),
                 Statement::Continue(c) => self.visit_continue_stmt(c),
                 Statement::Switch(s) => self.visit_switch_stmt(s),
+                Statement::DoWhile(d) => self.visit_do_while_stmt(d),
             }
             self.dedent();
         }
@@ -387,6 +455,7 @@ impl Visitor<()> for PrettyPrinter {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
         self.dedent();
         self.dedent();
@@ -459,7 +528,7 @@ impl Visitor<()> for PrettyPrinter {
 
     fn visit_struct_access(&mut self, access: &StructAccessExpr) {
         self.format_expression_str(&access.object);
-        self.output.push_str(&format!(".{}", access.field));
+        self.output.push_str(&format!("{}{}", access.operator(), access.field));
     }
 
     fn visit_grouped(&mut self, grouped: &GroupedExpr) {
@@ -471,4 +540,34 @@ impl Visitor<()> for PrettyPrinter {
     fn visit_array_access(&mut self, _access: &ArrayAccessExpr) {
         self.writeln("ArrayAccess");
     }
+
+    fn visit_cast(&mut self, cast: &CastExpr) {
+        self.output.push_str(&format!("({})", cast.target_type));
+        self.format_expression_str(&cast.expr);
+    }
+
+    fn visit_sizeof(&mut self, sizeof: &SizeofExpr) {
+        match &sizeof.operand {
+            SizeofOperand::Type(t) => self.output.push_str(&format!("sizeof({})", t)),
+            SizeofOperand::Expr(e) => {
+                self.output.push_str("sizeof(");
+                self.format_expression_str(e);
+                self.output.push(')');
+            }
+        }
+    }
+
+    fn visit_conditional(&mut self, cond: &ConditionalExpr) {
+        self.format_expression_str(&cond.condition);
+        self.output.push_str(" ? ");
+        self.format_expression_str(&cond.then_expr);
+        self.output.push_str(" : ");
+        self.format_expression_str(&cond.else_expr);
+    }
+
+    fn visit_comma(&mut self, comma: &CommaExpr) {
+        self.format_expression_str(&comma.left);
+        self.output.push_str(", ");
+        self.format_expression_str(&comma.right);
+    }
 }
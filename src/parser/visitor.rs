@@ -7,11 +7,13 @@ pub trait Visitor<T> {
     fn visit_program(&mut self, program: &Program) -> T;
     fn visit_function_decl(&mut self, func: &FunctionDecl) -> T;
     fn visit_struct_decl(&mut self, struct_decl: &StructDecl) -> T;
+    fn visit_enum_decl(&mut self, enum_decl: &EnumDecl) -> T;
     fn visit_var_decl(&mut self, var_decl: &VarDecl) -> T;
     fn visit_param(&mut self, param: &Param) -> T;
     fn visit_block(&mut self, block: &BlockStmt) -> T;
     fn visit_if_stmt(&mut self, if_stmt: &IfStmt) -> T;
     fn visit_while_stmt(&mut self, while_stmt: &WhileStmt) -> T;
+    fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt) -> T;
     fn visit_for_stmt(&mut self, for_stmt: &ForStmt) -> T;
     fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) -> T;
     fn visit_expr_stmt(&mut self, expr_stmt: &ExprStmt) -> T;
@@ -29,6 +31,10 @@ pub trait Visitor<T> {
     fn visit_struct_access(&mut self, access: &StructAccessExpr) -> T;
     fn visit_grouped(&mut self, grouped: &GroupedExpr) -> T;
     fn visit_array_access(&mut self, access: &ArrayAccessExpr) -> T;
+    fn visit_cast(&mut self, cast: &CastExpr) -> T;
+    fn visit_sizeof(&mut self, sizeof: &SizeofExpr) -> T;
+    fn visit_conditional(&mut self, cond: &ConditionalExpr) -> T;
+    fn visit_comma(&mut self, comma: &CommaExpr) -> T;
 }
 
 /// Трейт для Visitor, который модифицирует AST
@@ -36,11 +42,13 @@ pub trait VisitorMut {
     fn visit_program(&mut self, program: &mut Program);
     fn visit_function_decl(&mut self, func: &mut FunctionDecl);
     fn visit_struct_decl(&mut self, struct_decl: &mut StructDecl);
+    fn visit_enum_decl(&mut self, enum_decl: &mut EnumDecl);
     fn visit_var_decl(&mut self, var_decl: &mut VarDecl);
     fn visit_param(&mut self, param: &mut Param);
     fn visit_block(&mut self, block: &mut BlockStmt);
     fn visit_if_stmt(&mut self, if_stmt: &mut IfStmt);
     fn visit_while_stmt(&mut self, while_stmt: &mut WhileStmt);
+    fn visit_do_while_stmt(&mut self, do_while: &mut DoWhileStmt);
     fn visit_for_stmt(&mut self, for_stmt: &mut ForStmt);
     fn visit_return_stmt(&mut self, return_stmt: &mut ReturnStmt);
     fn visit_expr_stmt(&mut self, expr_stmt: &mut ExprStmt);
@@ -58,6 +66,10 @@ pub trait VisitorMut {
     fn visit_struct_access(&mut self, access: &mut StructAccessExpr);
     fn visit_grouped(&mut self, grouped: &mut GroupedExpr);
     fn visit_array_access(&mut self, access: &mut ArrayAccessExpr);
+    fn visit_cast(&mut self, cast: &mut CastExpr);
+    fn visit_sizeof(&mut self, sizeof: &mut SizeofExpr);
+    fn visit_conditional(&mut self, cond: &mut ConditionalExpr);
+    fn visit_comma(&mut self, comma: &mut CommaExpr);
 }
 
 /// Базовый Visitor, который ничего не делает (возвращает ())
@@ -81,6 +93,10 @@ impl DefaultVisitor {
                     self.visit_expression(elem);
                 }
             }
+            Expression::Cast(c) => self.visit_cast(c),
+            Expression::Sizeof(s) => self.visit_sizeof(s),
+            Expression::Conditional(c) => self.visit_conditional(c),
+            Expression::Comma(c) => self.visit_comma(c),
         }
     }
 
@@ -98,6 +114,7 @@ impl DefaultVisitor {
             Statement::Break(b) => self.visit_break_stmt(b),
             Statement::Continue(c) => self.visit_continue_stmt(c),
             Statement::Switch(s) => self.visit_switch_stmt(s),
+            Statement::DoWhile(d) => self.visit_do_while_stmt(d),
         }
     }
 }
@@ -112,6 +129,7 @@ impl Visitor<()> for DefaultVisitor {
                     self.visit_function_decl(&func);
                 }
                 Declaration::Struct(s) => self.visit_struct_decl(s),
+                Declaration::Enum(e) => self.visit_enum_decl(e),
                 Declaration::Variable(v) => self.visit_var_decl(v),
             }
         }
@@ -130,6 +148,8 @@ impl Visitor<()> for DefaultVisitor {
         }
     }
 
+    fn visit_enum_decl(&mut self, _enum_decl: &EnumDecl) {}
+
     fn visit_var_decl(&mut self, var_decl: &VarDecl) {
         if let Some(init) = &var_decl.initializer {
             self.visit_expression(init);
@@ -163,7 +183,7 @@ impl Visitor<()> for DefaultVisitor {
     }
 
     fn visit_case_stmt(&mut self, case_stmt: &CaseStmt) {
-        self.visit_literal(&case_stmt.value);
+        self.visit_expression(&case_stmt.value);
         self.visit_statement(&case_stmt.body);
     }
 
@@ -172,6 +192,11 @@ impl Visitor<()> for DefaultVisitor {
         self.visit_statement(&while_stmt.body);
     }
 
+    fn visit_do_while_stmt(&mut self, do_while: &DoWhileStmt) {
+        self.visit_statement(&do_while.body);
+        self.visit_expression(&do_while.condition);
+    }
+
     fn visit_for_stmt(&mut self, for_stmt: &ForStmt) {
         if let Some(init) = &for_stmt.init {
             self.visit_statement(init);
@@ -234,4 +259,25 @@ impl Visitor<()> for DefaultVisitor {
         self.visit_expression(&aa.array);
         self.visit_expression(&aa.index);
     }
+
+    fn visit_cast(&mut self, cast: &CastExpr) {
+        self.visit_expression(&cast.expr);
+    }
+
+    fn visit_sizeof(&mut self, sizeof: &SizeofExpr) {
+        if let SizeofOperand::Expr(expr) = &sizeof.operand {
+            self.visit_expression(expr);
+        }
+    }
+
+    fn visit_conditional(&mut self, cond: &ConditionalExpr) {
+        self.visit_expression(&cond.condition);
+        self.visit_expression(&cond.then_expr);
+        self.visit_expression(&cond.else_expr);
+    }
+
+    fn visit_comma(&mut self, comma: &CommaExpr) {
+        self.visit_expression(&comma.left);
+        self.visit_expression(&comma.right);
+    }
 }
@@ -1,8 +1,31 @@
 //! Ошибки препроцессора.
 
 use crate::common::position::Position;
+use std::fmt;
 use thiserror::Error;
 
+/// Место директивы `#include`: файл и строка, из которых включен
+/// следующий по стеку файл.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludeFrame {
+    pub file: String,
+    pub line: usize,
+}
+
+/// Стек включений от корневого файла к текущему.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IncludeStack(pub Vec<IncludeFrame>);
+
+impl fmt::Display for IncludeStack {
+    /// Печатает стек от ближайшего включения к корневому файлу.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in self.0.iter().rev() {
+            write!(f, "\n  включен из {}:{}", frame.file, frame.line)?;
+        }
+        Ok(())
+    }
+}
+
 /// Ошибки, возникающие в процессе работы препроцессора.
 #[derive(Debug, Error)]
 pub enum PreprocessorError {
@@ -25,12 +48,15 @@ pub enum PreprocessorError {
     #[error("Некорректное имя макроса: '{name}'")]
     InvalidMacroName { name: String },
 
-    #[error("Рекурсивное определение макроса: '{name}'")]
+    #[error("Рекурсивное определение макроса: '{name}' (превышен предел подстановок)")]
     MacroRecursion { name: String },
 
     #[error("Ошибка подстановки макроса: {message}")]
     MacroExpansion { message: String },
 
+    #[error("Ошибка подстановки макроса: незавершенный вызов макроса '{name}'")]
+    UnterminatedMacroCall { name: String },
+
     #[error("{position}: Непарная директива #else")]
     UnmatchedElse { position: Position },
 
@@ -45,6 +71,56 @@ pub enum PreprocessorError {
 
     #[error("{position}: Некорректный синтаксис директивы")]
     InvalidSyntax { position: Position, details: String },
+
+    #[error("{file}:{position}: Файл '{name}' не найден{stack}")]
+    IncludeNotFound {
+        file: String,
+        position: Position,
+        name: String,
+        stack: IncludeStack,
+    },
+
+    #[error("{file}:{position}: Циклическое включение файла '{name}'{stack}")]
+    IncludeCycle {
+        file: String,
+        position: Position,
+        name: String,
+        stack: IncludeStack,
+    },
+
+    #[error("{file}:{position}: Не удалось прочитать '{name}': {reason}{stack}")]
+    IncludeRead {
+        file: String,
+        position: Position,
+        name: String,
+        reason: String,
+        stack: IncludeStack,
+    },
+
+    #[error("{file}: {error}{stack}")]
+    InIncludedFile {
+        file: String,
+        error: Box<PreprocessorError>,
+        stack: IncludeStack,
+    },
+}
+
+impl PreprocessorError {
+    /// Привязывает ошибку из включенного файла к стеку включений.
+    /// Ошибки `#include` уже содержат стек и возвращаются без изменений.
+    pub fn in_included_file(self, file: &str, stack: &IncludeStack) -> Self {
+        match self {
+            error @ (PreprocessorError::IncludeNotFound { .. }
+            | PreprocessorError::IncludeCycle { .. }
+            | PreprocessorError::IncludeRead { .. }
+            | PreprocessorError::InIncludedFile { .. }) => error,
+            error => PreprocessorError::InIncludedFile {
+                file: file.to_string(),
+                error: Box::new(error),
+                stack: stack.clone(),
+            },
+        }
+    }
 }
 
 /// Результат работы препроцессора с возможной ошибкой.
@@ -0,0 +1,279 @@
+//! Константные выражения директив `#if` и `#elif`.
+//!
+//! Выражение вычисляется в `i64` с приоритетами операторов C. Перед
+//! вычислением `defined X` и `defined(X)` заменяются на `1` или `0`,
+//! затем раскрываются макросы, а оставшиеся идентификаторы считаются
+//! равными нулю.
+
+use crate::common::token::TokenKind;
+use crate::lexer::Scanner;
+
+/// Лексема константного выражения.
+#[derive(Debug, Clone, PartialEq)]
+enum ExprToken {
+    Number(i64),
+    Ident(String),
+    Op(&'static str),
+}
+
+/// Операторы выражения; двухсимвольные проверяются первыми.
+const OPERATORS: [&str; 26] = [
+    "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "~",
+    "&", "^", "|", "?", ":", "(", ")", ",", "=",
+];
+
+/// Лексема вместе с ее положением в тексте выражения.
+struct Spanned {
+    token: ExprToken,
+    start: usize,
+    end: usize,
+}
+
+/// Заменяет операторы `defined` на `1` или `0`. Выполняется до раскрытия
+/// макросов, чтобы имена под `defined` не подставлялись.
+pub fn replace_defined(text: &str, is_defined: impl Fn(&str) -> bool) -> Result<String, String> {
+    let tokens = tokenize(text)?;
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if !matches!(&tokens[i].token, ExprToken::Ident(name) if name == "defined") {
+            i += 1;
+            continue;
+        }
+        let (name, last) = match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) {
+            (Some(Spanned { token: ExprToken::Ident(name), .. }), _, _) => (name, i + 1),
+            (
+                Some(Spanned { token: ExprToken::Op("("), .. }),
+                Some(Spanned { token: ExprToken::Ident(name), .. }),
+                Some(Spanned { token: ExprToken::Op(")"), .. }),
+            ) => (name, i + 3),
+            _ => return Err("после 'defined' ожидалось имя макроса".to_string()),
+        };
+        result.push_str(&text[copied..tokens[i].start]);
+        result.push_str(if is_defined(name) { "1" } else { "0" });
+        copied = tokens[last].end;
+        i = last + 1;
+    }
+
+    result.push_str(&text[copied..]);
+    Ok(result)
+}
+
+/// Вычисляет выражение после раскрытия макросов.
+pub fn evaluate(text: &str) -> Result<i64, String> {
+    let tokens = tokenize(text)?.into_iter().map(|s| s.token).collect();
+    let mut parser = ExprParser { tokens, current: 0 };
+    let value = parser.conditional()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(token) => Err(format!("неожиданная лексема {}", describe(token))),
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Spanned>, String> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while let Some(c) = text[i..].chars().next() {
+        let start = i;
+        let token = if c.is_whitespace() {
+            i += c.len_utf8();
+            continue;
+        } else if c.is_ascii_digit() {
+            let len = text[i..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(text.len() - i);
+            i += len;
+            ExprToken::Number(parse_number(&text[start..i])?)
+        } else if Scanner::is_identifier_start(c) {
+            let len = text[i..]
+                .find(|c: char| !Scanner::is_identifier_continue(c))
+                .unwrap_or(text.len() - i);
+            i += len;
+            ExprToken::Ident(text[start..i].to_string())
+        } else if c == '\'' || c == '"' {
+            match Scanner::scan_literal(&text[i..]) {
+                Some((TokenKind::CharLiteral(value), len)) => {
+                    i += len;
+                    ExprToken::Number(value as i64)
+                }
+                Some(_) => return Err("строковый литерал в условии".to_string()),
+                None => return Err("некорректный литерал".to_string()),
+            }
+        } else {
+            let op = OPERATORS
+                .iter()
+                .find(|op| text[i..].starts_with(*op))
+                .ok_or_else(|| format!("недопустимый символ '{}'", c))?;
+            i += op.len();
+            ExprToken::Op(op)
+        };
+        tokens.push(Spanned {
+            token,
+            start,
+            end: i,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Разбирает целое число: десятичное, `0x` шестнадцатеричное или
+/// восьмеричное с ведущим нулем; суффиксы `u` и `l` допускаются.
+fn parse_number(text: &str) -> Result<i64, String> {
+    let digits = text.trim_end_matches(['u', 'U', 'l', 'L']);
+    let hex = digits.strip_prefix("0x").or(digits.strip_prefix("0X"));
+    let (digits, radix) = if let Some(hex) = hex {
+        (hex, 16)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (&digits[1..], 8)
+    } else {
+        (digits, 10)
+    };
+    u64::from_str_radix(digits, radix)
+        .map(|value| value as i64)
+        .map_err(|_| format!("некорректное число '{}'", text))
+}
+
+fn describe(token: &ExprToken) -> String {
+    match token {
+        ExprToken::Number(value) => format!("'{}'", value),
+        ExprToken::Ident(name) => format!("'{}'", name),
+        ExprToken::Op(op) => format!("'{}'", op),
+    }
+}
+
+/// Бинарные операторы по уровням приоритета, от низшего к высшему.
+const BINARY_LEVELS: [&[&str]; 10] = [
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", "<=", ">", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+/// Парсер константного выражения методом рекурсивного спуска.
+struct ExprParser {
+    tokens: Vec<ExprToken>,
+    current: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&ExprToken> {
+        self.tokens.get(self.current)
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        let matched = matches!(self.peek(), Some(ExprToken::Op(found)) if *found == op);
+        if matched {
+            self.current += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), String> {
+        if self.eat(op) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(token) => Err(format!("ожидалось '{}', найдено {}", op, describe(token))),
+            None => Err(format!("ожидалось '{}'", op)),
+        }
+    }
+
+    /// `cond ? a : b`, правоассоциативный
+    fn conditional(&mut self) -> Result<i64, String> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(condition);
+        }
+        let then_value = self.conditional()?;
+        self.expect(":")?;
+        let else_value = self.conditional()?;
+        Ok(if condition != 0 { then_value } else { else_value })
+    }
+
+    fn binary(&mut self, level: usize) -> Result<i64, String> {
+        if level == BINARY_LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = BINARY_LEVELS[level].iter().find(|op| self.eat(op)) {
+            let right = self.binary(level + 1)?;
+            left = apply_binary(op, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        if self.eat("-") {
+            return Ok(self.unary()?.wrapping_neg());
+        }
+        if self.eat("+") {
+            return self.unary();
+        }
+        if self.eat("!") {
+            return Ok((self.unary()? == 0) as i64);
+        }
+        if self.eat("~") {
+            return Ok(!self.unary()?);
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<i64, String> {
+        let token = self.peek().cloned();
+        match token {
+            Some(ExprToken::Number(value)) => {
+                self.current += 1;
+                Ok(value)
+            }
+            // Имена, оставшиеся после подстановки макросов, равны нулю
+            Some(ExprToken::Ident(_)) => {
+                self.current += 1;
+                Ok(0)
+            }
+            Some(ExprToken::Op("(")) => {
+                self.current += 1;
+                let value = self.conditional()?;
+                self.expect(")")?;
+                Ok(value)
+            }
+            Some(token) => Err(format!("ожидалось выражение, найдено {}", describe(&token))),
+            None => Err("ожидалось выражение".to_string()),
+        }
+    }
+}
+
+fn apply_binary(op: &str, left: i64, right: i64) -> Result<i64, String> {
+    let value = match op {
+        "||" => (left != 0 || right != 0) as i64,
+        "&&" => (left != 0 && right != 0) as i64,
+        "|" => left | right,
+        "^" => left ^ right,
+        "&" => left & right,
+        "==" => (left == right) as i64,
+        "!=" => (left != right) as i64,
+        "<" => (left < right) as i64,
+        "<=" => (left <= right) as i64,
+        ">" => (left > right) as i64,
+        ">=" => (left >= right) as i64,
+        "<<" => left.wrapping_shl(right as u32),
+        ">>" => left.wrapping_shr(right as u32),
+        "+" => left.wrapping_add(right),
+        "-" => left.wrapping_sub(right),
+        "*" => left.wrapping_mul(right),
+        "/" | "%" if right == 0 => return Err("деление на ноль".to_string()),
+        "/" => left.wrapping_div(right),
+        "%" => left.wrapping_rem(right),
+        _ => unreachable!("неизвестный оператор {}", op),
+    };
+    Ok(value)
+}
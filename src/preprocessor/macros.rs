@@ -1,13 +1,34 @@
 //! Таблица макросов для препроцессора.
+//!
+//! Подстановка работает с лексемами препроцессора по правилам C:
+//! аргументы функциональных макросов раскрываются заранее (кроме операндов
+//! `#` и `##`), результат подстановки повторно просматривается вместе с
+//! остатком строки. Каждая лексема помнит макросы, из раскрытия которых она
+//! получена. Встреченное внутри такого раскрытия имя макроса «окрашивается»
+//! и остается нераскрытым навсегда, поэтому `#define A B` и `#define B A`
+//! дают `A`. Ошибкой рекурсии считается только превышение предела числа
+//! подстановок в одной строке.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
 
 use super::PreprocessorError;
+use crate::lexer::Scanner;
+
+/// Имя параметра, принимающего переменные аргументы.
+const VA_ARGS: &str = "__VA_ARGS__";
+
+/// Предел числа подстановок при раскрытии одной последовательности лексем.
+const MAX_EXPANSIONS: usize = 1 << 16;
 
 /// Определение макроса.
 #[derive(Debug, Clone)]
 pub struct MacroDefinition {
     name: String,
+    /// Параметры функционального макроса; `None` для макроса-объекта
+    params: Option<Vec<String>>,
+    /// Принимает ли макрос `...`
+    variadic: bool,
     value: String,
 }
 
@@ -15,10 +36,66 @@ impl MacroDefinition {
     pub fn new(name: &str, value: &str) -> Self {
         Self {
             name: name.to_string(),
+            params: None,
+            variadic: false,
+            value: value.to_string(),
+        }
+    }
+
+    /// Создает функциональный макрос.
+    pub fn function(name: &str, params: Vec<String>, variadic: bool, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            params: Some(params),
+            variadic,
             value: value.to_string(),
         }
     }
 
+    /// Разбирает текст директивы после `#define`: `NAME value` или
+    /// `NAME(a, b, ...) value`. Список параметров начинается только сразу
+    /// после имени, без пробела. Возвращает причину ошибки для
+    /// некорректного списка параметров или неверного использования `#`/`##`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        // Недопустимое имя берется целиком, чтобы показать его в ошибке
+        let name_len = match text.find(|c: char| !Scanner::is_identifier_continue(c)) {
+            Some(0) => text.find(char::is_whitespace).unwrap_or(text.len()),
+            end => end.unwrap_or(text.len()),
+        };
+        let (name, rest) = text.split_at(name_len);
+
+        let Some(rest) = rest.strip_prefix('(') else {
+            return Ok(Self::new(name, rest.trim()));
+        };
+        let Some(close) = rest.find(')') else {
+            return Err("незавершенный список параметров макроса".to_string());
+        };
+
+        let mut params = Vec::new();
+        let mut variadic = false;
+        let list = rest[..close].trim();
+        if !list.is_empty() {
+            for param in list.split(',').map(str::trim) {
+                if variadic {
+                    return Err("'...' должно быть последним параметром".to_string());
+                }
+                if param == "..." {
+                    variadic = true;
+                } else if !MacroTable::is_valid_macro_name(param) || param == VA_ARGS {
+                    return Err(format!("некорректный параметр макроса '{}'", param));
+                } else if params.iter().any(|p| p == param) {
+                    return Err(format!("повторяющийся параметр макроса '{}'", param));
+                } else {
+                    params.push(param.to_string());
+                }
+            }
+        }
+
+        let definition = Self::function(name, params, variadic, rest[close + 1..].trim());
+        definition.check_operators()?;
+        Ok(definition)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -26,33 +103,120 @@ impl MacroDefinition {
     pub fn value(&self) -> &str {
         &self.value
     }
+
+    /// Параметры функционального макроса.
+    pub fn params(&self) -> Option<&[String]> {
+        self.params.as_deref()
+    }
+
+    pub fn is_function_like(&self) -> bool {
+        self.params.is_some()
+    }
+
+    pub fn is_variadic(&self) -> bool {
+        self.variadic
+    }
+
+    /// Индекс параметра по имени; `__VA_ARGS__` следует за именованными.
+    fn param_index(&self, name: &str) -> Option<usize> {
+        let params = self.params.as_ref()?;
+        match params.iter().position(|p| p == name) {
+            Some(index) => Some(index),
+            None if self.variadic && name == VA_ARGS => Some(params.len()),
+            None => None,
+        }
+    }
+
+    /// Проверяет, что `##` не стоит на краю тела, а `#` применяется
+    /// только к параметрам.
+    fn check_operators(&self) -> Result<(), String> {
+        let body = tokenize(&self.value);
+        let items = self.body_items(&body);
+        if matches!(items.first(), Some(BodyItem::Paste))
+            || matches!(items.last(), Some(BodyItem::Paste))
+        {
+            return Err("'##' не может стоять в начале или конце макроса".to_string());
+        }
+        let stray_hash = items
+            .iter()
+            .any(|item| matches!(item, BodyItem::Token(t) if t.text == "#"));
+        if stray_hash && self.is_function_like() {
+            return Err("за '#' должен следовать параметр макроса".to_string());
+        }
+        Ok(())
+    }
+
+    /// Разбивает тело макроса на лексемы, параметры и операторы `#`/`##`.
+    fn body_items(&self, body: &[PpToken]) -> Vec<BodyItem> {
+        let mut items = Vec::new();
+        let mut i = 0;
+        while i < body.len() {
+            let token = &body[i];
+            let next = next_non_space(body, i + 1);
+            if token.text == "#" && next.is_some_and(|j| j == i + 1 && body[j].text == "#") {
+                while matches!(items.last(), Some(BodyItem::Token(t)) if t.kind == PpKind::Space) {
+                    items.pop();
+                }
+                items.push(BodyItem::Paste);
+                i = next_non_space(body, i + 2).unwrap_or(body.len());
+                continue;
+            }
+            let stringified = next
+                .filter(|_| token.text == "#" && self.is_function_like())
+                .and_then(|j| self.param_index(&body[j].text).map(|index| (j, index)));
+            if let Some((j, index)) = stringified {
+                items.push(BodyItem::Stringify(index));
+                i = j + 1;
+                continue;
+            }
+            match self.param_index(&token.text).filter(|_| token.kind == PpKind::Ident) {
+                Some(index) => items.push(BodyItem::Param(index)),
+                None => items.push(BodyItem::Token(token.clone())),
+            }
+            i += 1;
+        }
+        items
+    }
+}
+
+/// Фрагмент результата подстановки вместе с его происхождением.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedFragment {
+    pub text: String,
+    /// Индекс символа входной строки, с которого скопирован фрагмент, или
+    /// место вызова внешнего макроса, если фрагмент получен раскрытием
+    pub origin: usize,
+    /// Макросы, при раскрытии которых получен фрагмент, от внешнего к внутреннему
+    pub macros: Rc<[String]>,
 }
 
 /// Таблица макросов для хранения и подстановки.
 #[derive(Debug, Default)]
 pub struct MacroTable {
     macros: HashMap<String, MacroDefinition>,
-    expansion_stack: HashSet<String>,
 }
 
 impl MacroTable {
     pub fn new() -> Self {
         Self {
             macros: HashMap::new(),
-            expansion_stack: HashSet::new(),
         }
     }
 
     /// Определяет новый макрос.
     pub fn define(&mut self, name: &str, value: &str) -> Result<(), PreprocessorError> {
-        if !Self::is_valid_macro_name(name) {
+        self.insert(MacroDefinition::new(name, value))
+    }
+
+    /// Добавляет готовое определение макроса.
+    pub fn insert(&mut self, definition: MacroDefinition) -> Result<(), PreprocessorError> {
+        if !Self::is_valid_macro_name(definition.name()) {
             return Err(PreprocessorError::InvalidMacroName {
-                name: name.to_string(),
+                name: definition.name().to_string(),
             });
         }
 
-        let definition = MacroDefinition::new(name, value);
-        self.macros.insert(name.to_string(), definition);
+        self.macros.insert(definition.name().to_string(), definition);
         Ok(())
     }
 
@@ -67,73 +231,136 @@ impl MacroTable {
     }
 
     /// Расширяет макросы в строке.
-    pub fn expand(&mut self, input: &str) -> Result<String, PreprocessorError> {
-        let mut result = String::with_capacity(input.len());
-        let mut current_word = String::new();
-        let mut in_identifier = false;
-
-        for c in input.chars() {
-            if c.is_alphanumeric() || c == '_' {
-                if !in_identifier {
-                    in_identifier = true;
-                    current_word.clear();
-                }
-                current_word.push(c);
-                result.push(c);
-            } else {
-                if in_identifier {
-                    in_identifier = false;
-                    let macro_value = self
-                        .macros
-                        .get(&current_word)
-                        .map(|m| m.value().to_string());
+    pub fn expand(&self, input: &str) -> Result<String, PreprocessorError> {
+        let tokens = self.expand_tokens(tokenize(input))?;
+        Ok(tokens.into_iter().map(|token| token.text).collect())
+    }
 
-                    if let Some(value) = macro_value {
-                        let word_len = current_word.len();
-                        result.truncate(result.len() - word_len);
+    /// Расширяет макросы в строке и сообщает, откуда взят каждый фрагмент.
+    pub fn expand_fragments(
+        &self,
+        input: &str,
+    ) -> Result<Vec<ExpandedFragment>, PreprocessorError> {
+        let tokens = self.expand_tokens(tokenize(input))?;
+        Ok(tokens
+            .into_iter()
+            .map(|token| ExpandedFragment {
+                text: token.text,
+                origin: token.origin,
+                macros: token.macros,
+            })
+            .collect())
+    }
 
-                        if self.expansion_stack.contains(&current_word) {
-                            return Err(PreprocessorError::MacroRecursion {
-                                name: current_word.clone(),
-                            });
-                        }
+    /// Раскрывает макросы в последовательности лексем до неподвижной точки.
+    fn expand_tokens(&self, tokens: Vec<PpToken>) -> Result<Vec<PpToken>, PreprocessorError> {
+        let mut input: VecDeque<PpToken> = tokens.into();
+        let mut output = Vec::with_capacity(input.len());
+        let mut expansions = 0;
 
-                        self.expansion_stack.insert(current_word.clone());
+        while let Some(token) = input.pop_front() {
+            let definition = match self.macros.get(&token.text) {
+                Some(definition) if token.kind == PpKind::Ident && !token.painted => definition,
+                _ => {
+                    output.push(token);
+                    continue;
+                }
+            };
+            // Имя функционального макроса без скобок остается как есть
+            if definition.is_function_like() && !next_is_open_paren(&input) {
+                output.push(token);
+                continue;
+            }
+            if token.hide.contains(definition.name()) {
+                // `#define f(x) x + f(x)`: `f(1)` дает `1 + f(1)`
+                output.push(PpToken {
+                    painted: true,
+                    ..token
+                });
+                continue;
+            }
+            expansions += 1;
+            if expansions > MAX_EXPANSIONS {
+                return Err(PreprocessorError::MacroRecursion {
+                    name: definition.name().to_string(),
+                });
+            }
 
-                        let expanded = self.expand(&value)?;
-                        result.push_str(&expanded);
+            let args = if definition.is_function_like() {
+                collect_args(&mut input, definition)?
+            } else {
+                Vec::new()
+            };
+            let mut hide = (*token.hide).clone();
+            hide.insert(definition.name().to_string());
+            let hide = Rc::new(hide);
 
-                        self.expansion_stack.remove(&current_word);
-                    }
-                }
-                result.push(c);
+            let replacement = self.substitute(definition, &token, &args)?;
+            for token in replacement.into_iter().rev() {
+                input.push_front(token.hidden(&hide));
             }
         }
 
-        if in_identifier && !current_word.is_empty() {
-            let macro_value = self
-                .macros
-                .get(&current_word)
-                .map(|m| m.value().to_string());
+        Ok(output)
+    }
 
-            if let Some(value) = macro_value {
-                let word_len = current_word.len();
-                result.truncate(result.len() - word_len);
+    /// Подставляет аргументы в тело макроса и выполняет `#` и `##`.
+    /// Лексемы тела привязываются к месту вызова `site`, аргументы сохраняют
+    /// собственное происхождение.
+    fn substitute(
+        &self,
+        definition: &MacroDefinition,
+        site: &PpToken,
+        args: &[Vec<PpToken>],
+    ) -> Result<Vec<PpToken>, PreprocessorError> {
+        let macros: Rc<[String]> =
+            site.macros.iter().cloned().chain([definition.name().to_string()]).collect();
+        let body = tokenize(definition.value());
+        let items = definition.body_items(&body);
+        let mut expanded_args: Vec<Option<Vec<PpToken>>> = vec![None; args.len()];
+        let mut output: Vec<PpToken> = Vec::new();
+        let mut paste = false;
 
-                if self.expansion_stack.contains(&current_word) {
-                    return Err(PreprocessorError::MacroRecursion {
-                        name: current_word.clone(),
-                    });
+        for (i, item) in items.iter().enumerate() {
+            let tokens = match item {
+                BodyItem::Paste => {
+                    paste = true;
+                    continue;
+                }
+                BodyItem::Token(token) => vec![token.clone().expanded_from(site, &macros)],
+                BodyItem::Stringify(index) => {
+                    let text = stringify(&args[*index]);
+                    vec![PpToken::new(PpKind::Literal, text, 0).expanded_from(site, &macros)]
+                }
+                // Операнды `##` подставляются без предварительного раскрытия
+                BodyItem::Param(index)
+                    if paste || matches!(items.get(i + 1), Some(BodyItem::Paste)) =>
+                {
+                    args[*index].clone()
                 }
+                BodyItem::Param(index) => match &expanded_args[*index] {
+                    Some(tokens) => tokens.clone(),
+                    None => {
+                        let tokens = self.expand_tokens(args[*index].clone())?;
+                        expanded_args[*index] = Some(tokens.clone());
+                        tokens
+                    }
+                },
+            };
 
-                self.expansion_stack.insert(current_word.clone());
-                let expanded = self.expand(&value)?;
-                result.push_str(&expanded);
-                self.expansion_stack.remove(&current_word);
+            let mut tokens = tokens.into_iter();
+            if std::mem::take(&mut paste) {
+                match (output.pop(), tokens.next()) {
+                    (Some(left), Some(right)) => {
+                        output.push(left.pasted(&right).expanded_from(site, &macros))
+                    }
+                    (left, right) => output.extend(left.into_iter().chain(right)),
+                }
             }
+            output.extend(tokens);
         }
 
-        Ok(result)
+        Ok(output)
     }
 
     /// Проверяет валидность имени макроса.
@@ -143,11 +370,11 @@ impl MacroTable {
         }
 
         let first_char = name.chars().next().unwrap();
-        if !first_char.is_alphabetic() && first_char != '_' {
+        if !Scanner::is_identifier_start(first_char) {
             return false;
         }
 
-        name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        name.chars().all(Scanner::is_identifier_continue)
     }
 
     /// Возвращает итератор по всем определенным макросам.
@@ -155,3 +382,233 @@ impl MacroTable {
         self.macros.iter()
     }
 }
+
+/// Вид лексемы препроцессора.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PpKind {
+    Ident,
+    /// Строковый или символьный литерал
+    Literal,
+    Space,
+    Punct,
+}
+
+/// Лексема препроцессора вместе с макросами, из раскрытия которых она
+/// получена.
+#[derive(Debug, Clone)]
+struct PpToken {
+    kind: PpKind,
+    text: String,
+    hide: Rc<HashSet<String>>,
+    /// Имя макроса, встреченное внутри собственного раскрытия; больше не
+    /// раскрывается
+    painted: bool,
+    /// Индекс символа входной строки, см. [`ExpandedFragment::origin`]
+    origin: usize,
+    /// Цепочка раскрытых макросов, см. [`ExpandedFragment::macros`]
+    macros: Rc<[String]>,
+}
+
+impl PpToken {
+    fn new(kind: PpKind, text: String, origin: usize) -> Self {
+        Self {
+            kind,
+            text,
+            hide: Rc::default(),
+            painted: false,
+            origin,
+            macros: Rc::from([]),
+        }
+    }
+
+    /// Привязывает лексему тела макроса к месту вызова.
+    fn expanded_from(mut self, site: &PpToken, macros: &Rc<[String]>) -> Self {
+        self.origin = site.origin;
+        self.macros = Rc::clone(macros);
+        self
+    }
+
+    /// Добавляет макросы к множеству запрещенных для повторного раскрытия.
+    fn hidden(mut self, hide: &Rc<HashSet<String>>) -> Self {
+        self.hide = if self.hide.is_subset(hide) {
+            Rc::clone(hide)
+        } else {
+            Rc::new(self.hide.union(hide).cloned().collect())
+        };
+        self
+    }
+
+    /// Склеивает две лексемы оператором `##`.
+    fn pasted(self, right: &PpToken) -> Self {
+        let text = self.text + &right.text;
+        let kind = if MacroTable::is_valid_macro_name(&text) {
+            PpKind::Ident
+        } else {
+            PpKind::Punct
+        };
+        Self {
+            kind,
+            text,
+            painted: false,
+            ..self
+        }
+    }
+}
+
+/// Элемент тела макроса.
+#[derive(Debug)]
+enum BodyItem {
+    Token(PpToken),
+    Param(usize),
+    Stringify(usize),
+    Paste,
+}
+
+/// Разбивает строку на идентификаторы, литералы, пробельные промежутки и
+/// отдельные символы. Идентификаторы и литералы распознаются по правилам
+/// сканера, поэтому макросы не подставляются внутри строк.
+fn tokenize(input: &str) -> Vec<PpToken> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut origin = 0;
+
+    while let Some(c) = input[start..].chars().next() {
+        let rest = &input[start..];
+        let run = |pred: fn(char) -> bool| rest.find(|c: char| !pred(c)).unwrap_or(rest.len());
+        let (kind, len) = if Scanner::is_identifier_start(c) {
+            (PpKind::Ident, run(Scanner::is_identifier_continue))
+        } else if c.is_ascii_digit() {
+            // Число вместе с суффиксом остается одной лексемой
+            (PpKind::Punct, run(Scanner::is_identifier_continue))
+        } else if c.is_whitespace() {
+            (PpKind::Space, run(char::is_whitespace))
+        } else if let Some((_, len)) = Scanner::scan_literal(rest) {
+            (PpKind::Literal, len)
+        } else {
+            (PpKind::Punct, c.len_utf8())
+        };
+        let text = &rest[..len];
+        tokens.push(PpToken::new(kind, text.to_string(), origin));
+        start += len;
+        origin += text.chars().count();
+    }
+
+    tokens
+}
+
+fn next_non_space(tokens: &[PpToken], from: usize) -> Option<usize> {
+    (from..tokens.len()).find(|&i| tokens[i].kind != PpKind::Space)
+}
+
+fn next_non_space_token(input: &VecDeque<PpToken>) -> Option<&PpToken> {
+    input.iter().find(|token| token.kind != PpKind::Space)
+}
+
+fn next_is_open_paren(input: &VecDeque<PpToken>) -> bool {
+    next_non_space_token(input).is_some_and(|token| token.text == "(")
+}
+
+/// Забирает из входа аргументы вызова функционального макроса вместе со
+/// скобками и проверяет их количество.
+fn collect_args(
+    input: &mut VecDeque<PpToken>,
+    definition: &MacroDefinition,
+) -> Result<Vec<Vec<PpToken>>, PreprocessorError> {
+    while input.front().is_some_and(|token| token.text != "(") {
+        input.pop_front();
+    }
+    input.pop_front();
+
+    let mut args = vec![Vec::new()];
+    let mut commas = Vec::new();
+    let mut depth = 0;
+    let mut closed = false;
+
+    while let Some(token) = input.pop_front() {
+        match token.text.as_str() {
+            _ if token.kind != PpKind::Punct => {}
+            "(" => depth += 1,
+            ")" if depth == 0 => {
+                closed = true;
+                break;
+            }
+            ")" => depth -= 1,
+            "," if depth == 0 => {
+                args.push(Vec::new());
+                commas.push(token);
+                continue;
+            }
+            _ => {}
+        }
+        args.last_mut().unwrap().push(token);
+    }
+
+    if !closed {
+        return Err(PreprocessorError::UnterminatedMacroCall {
+            name: definition.name().to_string(),
+        });
+    }
+
+    let mut args: Vec<Vec<PpToken>> = args.into_iter().map(trim_spaces).collect();
+    let count = definition.params().map_or(0, <[String]>::len);
+    if count == 0 && args.len() == 1 && args[0].is_empty() {
+        args.clear();
+    }
+
+    if definition.is_variadic() && args.len() >= count {
+        let mut rest = args.split_off(count).into_iter();
+        let mut va_args = rest.next().unwrap_or_default();
+        for (arg, comma) in rest.zip(commas.into_iter().skip(count)) {
+            let space = PpToken {
+                kind: PpKind::Space,
+                text: " ".to_string(),
+                ..comma.clone()
+            };
+            va_args.push(comma);
+            va_args.push(space);
+            va_args.extend(arg);
+        }
+        args.push(va_args);
+    } else if args.len() != count {
+        return Err(PreprocessorError::MacroExpansion {
+            message: format!(
+                "макрос '{}' ожидает аргументов: {}, передано: {}",
+                definition.name(),
+                count,
+                args.len()
+            ),
+        });
+    }
+
+    Ok(args)
+}
+
+fn trim_spaces(mut tokens: Vec<PpToken>) -> Vec<PpToken> {
+    while tokens.last().is_some_and(|token| token.kind == PpKind::Space) {
+        tokens.pop();
+    }
+    let start = next_non_space(&tokens, 0).unwrap_or(tokens.len());
+    tokens.split_off(start)
+}
+
+/// Превращает аргумент в строковый литерал для оператора `#`; кавычки и
+/// обратные слеши экранируются только внутри литералов.
+fn stringify(arg: &[PpToken]) -> String {
+    let mut result = String::from("\"");
+    for token in arg {
+        match token.kind {
+            PpKind::Space => result.push(' '),
+            PpKind::Ident | PpKind::Punct => result.push_str(&token.text),
+            PpKind::Literal => {
+                for c in token.text.chars() {
+                    if c == '"' || c == '\\' {
+                        result.push('\\');
+                    }
+                    result.push(c);
+                }
+            }
+        }
+    }
+    result.push('"');
+    result
+}
@@ -3,7 +3,9 @@
 //! Обрабатывает директивы препроцессора и очищает исходный код:
 //! 1. Удаляет комментарии
 //! 2. Обрабатывает макросы #define
-//! 3. Поддерживает #ifdef/#ifndef/#endif
+//! 3. Поддерживает #ifdef/#ifndef/#if/#elif/#else/#endif
+//! 4. Подставляет файлы #include "file" и #include <file>, учитывая
+//!    стражи включения и #pragma once
 //!
 //! # Пример использования
 //!
@@ -28,12 +30,28 @@
 //! ```
 
 mod error;
+mod expression;
 mod macros;
+mod source_map;
 
-pub use error::PreprocessorError;
-pub use macros::{MacroDefinition, MacroTable};
+pub use error::{IncludeFrame, IncludeStack, PreprocessorError};
+pub use macros::{ExpandedFragment, MacroDefinition, MacroTable};
+pub use source_map::{SourceLocation, SourceMap};
 
 use crate::common::position::Position;
+use source_map::LineMapper;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Настройки поиска файлов для `#include`.
+#[derive(Debug, Clone, Default)]
+pub struct IncludeOptions {
+    /// Исходный файл; `#include "..."` ищется сначала в его каталоге
+    pub source_file: Option<PathBuf>,
+    /// Каталоги поиска (`-I`)
+    pub search_paths: Vec<PathBuf>,
+}
 
 /// Препроцессор для обработки исходного кода MiniC.
 #[derive(Debug)]
@@ -46,6 +64,16 @@ pub struct Preprocessor<'a> {
     preserve_line_numbers: bool,
     /// Поддержка вложенных директив #if
     support_conditionals: bool,
+    /// Путь к исходному файлу, от каталога которого ищутся `#include "..."`
+    source_file: Option<PathBuf>,
+    /// Каталоги поиска включаемых файлов
+    include_paths: Vec<PathBuf>,
+    /// Директивы `#include`, файлы которых обрабатываются сейчас
+    include_stack: Vec<IncludeFrame>,
+    /// Файлы, отмеченные `#pragma once`
+    once_files: HashSet<PathBuf>,
+    /// Карта соответствия результата исходным файлам
+    source_map: SourceMap,
 }
 
 impl<'a> Preprocessor<'a> {
@@ -56,6 +84,11 @@ impl<'a> Preprocessor<'a> {
             macros: MacroTable::new(),
             preserve_line_numbers: true,
             support_conditionals: true,
+            source_file: None,
+            include_paths: Vec::new(),
+            include_stack: Vec::new(),
+            once_files: HashSet::new(),
+            source_map: SourceMap::default(),
         }
     }
 
@@ -80,25 +113,93 @@ impl<'a> Preprocessor<'a> {
         self.support_conditionals = enable;
     }
 
+    /// Задает путь к исходному файлу для поиска `#include "..."`.
+    pub fn set_source_file(&mut self, path: &Path) {
+        self.source_file = Some(path.to_path_buf());
+    }
+
+    /// Добавляет каталог поиска включаемых файлов.
+    pub fn add_include_path(&mut self, path: &Path) {
+        self.include_paths.push(path.to_path_buf());
+    }
+
+    /// Применяет настройки поиска файлов для `#include`.
+    pub fn set_include_options(&mut self, options: &IncludeOptions) {
+        if let Some(file) = &options.source_file {
+            self.set_source_file(file);
+        }
+        for dir in &options.search_paths {
+            self.add_include_path(dir);
+        }
+    }
+
     /// Обрабатывает исходный код и возвращает очищенную версию.
     pub fn process(&mut self) -> Result<String, PreprocessorError> {
-        let mut result = String::with_capacity(self.source.len());
-        let mut condition_stack = Vec::new();
-
-        let processed_source =
-            self.remove_comments_from_whole_source(self.source, Position::new(1, 1))?;
+        let source = self.source;
+        let file = self.source_file.as_deref().map(Self::canonical);
+        let (result, source_map) = self.process_source(source, file.as_deref())?;
+        self.source_map = source_map;
+        Ok(result)
+    }
 
-        let lines = processed_source.lines().enumerate();
+    /// Карта соответствия строк результата последнего [`Self::process`]
+    /// исходным файлам и макросам.
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
 
-        for (line_num, line) in lines {
-            let line_position = Position::new(line_num + 1, 1);
+    /// Обрабатывает текст одного файла; включенные файлы обрабатываются
+    /// рекурсивно с общей таблицей макросов и собственным стеком условий.
+    fn process_source(
+        &mut self,
+        source: &str,
+        file: Option<&Path>,
+    ) -> Result<(String, SourceMap), PreprocessorError> {
+        let mut result = String::with_capacity(source.len());
+        let file_name: Option<Rc<str>> = file.map(|f| Rc::from(f.display().to_string()));
+        let mut source_map = SourceMap::new(file_name.clone());
+        let mut condition_stack = Vec::new();
 
-            if let Some(directive) = Self::parse_directive(line) {
-                match self.process_directive(directive, &mut condition_stack, line_position)? {
+        let (processed_source, origins) =
+            self.remove_comments_from_whole_source(source, Position::new(1, 1))?;
+
+        // Незавершенный вызов макроса: текст, происхождение его символов и
+        // начала уже поглощенных строк
+        let mut pending: Option<(String, Vec<Position>, Vec<Position>)> = None;
+
+        let mut offset = 0;
+        let mut lines = processed_source.split_inclusive('\n').enumerate().peekable();
+        while let Some((line_num, raw_line)) = lines.next() {
+            let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let line_len = line.chars().count();
+            let line_origins = &origins[offset..offset + line_len];
+            let line_end = origins
+                .get(offset + line_len)
+                .copied()
+                .unwrap_or(Position::new(line_num + 1, 1));
+            offset += raw_line.chars().count();
+
+            let line_start = line_origins.first().copied().unwrap_or(line_end);
+            let line_position = Position::new(line_start.line, 1);
+            let mut mapper = LineMapper::new(file_name.clone());
+
+            // Строки с аргументами многострочного вызова директивами не являются
+            if let Some(directive) = Self::parse_directive(line).filter(|_| pending.is_none()) {
+                let result_kind =
+                    self.process_directive(directive, &mut condition_stack, line_position, file)?;
+                match result_kind {
                     DirectiveResult::SkipLine => continue,
+                    DirectiveResult::Include(text, included_map) => {
+                        result.push_str(&text);
+                        source_map.append(included_map);
+                        continue;
+                    }
                     DirectiveResult::ProcessLine(processed) => {
                         result.push_str(&processed);
                         result.push('\n');
+                        mapper.copied(&line_origins[line_len - directive.chars().count()..]);
+                        source_map.push_line(mapper.finish(line_end));
                         continue;
                     }
                     DirectiveResult::Continue => {}
@@ -108,14 +209,55 @@ impl<'a> Preprocessor<'a> {
             if !self.is_section_active(&condition_stack) {
                 if self.preserve_line_numbers {
                     result.push('\n');
+                    source_map.push_line(mapper.finish(line_start));
                 }
                 continue;
             }
 
-            let expanded_line = self.macros.expand(line)?;
-
-            result.push_str(&expanded_line);
+            let (text, text_origins, joined) = match pending.take() {
+                Some((mut text, mut text_origins, joined)) => {
+                    text.push_str(line);
+                    text_origins.extend_from_slice(line_origins);
+                    (text, text_origins, joined)
+                }
+                None => (line.to_string(), line_origins.to_vec(), Vec::new()),
+            };
+
+            let fragments = match self.macros.expand_fragments(&text) {
+                // Аргументы вызова продолжаются на следующей строке
+                Err(PreprocessorError::UnterminatedMacroCall { .. }) if lines.peek().is_some() => {
+                    let (mut text, mut text_origins, mut joined) = (text, text_origins, joined);
+                    text.push(' ');
+                    text_origins.push(line_end);
+                    joined.push(line_start);
+                    pending = Some((text, text_origins, joined));
+                    continue;
+                }
+                fragments => fragments?,
+            };
+
+            for fragment in fragments {
+                let len = fragment.text.chars().count();
+                let copied = text_origins.get(fragment.origin..fragment.origin + len);
+                match copied {
+                    Some(copied) if fragment.macros.is_empty() => mapper.copied(copied),
+                    _ => {
+                        let origin = text_origins.get(fragment.origin).copied().unwrap_or(line_end);
+                        mapper.expanded(len, origin, &fragment.macros);
+                    }
+                }
+                result.push_str(&fragment.text);
+            }
             result.push('\n');
+            source_map.push_line(mapper.finish(line_end));
+
+            // Вызов раскрыт в первой строке; поглощенные строки остаются пустыми
+            if self.preserve_line_numbers && !joined.is_empty() {
+                for start in joined.into_iter().skip(1).chain([line_start]) {
+                    result.push('\n');
+                    source_map.push_line(LineMapper::new(file_name.clone()).finish(start));
+                }
+            }
         }
 
         if !condition_stack.is_empty() {
@@ -124,7 +266,7 @@ impl<'a> Preprocessor<'a> {
             });
         }
 
-        Ok(result)
+        Ok((result, source_map))
     }
 
     /// Определяет, является ли строка директивой препроцессора.
@@ -143,6 +285,7 @@ impl<'a> Preprocessor<'a> {
         directive: &str,
         condition_stack: &mut Vec<ConditionState>,
         position: Position,
+        file: Option<&Path>,
     ) -> Result<DirectiveResult, PreprocessorError> {
         let parts: Vec<&str> = directive.split_whitespace().collect();
         if parts.is_empty() {
@@ -150,6 +293,21 @@ impl<'a> Preprocessor<'a> {
         }
 
         match parts[0] {
+            "#define" | "#undef" | "#include" | "#pragma"
+                if !self.is_section_active(condition_stack) =>
+            {
+                Ok(DirectiveResult::SkipLine)
+            }
+            "#include" => {
+                let (text, source_map) = self.include(directive, position, file)?;
+                Ok(DirectiveResult::Include(text, source_map))
+            }
+            "#pragma" => {
+                if let (Some(&"once"), Some(file)) = (parts.get(1), file) {
+                    self.once_files.insert(file.to_path_buf());
+                }
+                Ok(DirectiveResult::SkipLine)
+            }
             "#define" => {
                 if parts.len() < 2 {
                     return Err(PreprocessorError::InvalidDirective {
@@ -159,14 +317,17 @@ impl<'a> Preprocessor<'a> {
                     });
                 }
 
-                let name = parts[1];
-                let value = if parts.len() > 2 {
-                    parts[2..].join(" ")
-                } else {
-                    String::new()
-                };
+                let text = directive["#define".len()..].trim();
+                let definition =
+                    MacroDefinition::parse(text).map_err(|reason| {
+                        PreprocessorError::InvalidDirective {
+                            position,
+                            directive: directive.to_string(),
+                            reason,
+                        }
+                    })?;
 
-                self.macros.define(name, &value)?;
+                self.macros.insert(definition)?;
                 Ok(DirectiveResult::SkipLine)
             }
             "#undef" => {
@@ -196,7 +357,10 @@ impl<'a> Preprocessor<'a> {
 
                 let name = parts[1];
                 let is_defined = self.macros.is_defined(name);
-                condition_stack.push(ConditionState::IfDef { active: is_defined });
+                condition_stack.push(ConditionState::IfDef {
+                    active: is_defined,
+                    taken: is_defined,
+                });
 
                 Ok(DirectiveResult::SkipLine)
             }
@@ -217,6 +381,7 @@ impl<'a> Preprocessor<'a> {
                 let is_defined = self.macros.is_defined(name);
                 condition_stack.push(ConditionState::IfNDef {
                     active: !is_defined,
+                    taken: !is_defined,
                 });
 
                 Ok(DirectiveResult::SkipLine)
@@ -238,162 +403,287 @@ impl<'a> Preprocessor<'a> {
                     return Ok(DirectiveResult::SkipLine);
                 }
 
-                if let Some(state) = condition_stack.last_mut() {
-                    match state {
-                        ConditionState::IfDef { active, .. } => {
-                            *active = !*active;
-                        }
-                        ConditionState::IfNDef { active, .. } => {
-                            *active = !*active;
-                        }
-                    }
-                } else {
+                let Some(state) = condition_stack.last_mut() else {
                     return Err(PreprocessorError::UnmatchedElse { position });
+                };
+                let Some(taken) = state.taken() else {
+                    return Err(PreprocessorError::InvalidDirective {
+                        position,
+                        directive: directive.to_string(),
+                        reason: "повторная ветвь #else".to_string(),
+                    });
+                };
+                *state = ConditionState::Else { active: !taken };
+
+                Ok(DirectiveResult::SkipLine)
+            }
+            "#if" => {
+                if !self.support_conditionals {
+                    return Ok(DirectiveResult::SkipLine);
                 }
 
+                // В неактивной секции условие не вычисляется
+                let active = self.is_section_active(condition_stack)
+                    && self.evaluate_condition(directive, "#if", position)?;
+                let taken = active || !self.is_section_active(condition_stack);
+                condition_stack.push(ConditionState::If { active, taken });
+
+                Ok(DirectiveResult::SkipLine)
+            }
+            "#elif" => {
+                if !self.support_conditionals {
+                    return Ok(DirectiveResult::SkipLine);
+                }
+
+                let Some(taken) = condition_stack.last().map(ConditionState::taken) else {
+                    return Err(PreprocessorError::InvalidDirective {
+                        position,
+                        directive: directive.to_string(),
+                        reason: "#elif без #if".to_string(),
+                    });
+                };
+                let Some(taken) = taken else {
+                    return Err(PreprocessorError::InvalidDirective {
+                        position,
+                        directive: directive.to_string(),
+                        reason: "#elif после #else".to_string(),
+                    });
+                };
+                condition_stack.pop();
+                let active = !taken
+                    && self.is_section_active(condition_stack)
+                    && self.evaluate_condition(directive, "#elif", position)?;
+                condition_stack.push(ConditionState::If {
+                    active,
+                    taken: taken || active,
+                });
+
                 Ok(DirectiveResult::SkipLine)
             }
             _ => Ok(DirectiveResult::ProcessLine(directive.to_string())),
         }
     }
 
+    /// Вычисляет условие `#if`/`#elif`: подставляет `defined`, раскрывает
+    /// макросы и считает выражение. Ошибки разбора привязываются к позиции
+    /// директивы.
+    fn evaluate_condition(
+        &self,
+        directive: &str,
+        keyword: &str,
+        position: Position,
+    ) -> Result<bool, PreprocessorError> {
+        let invalid = |reason: String| PreprocessorError::InvalidDirective {
+            position,
+            directive: directive.to_string(),
+            reason,
+        };
+        let text = directive[keyword.len()..].trim();
+        let text = expression::replace_defined(text, |name| self.macros.is_defined(name))
+            .map_err(invalid)?;
+        let text = self.macros.expand(&text)?;
+        let value = expression::evaluate(&text).map_err(invalid)?;
+        Ok(value != 0)
+    }
+
+    /// Подставляет файл из `#include "file"` или `#include <file>`.
+    fn include(
+        &mut self,
+        directive: &str,
+        position: Position,
+        file: Option<&Path>,
+    ) -> Result<(String, SourceMap), PreprocessorError> {
+        let current = Self::display_name(file);
+        let spec = directive.trim_start_matches("#include").trim();
+        let (name, angled) = match (spec.chars().next(), spec.chars().last()) {
+            (Some('"'), Some('"')) if spec.len() >= 2 => (&spec[1..spec.len() - 1], false),
+            (Some('<'), Some('>')) => (&spec[1..spec.len() - 1], true),
+            _ => {
+                return Err(PreprocessorError::InvalidDirective {
+                    position,
+                    directive: directive.to_string(),
+                    reason: "ожидалось \"файл\" или <файл>".to_string(),
+                });
+            }
+        };
+
+        let stack = IncludeStack(self.include_stack.clone());
+        let Some(path) = self.resolve_include(name, angled, file) else {
+            return Err(PreprocessorError::IncludeNotFound {
+                file: current,
+                position,
+                name: name.to_string(),
+                stack,
+            });
+        };
+        if self.once_files.contains(&path) {
+            return Ok((String::new(), SourceMap::default()));
+        }
+
+        // Повтор директивы, которая еще обрабатывается, означает бесконечное
+        // включение; стражи включения до этой точки не доходят
+        let frame = IncludeFrame {
+            file: current.clone(),
+            line: position.line,
+        };
+        if self.include_stack.contains(&frame) {
+            return Err(PreprocessorError::IncludeCycle {
+                file: current,
+                position,
+                name: name.to_string(),
+                stack,
+            });
+        }
+
+        let text = std::fs::read_to_string(&path).map_err(|e| PreprocessorError::IncludeRead {
+            file: current,
+            position,
+            name: name.to_string(),
+            reason: e.to_string(),
+            stack,
+        })?;
+
+        self.include_stack.push(frame);
+        let result = self.process_source(&text, Some(&path));
+        let stack = IncludeStack(self.include_stack.clone());
+        self.include_stack.pop();
+        result.map_err(|e| e.in_included_file(&path.display().to_string(), &stack))
+    }
+
+    /// Ищет включаемый файл: `"file"` сначала в каталоге текущего файла,
+    /// затем в каталогах поиска; `<file>` только в каталогах поиска.
+    fn resolve_include(&self, name: &str, angled: bool, file: Option<&Path>) -> Option<PathBuf> {
+        let local_dir = file.and_then(Path::parent).unwrap_or(Path::new(""));
+        let local = (!angled).then(|| local_dir.join(name));
+        local
+            .into_iter()
+            .chain(self.include_paths.iter().map(|dir| dir.join(name)))
+            .find(|candidate| candidate.is_file())
+            .map(|candidate| Self::canonical(&candidate))
+    }
+
+    fn canonical(path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    fn display_name(file: Option<&Path>) -> String {
+        file.map_or_else(|| "<исходный код>".to_string(), |f| f.display().to_string())
+    }
+
     /// Проверяет, активна ли текущая секция кода.
     fn is_section_active(&self, condition_stack: &[ConditionState]) -> bool {
         let mut active = true;
 
         for state in condition_stack {
-            match state {
-                ConditionState::IfDef { active: a, .. } => active = active && *a,
-                ConditionState::IfNDef { active: a, .. } => active = active && *a,
-            }
+            active = active && state.is_active();
         }
 
         active
     }
 
+    /// Удаляет комментарии. Вместе с текстом возвращает позицию в исходном
+    /// тексте для каждого символа результата.
     fn remove_comments_from_whole_source(
         &self,
         source: &str,
         start_position: Position,
-    ) -> Result<String, PreprocessorError> {
-        let mut result = String::with_capacity(source.len());
-        let mut chars = source.chars().peekable();
+    ) -> Result<(String, Vec<Position>), PreprocessorError> {
+        let mut chars = Vec::with_capacity(source.len());
         let mut position = start_position;
+        for c in source.chars() {
+            chars.push((c, position));
+            if c == '\n' {
+                position.new_line();
+            } else {
+                position.advance_column(1);
+            }
+        }
+
+        let preserve = self.preserve_line_numbers;
+        let mut result = String::with_capacity(source.len());
+        let mut origins = Vec::with_capacity(source.len());
+        // Символ результата вместе с позицией, из которой он получен
+        let mut emit = |c: char, at: Position| {
+            result.push(c);
+            origins.push(at);
+        };
         let mut in_string = false;
         let mut in_char = false;
         let mut escape_next = false;
         let mut comment_depth = 0;
         let mut comment_start: Option<Position> = None;
+        let mut i = 0;
 
-        while let Some(c) = chars.next() {
-            let old_line = position.line;
-            let old_column = position.column;
-
-            if c == '\n' {
-                position.line += 1;
-                position.column = 1;
-            } else {
-                position.column += 1;
-            }
+        while let Some(&(c, at)) = chars.get(i) {
+            let next = chars.get(i + 1).map(|&(next, _)| next);
+            i += 1;
 
             if escape_next {
                 escape_next = false;
-                result.push(c);
+                emit(c, at);
                 continue;
             }
 
-            if !in_string && !in_char && comment_depth == 0 {
-                if c == '/' {
-                    match chars.peek() {
-                        Some('/') => {
-                            chars.next();
-
-                            if self.preserve_line_numbers {
-                                result.push(' ');
-                                result.push(' ');
-                            }
-
-                            for ch in chars.by_ref() {
-                                if ch == '\n' {
-                                    if self.preserve_line_numbers {
-                                        result.push('\n');
-                                    }
-                                    break;
-                                } else if self.preserve_line_numbers {
-                                    result.push(' ');
-                                }
-                            }
-                            continue;
+            if !in_string && !in_char && comment_depth == 0 && c == '/' && next == Some('/') {
+                if preserve {
+                    emit(' ', at);
+                    emit(' ', chars[i].1);
+                }
+                i += 1;
+
+                while let Some(&(ch, ch_at)) = chars.get(i) {
+                    i += 1;
+                    if ch == '\n' {
+                        if preserve {
+                            emit('\n', ch_at);
                         }
-                        _ => {}
+                        break;
+                    } else if preserve {
+                        emit(' ', ch_at);
                     }
                 }
+                continue;
             }
 
             if !in_string && !in_char {
-                if c == '/' && chars.peek() == Some(&'*') && comment_depth == 0 {
-                    chars.next();
-                    comment_start = Some(Position::new(old_line, old_column));
-                    comment_depth = 1;
-
-                    if self.preserve_line_numbers {
-                        result.push(' ');
-                        result.push(' ');
+                let opens = c == '/' && next == Some('*');
+                let closes = c == '*' && next == Some('/') && comment_depth > 0;
+                if opens || closes {
+                    if preserve {
+                        emit(' ', at);
+                        emit(' ', chars[i].1);
                     }
-                    continue;
-                } else if c == '/' && chars.peek() == Some(&'*') && comment_depth > 0 {
-                    chars.next();
-                    comment_depth += 1;
-
-                    if self.preserve_line_numbers {
-                        result.push(' ');
-                        result.push(' ');
-                    }
-                    continue;
-                } else if c == '*' && chars.peek() == Some(&'/') && comment_depth > 0 {
-                    chars.next();
-                    comment_depth -= 1;
+                    i += 1;
 
-                    if self.preserve_line_numbers {
-                        result.push(' ');
-                        result.push(' ');
-                    }
-
-                    if comment_depth == 0 {
-                        comment_start = None;
+                    if opens {
+                        if comment_depth == 0 {
+                            comment_start = Some(at);
+                        }
+                        comment_depth += 1;
+                    } else {
+                        comment_depth -= 1;
+                        if comment_depth == 0 {
+                            comment_start = None;
+                        }
                     }
                     continue;
                 }
             }
 
             if comment_depth > 0 {
-                if self.preserve_line_numbers {
-                    if c == '\n' {
-                        result.push('\n');
-                    } else {
-                        result.push(' ');
-                    }
+                if preserve {
+                    emit(if c == '\n' { '\n' } else { ' ' }, at);
                 }
                 continue;
             }
 
             match c {
-                '"' if !in_char => {
-                    in_string = !in_string;
-                    result.push(c);
-                }
-                '\'' if !in_string => {
-                    in_char = !in_char;
-                    result.push(c);
-                }
-                '\\' if in_string || in_char => {
-                    escape_next = true;
-                    result.push(c);
-                }
-                _ => {
-                    result.push(c);
-                }
+                '"' if !in_char => in_string = !in_string,
+                '\'' if !in_string => in_char = !in_char,
+                '\\' if in_string || in_char => escape_next = true,
+                _ => {}
             }
+            emit(c, at);
         }
 
         if comment_depth > 0 {
@@ -402,15 +692,39 @@ impl<'a> Preprocessor<'a> {
             });
         }
 
-        Ok(result)
+        Ok((result, origins))
     }
 }
 
-/// Состояние условной директивы.
+/// Состояние условной директивы. `taken` отмечает, что одна из ветвей
+/// цепочки уже выбрана и последующие `#elif`/`#else` неактивны.
 #[derive(Debug, Clone)]
 enum ConditionState {
-    IfDef { active: bool },
-    IfNDef { active: bool },
+    IfDef { active: bool, taken: bool },
+    IfNDef { active: bool, taken: bool },
+    If { active: bool, taken: bool },
+    Else { active: bool },
+}
+
+impl ConditionState {
+    fn is_active(&self) -> bool {
+        match self {
+            ConditionState::IfDef { active, .. }
+            | ConditionState::IfNDef { active, .. }
+            | ConditionState::If { active, .. }
+            | ConditionState::Else { active } => *active,
+        }
+    }
+
+    /// Выбрана ли уже одна из ветвей; `None` после `#else`.
+    fn taken(&self) -> Option<bool> {
+        match self {
+            ConditionState::IfDef { taken, .. }
+            | ConditionState::IfNDef { taken, .. }
+            | ConditionState::If { taken, .. } => Some(*taken),
+            ConditionState::Else { .. } => None,
+        }
+    }
 }
 
 /// Результат обработки директивы.
@@ -418,6 +732,8 @@ enum ConditionState {
 enum DirectiveResult {
     SkipLine,
     ProcessLine(String),
+    /// Обработанный текст включенного файла и его карта
+    Include(String, SourceMap),
     #[allow(dead_code)]
     Continue,
 }
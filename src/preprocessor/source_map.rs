@@ -0,0 +1,178 @@
+//! Карта соответствия результата препроцессора исходным файлам.
+//!
+//! Для каждой строки результата хранится список сегментов. Начиная с
+//! колонки сегмента текст либо скопирован из исходного файла символ в
+//! символ, либо получен раскрытием макросов и указывает на место вызова
+//! внешнего макроса.
+
+use crate::common::position::Position;
+use std::rc::Rc;
+
+/// Место в исходном файле, из которого получен фрагмент результата.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    /// Файл; `None` для исходного кода без пути
+    pub file: Option<Rc<str>>,
+    /// Строка и колонка в файле
+    pub position: Position,
+    /// Макросы, при раскрытии которых получен фрагмент, от внешнего к внутреннему
+    pub macros: Vec<String>,
+}
+
+/// Сегмент строки результата.
+#[derive(Debug, Clone)]
+struct Segment {
+    /// Колонка начала сегмента в строке результата
+    column: usize,
+    file: Option<Rc<str>>,
+    position: Position,
+    macros: Rc<[String]>,
+}
+
+impl Segment {
+    fn is_copied(&self) -> bool {
+        self.macros.is_empty()
+    }
+}
+
+/// Соответствие строк и колонок результата препроцессора исходным местам.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Основной файл; места в нем показываются без имени файла
+    root: Option<Rc<str>>,
+    lines: Vec<Vec<Segment>>,
+}
+
+impl SourceMap {
+    pub(super) fn new(root: Option<Rc<str>>) -> Self {
+        Self {
+            root,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Количество строк результата.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub(super) fn push_line(&mut self, line: LineMapper) {
+        self.lines.push(line.segments);
+    }
+
+    /// Добавляет строки включенного файла.
+    pub(super) fn append(&mut self, other: SourceMap) {
+        self.lines.extend(other.lines);
+    }
+
+    /// Находит исходное место для позиции в результате препроцессора.
+    pub fn locate(&self, position: Position) -> Option<SourceLocation> {
+        let segments = self.lines.get(position.line.checked_sub(1)?)?;
+        let segment = segments
+            .iter()
+            .rev()
+            .find(|segment| segment.column <= position.column)
+            .or(segments.first())?;
+
+        let mut original = segment.position;
+        if segment.is_copied() {
+            original.column += position.column.saturating_sub(segment.column);
+        }
+        Some(SourceLocation {
+            file: segment.file.clone(),
+            position: original,
+            macros: segment.macros.to_vec(),
+        })
+    }
+
+    /// Находит исходный файл и строку для строки результата. Файл `None`
+    /// означает основной файл.
+    pub fn locate_line(&self, line: usize) -> Option<(Option<Rc<str>>, usize)> {
+        let location = self.locate(Position::new(line.max(1), 1))?;
+        let file = location.file.filter(|file| Some(file) != self.root.as_ref());
+        Some((file, location.position.line))
+    }
+
+    /// Переводит позицию диагностики в исходное место и добавляет
+    /// примечания о включенном файле и раскрытых макросах.
+    pub fn relocate(&self, position: &mut Position, notes: &mut Vec<String>) {
+        let Some(location) = self.locate(*position) else {
+            return;
+        };
+        *position = location.position;
+        match &location.file {
+            Some(file) if location.file != self.root => notes.push(format!("в файле {}", file)),
+            _ => {}
+        }
+        for name in location.macros.iter().rev() {
+            notes.push(format!("в раскрытии макроса '{}'", name));
+        }
+    }
+}
+
+/// Собирает сегменты одной строки результата.
+#[derive(Debug)]
+pub(super) struct LineMapper {
+    file: Option<Rc<str>>,
+    segments: Vec<Segment>,
+    /// Колонка следующего символа результата
+    column: usize,
+}
+
+impl LineMapper {
+    pub(super) fn new(file: Option<Rc<str>>) -> Self {
+        Self {
+            file,
+            segments: Vec::new(),
+            column: 1,
+        }
+    }
+
+    /// Отмечает символы, скопированные из исходных позиций `origins`.
+    pub(super) fn copied(&mut self, origins: &[Position]) {
+        for &origin in origins {
+            let continues = self.segments.last().is_some_and(|segment| {
+                segment.is_copied()
+                    && segment.position.line == origin.line
+                    && segment.position.column + (self.column - segment.column) == origin.column
+            });
+            if !continues {
+                self.push_segment(origin, Rc::from([]));
+            }
+            self.column += 1;
+        }
+    }
+
+    /// Отмечает `len` символов, полученных раскрытием макросов, вызванных
+    /// в позиции `origin`.
+    pub(super) fn expanded(&mut self, len: usize, origin: Position, macros: &[String]) {
+        let continues = self.segments.last().is_some_and(|segment| {
+            !segment.is_copied() && segment.position == origin && *segment.macros == *macros
+        });
+        if !continues && len > 0 {
+            self.push_segment(origin, Rc::from(macros));
+        }
+        self.column += len;
+    }
+
+    /// Привязывает строку без сегментов (например, пустую) к `origin`.
+    pub(super) fn finish(mut self, origin: Position) -> Self {
+        if self.segments.is_empty() {
+            self.push_segment(origin, Rc::from([]));
+        }
+        self
+    }
+
+    fn push_segment(&mut self, position: Position, macros: Rc<[String]>) {
+        self.segments.push(Segment {
+            column: self.column,
+            file: self.file.clone(),
+            position,
+            macros,
+        });
+    }
+}
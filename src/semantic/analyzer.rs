@@ -1,8 +1,9 @@
 //! Основной семантический анализатор
 
+use crate::lexer::Scanner;
 use crate::parser::ast::*;
 use crate::semantic::errors::{SemanticError, SemanticErrorKind, SemanticErrors};
-use crate::semantic::symbol_table::{Symbol, SymbolTable};
+use crate::semantic::symbol_table::{Symbol, SymbolKind, SymbolTable};
 use crate::semantic::type_system::{BinaryOpType, Type, TypeChecker, UnaryOpType};
 
 /// Декорированный AST с аннотациями типов
@@ -101,6 +102,7 @@ impl SemanticAnalyzer {
             match decl {
                 Declaration::Function(func) => self.collect_function(func),
                 Declaration::Struct(struct_decl) => self.collect_struct(struct_decl),
+                Declaration::Enum(enum_decl) => self.collect_enum(enum_decl),
                 Declaration::Variable(var) => self.collect_global_variable(var),
                 Declaration::ExternFunction(ext) => {
                     let param_types: Vec<Type> = ext
@@ -158,6 +160,7 @@ impl SemanticAnalyzer {
 
         for field in &struct_decl.fields {
             let field_type = Type::from_ast(&field.var_type);
+            self.check_field_type(&struct_decl.name, field, &field_type);
             if fields.contains_key(&field.name) {
                 self.errors.add(
                     SemanticError::new(
@@ -183,7 +186,8 @@ impl SemanticAnalyzer {
             }
         }
 
-        let struct_offsets = Type::struct_offsets(&field_types, &field_order);
+        let (struct_offsets, size, alignment) =
+            self.symbol_table.struct_layout(&field_types, &field_order);
         for (field_name, offset) in struct_offsets {
             if let Some(field_symbol) = field_symbols.get_mut(&field_name) {
                 field_symbol.stack_offset = Some(offset as i32);
@@ -194,7 +198,8 @@ impl SemanticAnalyzer {
             struct_decl.name.clone(),
             fields,
             struct_decl.node.position(),
-        );
+        )
+        .with_layout(size, alignment);
         if !self.symbol_table.insert(&struct_decl.name, symbol) {
             self.errors.add(
                 SemanticError::new(
@@ -207,6 +212,72 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Регистрирует перечисление и его константы в глобальной области.
+    /// Константа без явного значения на единицу больше предыдущей
+    fn collect_enum(&mut self, enum_decl: &EnumDecl) {
+        let symbol = Symbol::enum_type(enum_decl.name.clone(), enum_decl.node.position());
+        if !self.symbol_table.insert(&enum_decl.name, symbol) {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::DuplicateDeclaration,
+                    enum_decl.node.position(),
+                    format!("Перечисление '{}' уже объявлено", enum_decl.name),
+                )
+                .with_suggestion("Используйте другое имя перечисления".to_string()),
+            );
+        }
+
+        let mut next_value = 0;
+        for variant in &enum_decl.variants {
+            let value = variant.value.unwrap_or(next_value);
+            next_value = value + 1;
+            let constant = Symbol::enum_constant(
+                variant.name.clone(),
+                &enum_decl.name,
+                value,
+                variant.node.position(),
+            );
+            if !self.symbol_table.insert(&variant.name, constant) {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::DuplicateDeclaration,
+                        variant.node.position(),
+                        format!("Имя '{}' уже объявлено", variant.name),
+                    )
+                    .with_suggestion("Используйте другое имя константы".to_string()),
+                );
+            }
+        }
+    }
+
+    /// Поле-структура должна быть объявлена раньше; на себя структура
+    /// может ссылаться только через указатель
+    fn check_field_type(&mut self, owner: &str, field: &VarDecl, field_type: &Type) {
+        let mut inner = field_type;
+        while let Type::Array(elem, _) = inner {
+            inner = elem;
+        }
+        match inner {
+            Type::Struct(name) if name == owner => {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        field.node.position(),
+                        format!("Структура '{}' не может содержать саму себя", owner),
+                    )
+                    .with_suggestion(format!(
+                        "Объявите поле '{}' указателем: struct {}*",
+                        field.name, owner
+                    )),
+                );
+            }
+            Type::Struct(_) => {
+                self.check_complete_type(inner, field.node.position());
+            }
+            _ => {}
+        }
+    }
+
     fn collect_global_variable(&mut self, var: &VarDecl) {
         let var_type = match &var.var_type {
             crate::parser::ast::Type::Inferred => {
@@ -228,6 +299,8 @@ impl SemanticAnalyzer {
             }
             _ => Type::from_ast(&var.var_type),
         };
+        self.check_array_dims(&var_type, var.node.position());
+        let var_type = self.complete_array_type(var_type, var);
         let symbol = Symbol::variable(var.name.clone(), var_type, var.node.position());
         if !self.symbol_table.insert(&var.name, symbol) {
             self.errors.add(
@@ -245,7 +318,7 @@ impl SemanticAnalyzer {
         for decl in &program.declarations {
             match decl {
                 Declaration::Function(func) => self.analyze_function(func),
-                Declaration::Struct(_) => {}
+                Declaration::Struct(_) | Declaration::Enum(_) => {}
                 Declaration::ExternFunction(ext) => {
                     let param_types: Vec<Type> = ext
                         .parameters
@@ -263,6 +336,9 @@ impl SemanticAnalyzer {
                     self.symbol_table.insert(&ext.name, symbol);
                 }
                 Declaration::Variable(var) => {
+                    if let Some(initializer) = &var.initializer {
+                        self.check_constant_initializer(var, initializer);
+                    }
                     if let crate::parser::ast::Type::Inferred = &var.var_type {
                         if let Some(initializer) = &var.initializer {
                             let init_type = self.analyze_expression(initializer);
@@ -276,6 +352,14 @@ impl SemanticAnalyzer {
                             }
                         }
                     } else if let Some(initializer) = &var.initializer {
+                        if let Expression::ArrayInitializer(arr_init) = initializer.as_ref() {
+                            let var_type = match self.symbol_table.lookup(&var.name) {
+                                Some(symbol) => symbol.typ.clone(),
+                                None => Type::from_ast(&var.var_type),
+                            };
+                            self.check_array_initializer(&var_type, var, arr_init);
+                            continue;
+                        }
                         let init_type = self.analyze_expression(initializer);
                         let var_type = Type::from_ast(&var.var_type);
                         if let Some(init_type) = init_type {
@@ -286,16 +370,70 @@ impl SemanticAnalyzer {
                                         var.node.position(),
                                         format!("Несоответствие типов при инициализации глобальной переменной '{}'", var.name),
                                     )
-                                    .with_types(var_type, init_type),
+                                    .with_types(var_type.clone(), init_type),
                                 );
                             }
                         }
+                        self.check_string_initializer(&var_type, var, initializer);
                     }
                 }
             }
         }
     }
 
+    /// Инициализатор глобальной переменной вычисляется при компиляции,
+    /// иначе переменная осталась бы нулевой
+    fn check_constant_initializer(&mut self, var: &VarDecl, initializer: &Expression) {
+        if self.is_constant_expression(initializer) {
+            return;
+        }
+        self.errors.add(
+            SemanticError::new(
+                SemanticErrorKind::InvalidExpression,
+                initializer.node_position(),
+                format!(
+                    "Инициализатор глобальной переменной '{}' не является константой",
+                    var.name
+                ),
+            )
+            .with_suggestion(
+                "Используйте литералы, sizeof, константы перечислений и операции над ними \
+                 или присвойте значение внутри функции"
+                    .to_string(),
+            ),
+        );
+    }
+
+    /// Выражение, которое IR-генератор сворачивает в константу: литералы,
+    /// `sizeof`, константы перечислений, приведения и операции над ними
+    fn is_constant_expression(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(_) | Expression::Sizeof(_) => true,
+            Expression::Grouped(g) => self.is_constant_expression(&g.expr),
+            Expression::Cast(c) => self.is_constant_expression(&c.expr),
+            Expression::Identifier(ident) => self
+                .symbol_table
+                .lookup(&ident.name)
+                .is_some_and(|s| s.kind == SymbolKind::EnumConstant),
+            Expression::Unary(u) => {
+                matches!(u.operator, UnaryOp::Plus | UnaryOp::Neg | UnaryOp::Not | UnaryOp::BitNot)
+                    && self.is_constant_expression(&u.operand)
+            }
+            Expression::Binary(b) => {
+                self.is_constant_expression(&b.left) && self.is_constant_expression(&b.right)
+            }
+            Expression::Conditional(c) => {
+                self.is_constant_expression(&c.condition)
+                    && self.is_constant_expression(&c.then_expr)
+                    && self.is_constant_expression(&c.else_expr)
+            }
+            Expression::ArrayInitializer(arr) => {
+                arr.elements.iter().all(|e| self.is_constant_expression(e))
+            }
+            _ => false,
+        }
+    }
+
     fn analyze_function(&mut self, func: &FunctionDecl) {
         self.symbol_table.enter_scope();
         let previous_function = self.current_function.take();
@@ -311,6 +449,7 @@ impl SemanticAnalyzer {
 
         for param in &func.parameters {
             let param_type = Type::from_ast(&param.param_type);
+            self.check_array_dims(&param_type, param.node.position());
             let symbol = Symbol::parameter(param.name.clone(), param_type, param.node.position());
             if !self.symbol_table.insert(&param.name, symbol) {
                 self.errors.add(
@@ -409,24 +548,12 @@ impl SemanticAnalyzer {
             }
             _ => Type::from_ast(&var.var_type),
         };
+        self.check_array_dims(&var_type, var.node.position());
+        let var_type = self.complete_array_type(var_type, var);
 
         if let Some(init) = &var.initializer {
             if let Expression::ArrayInitializer(arr_init) = init.as_ref() {
-                if let crate::parser::ast::Type::Array(_, Some(max_size)) = &var.var_type {
-                    if arr_init.elements.len() as i32 > *max_size {
-                        self.errors.add(
-                            SemanticError::new(
-                                SemanticErrorKind::TypeMismatch,
-                                var.node.position(),
-                                format!(
-                                    "Слишком много элементов в инициализаторе массива: объявлено [{}], получено {}",
-                                    max_size,
-                                    arr_init.elements.len()
-                                ),
-                            ),
-                        );
-                    }
-                }
+                self.check_array_initializer(&var_type, var, arr_init);
             } else {
                 let init_type = self.analyze_expression(init);
                 if let Some(init_type) = init_type {
@@ -448,6 +575,7 @@ impl SemanticAnalyzer {
                         );
                     }
                 }
+                self.check_string_initializer(&var_type, var, init);
             }
         }
 
@@ -455,6 +583,179 @@ impl SemanticAnalyzer {
         self.symbol_table.insert_with_offset(&var.name, symbol);
     }
 
+    fn check_array_initializer(
+        &mut self,
+        var_type: &Type,
+        var: &VarDecl,
+        arr_init: &ArrayInitializerExpr,
+    ) {
+        if !var_type.is_array() {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::AssignmentTypeMismatch,
+                    var.node.position(),
+                    format!(
+                        "Список инициализации для переменной '{}', не являющейся массивом",
+                        var.name
+                    ),
+                )
+                .with_suggestion("Списком {...} инициализируются только массивы".to_string()),
+            );
+            return;
+        }
+        self.check_initializer_shape(var_type, arr_init, var.node.position());
+    }
+
+    /// Строка копируется в массив `char` побайтно; завершающий ноль
+    /// можно опустить, но сами символы должны поместиться
+    fn check_string_initializer(&mut self, var_type: &Type, var: &VarDecl, init: &Expression) {
+        let (size, text) = match (var_type, init) {
+            (Type::Array(element, size), Expression::Literal(lit)) if **element == Type::Char => {
+                match &lit.value {
+                    LiteralValue::String(text) => (*size, Scanner::string_bytes(text)),
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+        if text.len() > size {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::TypeMismatch,
+                    init.node_position(),
+                    format!(
+                        "Строка из {} байт не помещается в массив '{}' из {} элементов",
+                        text.len(),
+                        var.name,
+                        size
+                    ),
+                )
+                .with_suggestion(format!("Увеличьте размер массива до {}", text.len() + 1)),
+            );
+        }
+    }
+
+    /// Выводит опущенный размер массива `int a[] = {...}` из числа
+    /// элементов инициализатора
+    fn complete_array_type(&mut self, var_type: Type, var: &VarDecl) -> Type {
+        let element = match var_type {
+            Type::Array(element, 0) => element,
+            other => return other,
+        };
+        match var.initializer.as_deref() {
+            Some(Expression::ArrayInitializer(init)) => Type::Array(element, init.elements.len()),
+            _ => {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        var.node.position(),
+                        format!("Не указан размер массива '{}'", var.name),
+                    )
+                    .with_suggestion(
+                        "Укажите размер или инициализатор: int a[] = {1, 2, 3}".to_string(),
+                    ),
+                );
+                Type::Array(element, 0)
+            }
+        }
+    }
+
+    /// Сверяет вложенность инициализатора с размерностями массива:
+    /// строке многомерного массива соответствует вложенный список
+    fn check_initializer_shape(
+        &mut self,
+        typ: &Type,
+        arr_init: &ArrayInitializerExpr,
+        position: crate::common::position::Position,
+    ) {
+        let (element, max_size) = match typ {
+            Type::Array(element, size) => (element.as_ref(), *size),
+            _ => return,
+        };
+        if max_size > 0 && arr_init.elements.len() > max_size {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::TypeMismatch,
+                    position,
+                    format!(
+                        "Слишком много элементов в инициализаторе массива: объявлено [{}], получено {}",
+                        max_size,
+                        arr_init.elements.len()
+                    ),
+                ),
+            );
+        }
+        for elem in &arr_init.elements {
+            match (element, elem) {
+                (Type::Array(..), Expression::ArrayInitializer(row)) => {
+                    self.check_initializer_shape(element, row, row.node.position());
+                }
+                (Type::Array(..), _) | (_, Expression::ArrayInitializer(_)) => {
+                    let expected = if matches!(element, Type::Array(..)) {
+                        "ожидался вложенный список"
+                    } else {
+                        "ожидалось значение"
+                    };
+                    self.errors.add(
+                        SemanticError::new(
+                            SemanticErrorKind::TypeMismatch,
+                            elem.node_position(),
+                            format!("Неверная вложенность инициализатора: {}", expected),
+                        )
+                        .with_suggestion(format!(
+                            "Каждая строка массива {} задается отдельным списком {{...}}",
+                            typ
+                        )),
+                    );
+                }
+                _ => {
+                    let mismatch = self
+                        .analyze_expression(elem)
+                        .filter(|t| !self.type_checker.is_assignable(element, t));
+                    if let Some(elem_type) = mismatch {
+                        self.errors.add(
+                            SemanticError::new(
+                                SemanticErrorKind::AssignmentTypeMismatch,
+                                elem.node_position(),
+                                "Несоответствие типа элемента инициализатора".to_string(),
+                            )
+                            .with_types(element.clone(), elem_type.clone())
+                            .with_suggestion(format!(
+                                "Ожидался тип {}, получен {}",
+                                element, elem_type
+                            )),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Размер можно опустить только у первой размерности массива
+    fn check_array_dims(&mut self, typ: &Type, position: crate::common::position::Position) {
+        let mut element = match typ {
+            Type::Array(element, _) => element.as_ref(),
+            _ => return,
+        };
+        while let Type::Array(inner, size) = element {
+            if *size == 0 {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        position,
+                        format!("Не указан размер внутреннего измерения массива {}", typ),
+                    )
+                    .with_suggestion(
+                        "Размер можно опустить только у первой размерности: int m[][4]"
+                            .to_string(),
+                    ),
+                );
+                return;
+            }
+            element = inner;
+        }
+    }
+
     fn analyze_block(&mut self, block: &BlockStmt) {
         self.symbol_table.enter_scope();
         for stmt in &block.statements {
@@ -471,6 +772,7 @@ impl SemanticAnalyzer {
             }
             Statement::If(if_stmt) => self.analyze_if(if_stmt),
             Statement::While(while_stmt) => self.analyze_while(while_stmt),
+            Statement::DoWhile(do_while) => self.analyze_do_while(do_while),
             Statement::For(for_stmt) => self.analyze_for(for_stmt),
             Statement::Return(return_stmt) => self.analyze_return(return_stmt),
             Statement::Block(block) => self.analyze_block(block),
@@ -548,14 +850,158 @@ impl SemanticAnalyzer {
         self.loop_depth -= 1;
     }
 
+    fn analyze_do_while(&mut self, do_while: &DoWhileStmt) {
+        self.loop_depth += 1;
+        self.analyze_statement(&do_while.body);
+        self.loop_depth -= 1;
+        let cond_type = self
+            .analyze_expression(&do_while.condition)
+            .filter(|t| !self.type_checker.is_compatible(&Type::Bool, t));
+        if let Some(cond_type) = cond_type {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::InvalidConditionType,
+                    do_while.condition.node_position(),
+                    "Условие do-while должно иметь булевый тип".to_string(),
+                )
+                .with_types(Type::Bool, cond_type)
+                .with_suggestion("Используйте выражение, возвращающее true/false".to_string()),
+            );
+        }
+    }
+
+    /// Значения case должны быть константами и не повторяться; switch по
+    /// перечислению без default должен перечислить все его константы
     fn analyze_switch(&mut self, switch_stmt: &SwitchStmt) {
-        let _expr_type = self.analyze_expression(&switch_stmt.expression);
+        let expr_type = self.analyze_expression(&switch_stmt.expression);
+        let mut seen = std::collections::HashSet::new();
         for case in &switch_stmt.cases {
+            let value = self.case_value(case, expr_type.as_ref());
+            if let Some(value) = value.filter(|v| !seen.insert(*v)) {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::DuplicateCase,
+                        case.value.node_position(),
+                        format!(
+                            "Значение case {} ({}) уже встречалось в этом switch",
+                            case.value_label(),
+                            value
+                        ),
+                    )
+                    .with_suggestion("Удалите или объедините повторяющиеся ветви".to_string()),
+                );
+            }
             self.analyze_statement(&case.body);
         }
         if let Some(default) = &switch_stmt.default {
             self.analyze_statement(default);
+        } else if let Some(Type::Enum(name)) = &expr_type {
+            self.check_switch_coverage(name, &seen, switch_stmt);
+        }
+    }
+
+    /// Вычисляет значение метки case: целый, символьный или булев литерал
+    /// либо константа перечисления того же типа, что и выражение switch
+    fn case_value(&mut self, case: &CaseStmt, switch_type: Option<&Type>) -> Option<i64> {
+        let (value, case_type) = match &case.value {
+            Expression::Literal(lit) => match lit.value {
+                LiteralValue::Int(v) => (v as i64, Type::Int),
+                LiteralValue::Long(v) => (v, Type::Long),
+                LiteralValue::UInt(v) => (v as i64, Type::UInt),
+                LiteralValue::ULong(v) => (v as i64, Type::ULong),
+                LiteralValue::Char(c) => (c as i64, Type::Char),
+                LiteralValue::Bool(b) => (b as i64, Type::Bool),
+                _ => {
+                    self.report_non_constant_case(case);
+                    return None;
+                }
+            },
+            Expression::Identifier(ident) => match self.symbol_table.lookup(&ident.name) {
+                Some(symbol) if symbol.kind == SymbolKind::EnumConstant => {
+                    (symbol.const_value?, symbol.typ.clone())
+                }
+                Some(_) => {
+                    self.report_non_constant_case(case);
+                    return None;
+                }
+                None => {
+                    self.analyze_identifier(ident);
+                    return None;
+                }
+            },
+            _ => {
+                self.report_non_constant_case(case);
+                return None;
+            }
+        };
+
+        let foreign = match (switch_type, &case_type) {
+            (Some(Type::Enum(expected)), Type::Enum(found)) if expected != found => Some(expected),
+            _ => None,
+        };
+        if let Some(expected) = foreign {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::TypeMismatch,
+                    case.value.node_position(),
+                    format!(
+                        "Константа '{}' не принадлежит перечислению '{}'",
+                        case.value_label(),
+                        expected
+                    ),
+                )
+                .with_types(Type::Enum(expected.clone()), case_type.clone()),
+            );
         }
+        Some(value)
+    }
+
+    fn report_non_constant_case(&mut self, case: &CaseStmt) {
+        self.errors.add(
+            SemanticError::new(
+                SemanticErrorKind::InvalidExpression,
+                case.value.node_position(),
+                format!("Значение case {} не является константой", case.value_label()),
+            )
+            .with_suggestion(
+                "Используйте целый или символьный литерал либо константу перечисления"
+                    .to_string(),
+            ),
+        );
+    }
+
+    /// Предупреждает о константах перечисления, не покрытых ни одним case
+    fn check_switch_coverage(
+        &mut self,
+        enum_name: &str,
+        seen: &std::collections::HashSet<i64>,
+        switch_stmt: &SwitchStmt,
+    ) {
+        let mut missing: Vec<(i64, String)> = self
+            .symbol_table
+            .global_symbols()
+            .filter(|s| s.kind == SymbolKind::EnumConstant)
+            .filter(|s| matches!(&s.typ, Type::Enum(name) if name == enum_name))
+            .filter_map(|s| s.const_value.map(|v| (v, s.name.clone())))
+            .filter(|(v, _)| !seen.contains(v))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        missing.sort();
+        let names: Vec<String> = missing.into_iter().map(|(_, name)| name).collect();
+        self.errors.add_warning(
+            SemanticError::new(
+                SemanticErrorKind::NonExhaustiveSwitch,
+                switch_stmt.node.position(),
+                format!(
+                    "switch по enum {} не обрабатывает: {}",
+                    enum_name,
+                    names.join(", ")
+                ),
+            )
+            .with_suggestion("Добавьте недостающие case или ветвь default".to_string()),
+        );
     }
 
     fn analyze_for(&mut self, for_stmt: &ForStmt) {
@@ -664,12 +1110,18 @@ impl SemanticAnalyzer {
             Expression::Grouped(grouped) => self.analyze_expression(&grouped.expr),
             Expression::ArrayAccess(access) => self.analyze_array_access(access),
             Expression::ArrayInitializer(arr) => {
-                for elem in &arr.elements {
-                    self.analyze_expression(elem);
-                }
-                arr.elements
-                    .first()
-                    .and_then(|e| self.analyze_expression(e))
+                // Тип списка - массив из типа первого элемента
+                let types: Vec<Option<Type>> =
+                    arr.elements.iter().map(|e| self.analyze_expression(e)).collect();
+                let element = types.into_iter().next()??;
+                Some(Type::Array(Box::new(element), arr.elements.len()))
+            }
+            Expression::Cast(cast) => self.analyze_cast(cast),
+            Expression::Sizeof(sizeof) => self.analyze_sizeof(sizeof),
+            Expression::Conditional(cond) => self.analyze_conditional(cond),
+            Expression::Comma(comma) => {
+                self.analyze_expression(&comma.left);
+                self.analyze_expression(&comma.right)
             }
         }
     }
@@ -677,9 +1129,14 @@ impl SemanticAnalyzer {
     fn analyze_literal(&self, lit: &Literal) -> Option<Type> {
         match lit.value {
             LiteralValue::Int(_) => Some(Type::Int),
+            LiteralValue::Long(_) => Some(Type::Long),
+            LiteralValue::UInt(_) => Some(Type::UInt),
+            LiteralValue::ULong(_) => Some(Type::ULong),
             LiteralValue::Float(_) => Some(Type::Float),
             LiteralValue::Bool(_) => Some(Type::Bool),
             LiteralValue::String(_) => Some(Type::Pointer(Box::new(Type::Char))),
+            LiteralValue::Char(_) => Some(Type::Char),
+            LiteralValue::Null => Some(Type::null_pointer()),
         }
     }
 
@@ -717,27 +1174,127 @@ impl SemanticAnalyzer {
                 );
                 return None;
             }
-            self.type_checker.binary_result_type(&left, &right, op_type)
+            let result = self.type_checker.binary_result_type(&left, &right, op_type);
+            let integer_only = matches!(op_type, BinaryOpType::Bitwise | BinaryOpType::Shift);
+            let operator = binary.operator.to_string();
+            let pointers = left.element_type().is_some() || right.element_type().is_some();
+            if result.is_none() && integer_only {
+                self.report_non_integer_operands(&operator, &left, &right, binary.node.position());
+            } else if result.is_none() && pointers {
+                let position = binary.node.position();
+                self.report_invalid_pointer_operation(&operator, &left, &right, position);
+            }
+            result
         } else {
             None
         }
     }
 
+    fn report_non_integer_operands(
+        &mut self,
+        operator: &str,
+        left: &Type,
+        right: &Type,
+        position: crate::common::position::Position,
+    ) {
+        self.errors.add(
+            SemanticError::new(
+                SemanticErrorKind::TypeMismatch,
+                position,
+                format!("Оператор '{}' применим только к целым числам", operator),
+            )
+            .with_types(left.clone(), right.clone())
+            .with_suggestion("Используйте операнды типа int или char".to_string()),
+        );
+    }
+
+    fn report_invalid_pointer_operation(
+        &mut self,
+        operator: &str,
+        left: &Type,
+        right: &Type,
+        position: crate::common::position::Position,
+    ) {
+        self.errors.add(
+            SemanticError::new(
+                SemanticErrorKind::TypeMismatch,
+                position,
+                format!("Недопустимая операция '{}' над указателями", operator),
+            )
+            .with_types(left.clone(), right.clone())
+            .with_suggestion(
+                "К указателю можно прибавить целое, вычесть целое или указатель того же типа \
+                 и сравнить его с указателем того же типа или null"
+                    .to_string(),
+            ),
+        );
+    }
+
     fn analyze_unary(&mut self, unary: &UnaryExpr) -> Option<Type> {
         let operand_type = self.analyze_expression(&unary.operand);
         if let Some(operand) = operand_type {
             let op_type: UnaryOpType = (&unary.operator).into();
-            self.type_checker.unary_result_type(&operand, op_type)
+            let result = self.type_checker.unary_result_type(&operand, op_type);
+            if result.is_none() && op_type == UnaryOpType::BitNot {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        unary.node.position(),
+                        "Оператор '~' применим только к целым числам".to_string(),
+                    )
+                    .with_suggestion("Используйте операнд типа int или char".to_string()),
+                );
+            }
+            result
         } else {
             None
         }
     }
 
     fn analyze_assignment(&mut self, assign: &AssignmentExpr) -> Option<Type> {
+        if let Expression::Identifier(ident) = assign.target.as_ref() {
+            let constant = self
+                .symbol_table
+                .lookup(&ident.name)
+                .is_some_and(|s| s.kind == SymbolKind::EnumConstant);
+            if constant {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::InvalidAssignmentTarget,
+                        assign.node.position(),
+                        format!("Нельзя присвоить значение константе '{}'", ident.name),
+                    )
+                    .with_suggestion("Константы перечисления неизменяемы".to_string()),
+                );
+                return None;
+            }
+        }
         let target_type = self.analyze_expression(&assign.target);
         let value_type = self.analyze_expression(&assign.value);
         if let (Some(target), Some(value)) = (target_type, value_type) {
-            if !self.type_checker.is_assignable(&target, &value) {
+            let bitwise = assign
+                .operator
+                .binary_op()
+                .map(|op| BinaryOpType::from(&op))
+                .is_some_and(|op| matches!(op, BinaryOpType::Bitwise | BinaryOpType::Shift));
+            let pointer_offset = target.is_pointer()
+                && matches!(assign.operator, AssignmentOp::AddAssign | AssignmentOp::SubAssign);
+            if bitwise && !(target.is_integer() && value.is_integer()) {
+                let operator = assign.operator.to_string();
+                self.report_non_integer_operands(&operator, &target, &value, assign.node.position());
+            } else if pointer_offset {
+                let op_type = if assign.operator == AssignmentOp::AddAssign {
+                    BinaryOpType::Addition
+                } else {
+                    BinaryOpType::Subtraction
+                };
+                let result = self.type_checker.binary_result_type(&target, &value, op_type);
+                if !result.is_some_and(|r| r.is_pointer()) {
+                    let operator = assign.operator.to_string();
+                    let position = assign.node.position();
+                    self.report_invalid_pointer_operation(&operator, &target, &value, position);
+                }
+            } else if !self.type_checker.is_assignable(&target, &value) {
                 self.errors.add(
                     SemanticError::new(
                         SemanticErrorKind::AssignmentTypeMismatch,
@@ -888,8 +1445,67 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Структура, у которой есть адрес: переменная, поле, элемент массива
+    /// или `*p`. Поля остальных значений (результата вызова, `?:`)
+    /// не размещены в памяти
+    fn is_struct_object(expr: &Expression) -> bool {
+        match expr {
+            Expression::Identifier(_)
+            | Expression::StructAccess(_)
+            | Expression::ArrayAccess(_) => true,
+            Expression::Unary(u) => u.operator == UnaryOp::Deref,
+            Expression::Grouped(g) => Self::is_struct_object(&g.expr),
+            _ => false,
+        }
+    }
+
     fn analyze_struct_access(&mut self, access: &StructAccessExpr) -> Option<Type> {
         let object_type = self.analyze_expression(&access.object);
+        // Для '->' поле ищется в структуре, на которую указывает объект
+        let object_type = match object_type {
+            Some(Type::Pointer(inner)) if access.is_arrow => Some(*inner),
+            Some(typ) if access.is_arrow => {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        access.node.position(),
+                        "Оператор '->' применим только к указателю на структуру".to_string(),
+                    )
+                    .with_types(Type::Pointer(Box::new(Type::Struct("?".to_string()))), typ)
+                    .with_suggestion("Для структуры используйте '.'".to_string()),
+                );
+                return None;
+            }
+            Some(Type::Pointer(inner)) if matches!(*inner, Type::Struct(_)) => {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::TypeMismatch,
+                        access.node.position(),
+                        "Доступ к полю через '.' у указателя на структуру".to_string(),
+                    )
+                    .with_types(Type::Struct("?".to_string()), Type::Pointer(inner))
+                    .with_suggestion(format!("Используйте '->{}'", access.field)),
+                );
+                return None;
+            }
+            Some(Type::Struct(_))
+                if !access.is_arrow && !Self::is_struct_object(&access.object) =>
+            {
+                self.errors.add(
+                    SemanticError::new(
+                        SemanticErrorKind::InvalidExpression,
+                        access.node.position(),
+                        format!("Поле '{}' берется у временного значения структуры", access.field),
+                    )
+                    .with_suggestion(
+                        "Сохраните структуру в переменную или обращайтесь к полю через указатель"
+                            .to_string(),
+                    ),
+                );
+                return None;
+            }
+            other => other,
+        };
         if let Some(typ) = object_type {
             match typ {
                 Type::Struct(name) => {
@@ -955,6 +1571,119 @@ impl SemanticAnalyzer {
         }
     }
 
+    fn analyze_cast(&mut self, cast: &CastExpr) -> Option<Type> {
+        let target = Type::from_ast(&cast.target_type);
+        self.check_complete_type(&target, cast.node.position());
+        let source = self.analyze_expression(&cast.expr)?;
+        if !self.type_checker.is_valid_cast(&source, &target) {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::TypeMismatch,
+                    cast.node.position(),
+                    format!("Недопустимое приведение типа {} к {}", source, target),
+                )
+                .with_types(target.clone(), source)
+                .with_suggestion(
+                    "Приводить можно числа друг к другу, указатели друг к другу \
+                     и целые к указателям"
+                        .to_string(),
+                ),
+            );
+            return None;
+        }
+        Some(target)
+    }
+
+    /// Условие `c ? a : b` должно быть булевым, а ветви - приводиться
+    /// к общему типу, который и становится типом выражения
+    fn analyze_conditional(&mut self, cond: &ConditionalExpr) -> Option<Type> {
+        let cond_type = self
+            .analyze_expression(&cond.condition)
+            .filter(|t| !self.type_checker.is_compatible(&Type::Bool, t));
+        if let Some(cond_type) = cond_type {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::InvalidConditionType,
+                    cond.condition.node_position(),
+                    "Условие '?:' должно иметь булевый тип".to_string(),
+                )
+                .with_types(Type::Bool, cond_type)
+                .with_suggestion("Используйте выражение, возвращающее true/false".to_string()),
+            );
+        }
+        let then_type = self.analyze_expression(&cond.then_expr);
+        let else_type = self.analyze_expression(&cond.else_expr);
+        let (then_type, else_type) = (then_type?, else_type?);
+        let result = self.type_checker.conditional_result_type(&then_type, &else_type);
+        if result.is_none() {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::TypeMismatch,
+                    cond.node.position(),
+                    format!(
+                        "Ветви '?:' имеют несовместимые типы {} и {}",
+                        then_type, else_type
+                    ),
+                )
+                .with_types(then_type, else_type)
+                .with_suggestion("Приведите обе ветви к одному типу".to_string()),
+            );
+        }
+        result
+    }
+
+    /// `sizeof` вычисляется на этапе компиляции и имеет тип `unsigned long`;
+    /// выражение-операнд только проверяется и не вычисляется
+    fn analyze_sizeof(&mut self, sizeof: &SizeofExpr) -> Option<Type> {
+        let operand = match &sizeof.operand {
+            SizeofOperand::Type(t) => Type::from_ast(t),
+            SizeofOperand::Expr(e) => self.analyze_expression(e)?,
+        };
+        if operand.is_void() || operand.is_function() {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::InvalidExpression,
+                    sizeof.node.position(),
+                    format!("sizeof неприменим к типу {}", operand),
+                )
+                .with_suggestion("Операнд sizeof должен иметь размер".to_string()),
+            );
+            return None;
+        }
+        if !self.check_complete_type(&operand, sizeof.node.position()) {
+            return None;
+        }
+        Some(Type::ULong)
+    }
+
+    /// Проверяет, что структура, входящая в тип по значению, объявлена
+    fn check_complete_type(
+        &mut self,
+        typ: &Type,
+        position: crate::common::position::Position,
+    ) -> bool {
+        let name = match typ {
+            Type::Struct(name) => name,
+            Type::Array(inner, _) => return self.check_complete_type(inner, position),
+            _ => return true,
+        };
+        let declared = self
+            .symbol_table
+            .lookup(name)
+            .is_some_and(|symbol| symbol.fields.is_some());
+        if !declared {
+            self.errors.add(
+                SemanticError::new(
+                    SemanticErrorKind::UndeclaredIdentifier,
+                    position,
+                    format!("Структура '{}' не объявлена", name),
+                )
+                .with_suggestion(format!("Объявите структуру '{}'", name)),
+            );
+        }
+        declared
+    }
+
     fn analyze_array_access(&mut self, access: &ArrayAccessExpr) -> Option<Type> {
         let array_type = self.analyze_expression(&access.array);
         let _index_type = self.analyze_expression(&access.index);
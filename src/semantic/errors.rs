@@ -35,6 +35,17 @@ pub enum SemanticErrorKind {
     ScopeError,
     InvalidBreak,
     InvalidContinue,
+    /// Повторяющееся значение case в switch
+    DuplicateCase,
+    /// switch по перечислению покрывает не все константы (предупреждение)
+    NonExhaustiveSwitch,
+}
+
+impl SemanticErrorKind {
+    /// Диагностика этого вида не останавливает компиляцию
+    pub fn is_warning(&self) -> bool {
+        matches!(self, SemanticErrorKind::NonExhaustiveSwitch)
+    }
 }
 
 /// Семантическая ошибка
@@ -54,6 +65,8 @@ pub struct SemanticError {
     pub expected_type: Option<Type>,
     /// Фактический тип (для ошибок типов)
     pub found_type: Option<Type>,
+    /// Примечания о месте ошибки (включенный файл, раскрытые макросы)
+    pub notes: Vec<String>,
 }
 
 impl SemanticError {
@@ -67,6 +80,7 @@ impl SemanticError {
             context: None,
             expected_type: None,
             found_type: None,
+            notes: Vec::new(),
         }
     }
 
@@ -108,9 +122,15 @@ impl fmt::Display for SemanticError {
             SemanticErrorKind::ScopeError => "ошибка области видимости",
             SemanticErrorKind::InvalidBreak => "Некорректный break",
             SemanticErrorKind::InvalidContinue => "Некорректный continue",
+            SemanticErrorKind::DuplicateCase => "повторяющееся значение case",
+            SemanticErrorKind::NonExhaustiveSwitch => "неполный switch",
         };
 
-        writeln!(f, "семантическая ошибка: {}", error_type)?;
+        if self.kind.is_warning() {
+            writeln!(f, "предупреждение: {}", error_type)?;
+        } else {
+            writeln!(f, "семантическая ошибка: {}", error_type)?;
+        }
         writeln!(
             f,
             "  --> строка {}, столбец {}",
@@ -136,6 +156,10 @@ impl fmt::Display for SemanticError {
             writeln!(f, "  | совет: {}", suggestion)?;
         }
 
+        for note in &self.notes {
+            writeln!(f, "  | примечание: {}", note)?;
+        }
+
         Ok(())
     }
 }
@@ -144,6 +168,7 @@ impl fmt::Display for SemanticError {
 #[derive(Debug, Clone, Default)]
 pub struct SemanticErrors {
     pub errors: Vec<SemanticError>,
+    pub warnings: Vec<SemanticError>,
     max_errors: usize,
     pub total_errors_detected: usize,
     pub actual_errors: usize,
@@ -154,6 +179,7 @@ impl SemanticErrors {
     pub fn new() -> Self {
         Self {
             errors: Vec::new(),
+            warnings: Vec::new(),
             max_errors: 50,
             total_errors_detected: 0,
             actual_errors: 0,
@@ -173,6 +199,11 @@ impl SemanticErrors {
         self.errors.push(error);
     }
 
+    /// Добавляет предупреждение; на успешность анализа оно не влияет
+    pub fn add_warning(&mut self, warning: SemanticError) {
+        self.warnings.push(warning);
+    }
+
     /// Добавляет ошибку и обновляет счетчик каскадных ошибок
     pub fn add_with_cascading(&mut self, error: SemanticError) {
         self.total_errors_detected += 1;
@@ -189,6 +220,11 @@ impl SemanticErrors {
         !self.errors.is_empty()
     }
 
+    /// Проверяет, есть ли предупреждения
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
     /// Возвращает количество ошибок
     pub fn len(&self) -> usize {
         self.errors.len()
@@ -197,6 +233,7 @@ impl SemanticErrors {
     /// Очищает все ошибки
     pub fn clear(&mut self) {
         self.errors.clear();
+        self.warnings.clear();
         self.total_errors_detected = 0;
         self.actual_errors = 0;
     }
@@ -213,8 +250,14 @@ impl fmt::Display for SemanticErrors {
         for error in &self.errors {
             writeln!(f, "{}", error)?;
         }
+        for warning in &self.warnings {
+            writeln!(f, "{}", warning)?;
+        }
         writeln!(f, "---")?;
         writeln!(f, "Итог: найдено {} ошибок", self.errors.len())?;
+        if self.has_warnings() {
+            writeln!(f, "  Предупреждений: {}", self.warnings.len())?;
+        }
         writeln!(f, "  Всего обнаружено: {}", self.total_errors_detected)?;
         writeln!(f, "  Уникальных ошибок: {}", self.actual_errors)?;
         writeln!(
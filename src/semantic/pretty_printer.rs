@@ -76,6 +76,14 @@ impl DecoratedAstPrinter {
             crate::semantic::symbol_table::SymbolKind::Field => {
                 output.push_str(&format!("{} поле", symbol.typ))
             }
+            crate::semantic::symbol_table::SymbolKind::Enum => {
+                output.push_str(&format!("enum {}", symbol.name))
+            }
+            crate::semantic::symbol_table::SymbolKind::EnumConstant => output.push_str(&format!(
+                "{} = {} константа",
+                symbol.typ,
+                symbol.const_value.unwrap_or_default()
+            )),
         }
         output.push('\n');
         output
@@ -106,6 +114,7 @@ impl DecoratedAstPrinter {
                 output
             }
             Declaration::Struct(sd) => self.format_struct(sd),
+            Declaration::Enum(ed) => self.format_enum(ed, symbol_table),
             Declaration::Variable(var) => self.format_variable(var, true, symbol_table),
         }
     }
@@ -155,6 +164,28 @@ impl DecoratedAstPrinter {
         output
     }
 
+    fn format_enum(&mut self, enum_decl: &EnumDecl, symbol_table: &SymbolTable) -> String {
+        let mut output = String::new();
+        output.push_str(&self.format_indent());
+        output.push_str(&format!(
+            "EnumDecl: {} [line {}]:\n",
+            enum_decl.name, enum_decl.node.line
+        ));
+        self.indent_level += 1;
+        for variant in &enum_decl.variants {
+            output.push_str(&self.format_indent());
+            let value = symbol_table
+                .lookup(&variant.name)
+                .and_then(|s| s.const_value);
+            match value {
+                Some(value) => output.push_str(&format!("- {} = {}\n", variant.name, value)),
+                None => output.push_str(&format!("- {}\n", variant.name)),
+            }
+        }
+        self.indent_level -= 1;
+        output
+    }
+
     fn format_block(&mut self, block: &BlockStmt, symbol_table: &SymbolTable) -> String {
         let mut output = String::new();
         output.push_str(&self.format_indent());
@@ -173,6 +204,7 @@ impl DecoratedAstPrinter {
             Statement::Expression(es) => self.format_expression_stmt(es),
             Statement::If(is) => self.format_if(is, symbol_table),
             Statement::While(ws) => self.format_while(ws, symbol_table),
+            Statement::DoWhile(dw) => self.format_do_while(dw, symbol_table),
             Statement::For(fs) => self.format_for(fs, symbol_table),
             Statement::Return(rs) => self.format_return(rs),
             Statement::Block(bs) => self.format_block(bs, symbol_table),
@@ -191,7 +223,7 @@ impl DecoratedAstPrinter {
                 ));
                 for case in &ss.cases {
                     out.push_str(&self.format_indent());
-                    out.push_str(&format!("Case {}:\n", case.value.value));
+                    out.push_str(&format!("Case {}:\n", case.value_label()));
                     self.indent_level += 1;
                     out.push_str(&self.format_statement(&case.body, symbol_table));
                     self.indent_level -= 1;
@@ -306,6 +338,25 @@ impl DecoratedAstPrinter {
         output
     }
 
+    fn format_do_while(&mut self, do_while: &DoWhileStmt, symbol_table: &SymbolTable) -> String {
+        let mut output = String::new();
+        output.push_str(&self.format_indent());
+        output.push_str(&format!("DoWhileStmt [line {}]:\n", do_while.node.line));
+        self.indent_level += 1;
+        output.push_str(&self.format_indent());
+        output.push_str("Body:\n");
+        self.indent_level += 1;
+        output.push_str(&self.format_statement(&do_while.body, symbol_table));
+        self.indent_level -= 1;
+        output.push_str(&self.format_indent());
+        output.push_str(&format!(
+            "Condition: {}\n",
+            self.format_expression(&do_while.condition)
+        ));
+        self.indent_level -= 1;
+        output
+    }
+
     fn format_for(&mut self, for_stmt: &ForStmt, symbol_table: &SymbolTable) -> String {
         let mut output = String::new();
         output.push_str(&self.format_indent());
@@ -373,7 +424,7 @@ impl DecoratedAstPrinter {
                 format!("{}({})", self.format_expression(&c.callee), args.join(", "))
             }
             Expression::StructAccess(sa) => {
-                format!("{}.{}", self.format_expression(&sa.object), sa.field)
+                format!("{}{}{}", self.format_expression(&sa.object), sa.operator(), sa.field)
             }
             Expression::ArrayAccess(aa) => format!(
                 "{}[{}]",
@@ -389,6 +440,24 @@ impl DecoratedAstPrinter {
                     .collect();
                 format!("{{{}}}", elems.join(", "))
             }
+            Expression::Cast(c) => {
+                format!("(({}){})", c.target_type, self.format_expression(&c.expr))
+            }
+            Expression::Sizeof(s) => match &s.operand {
+                SizeofOperand::Type(t) => format!("sizeof({})", t),
+                SizeofOperand::Expr(e) => format!("sizeof({})", self.format_expression(e)),
+            },
+            Expression::Conditional(c) => format!(
+                "({} ? {} : {})",
+                self.format_expression(&c.condition),
+                self.format_expression(&c.then_expr),
+                self.format_expression(&c.else_expr)
+            ),
+            Expression::Comma(c) => format!(
+                "({}, {})",
+                self.format_expression(&c.left),
+                self.format_expression(&c.right)
+            ),
         }
     }
 
@@ -12,6 +12,8 @@ pub enum SymbolKind {
     Function,
     Struct,
     Field,
+    Enum,
+    EnumConstant,
 }
 
 /// Информация о символе
@@ -25,6 +27,10 @@ pub struct Symbol {
     pub fields: Option<HashMap<String, Type>>,
     pub stack_offset: Option<i32>,
     pub is_variadic: bool,
+    /// Значение константы перечисления
+    pub const_value: Option<i64>,
+    /// Размер и выравнивание структуры в байтах
+    pub layout: Option<(usize, usize)>,
 }
 
 impl Symbol {
@@ -38,6 +44,8 @@ impl Symbol {
             fields: None,
             stack_offset: None,
             is_variadic: false,
+            const_value: None,
+            layout: None,
         }
     }
 
@@ -51,6 +59,8 @@ impl Symbol {
             fields: None,
             stack_offset: None,
             is_variadic: false,
+            const_value: None,
+            layout: None,
         }
     }
 
@@ -73,6 +83,8 @@ impl Symbol {
             fields: None,
             stack_offset: None,
             is_variadic,
+            const_value: None,
+            layout: None,
         }
     }
 
@@ -86,9 +98,17 @@ impl Symbol {
             fields: Some(fields),
             stack_offset: None,
             is_variadic: false,
+            const_value: None,
+            layout: None,
         }
     }
 
+    /// Задает размер и выравнивание структуры
+    pub fn with_layout(mut self, size: usize, alignment: usize) -> Self {
+        self.layout = Some((size, alignment));
+        self
+    }
+
     pub fn field(name: String, typ: Type, position: Position) -> Self {
         Self {
             name,
@@ -99,6 +119,38 @@ impl Symbol {
             fields: None,
             stack_offset: None,
             is_variadic: false,
+            const_value: None,
+            layout: None,
+        }
+    }
+
+    pub fn enum_type(name: String, position: Position) -> Self {
+        Self {
+            name: name.clone(),
+            typ: Type::Enum(name),
+            kind: SymbolKind::Enum,
+            position,
+            param_types: None,
+            fields: None,
+            stack_offset: None,
+            is_variadic: false,
+            const_value: None,
+            layout: None,
+        }
+    }
+
+    pub fn enum_constant(name: String, enum_name: &str, value: i64, position: Position) -> Self {
+        Self {
+            name,
+            typ: Type::Enum(enum_name.to_string()),
+            kind: SymbolKind::EnumConstant,
+            position,
+            param_types: None,
+            fields: None,
+            stack_offset: None,
+            is_variadic: false,
+            const_value: Some(value),
+            layout: None,
         }
     }
 
@@ -161,7 +213,7 @@ impl SymbolTable {
 
     pub fn insert_with_offset(&mut self, name: &str, mut symbol: Symbol) -> bool {
         if matches!(symbol.kind, SymbolKind::Variable | SymbolKind::Parameter) {
-            if let Some(size) = symbol.typ.size() {
+            if let Some(size) = self.type_size(&symbol.typ) {
                 symbol.stack_offset = Some(self.stack_offset);
                 self.stack_offset += size as i32;
             }
@@ -169,6 +221,61 @@ impl SymbolTable {
         self.insert(name, symbol)
     }
 
+    /// Размер значения типа в байтах; размер структуры берется из ее символа
+    pub fn type_size(&self, typ: &Type) -> Option<usize> {
+        match typ {
+            Type::Struct(name) => self.struct_symbol(name)?.layout.map(|(size, _)| size),
+            Type::Array(inner, count) => self.type_size(inner).map(|s| s * count),
+            t => t.size(),
+        }
+    }
+
+    /// Выравнивание типа в байтах с учетом вложенных структур
+    pub fn type_alignment(&self, typ: &Type) -> Option<usize> {
+        match typ {
+            Type::Struct(name) => self.struct_symbol(name)?.layout.map(|(_, align)| align),
+            Type::Array(inner, _) => self.type_alignment(inner),
+            t => t.alignment(),
+        }
+    }
+
+    /// Раскладка структуры по порядку объявления полей: смещения полей,
+    /// размер, округленный до выравнивания, и само выравнивание.
+    /// Поле-структура занимает размер своей раскладки
+    pub fn struct_layout(
+        &self,
+        fields: &HashMap<String, Type>,
+        field_order: &[String],
+    ) -> (HashMap<String, usize>, usize, usize) {
+        let mut offsets = HashMap::new();
+        let mut size: usize = 0;
+        let mut alignment = 1;
+        for name in field_order {
+            let field_type = match fields.get(name) {
+                Some(t) => t,
+                None => continue,
+            };
+            if let (Some(field_size), Some(align)) =
+                (self.type_size(field_type), self.type_alignment(field_type))
+            {
+                let align = align.max(1);
+                size = size.div_ceil(align) * align;
+                offsets.insert(name.clone(), size);
+                size += field_size;
+                alignment = alignment.max(align);
+            }
+        }
+        (offsets, size.div_ceil(alignment) * alignment, alignment)
+    }
+
+    fn struct_symbol(&self, name: &str) -> Option<&Symbol> {
+        self.scopes
+            .iter()
+            .rev()
+            .filter_map(|scope| scope.get(name))
+            .find(|symbol| symbol.kind == SymbolKind::Struct)
+    }
+
     pub fn lookup(&self, name: &str) -> Option<&Symbol> {
         for scope in self.scopes.iter().rev() {
             if let Some(symbol) = scope.get(name) {
@@ -228,6 +335,8 @@ impl SymbolTable {
                         SymbolKind::Function => "функция",
                         SymbolKind::Struct => "структура",
                         SymbolKind::Field => "поле",
+                        SymbolKind::Enum => "перечисление",
+                        SymbolKind::EnumConstant => "константа перечисления",
                     };
                     output.push_str(&format!("  {}: {} - {}\n", name, kind_ru, symbol.typ));
                 }
@@ -271,6 +380,8 @@ impl SymbolTable {
                         SymbolKind::Function => "функция",
                         SymbolKind::Struct => "структура",
                         SymbolKind::Field => "поле",
+                        SymbolKind::Enum => "перечисление",
+                        SymbolKind::EnumConstant => "константа перечисления",
                     };
                     output.push_str(&format!("  {}: {} - {}", name, kind_ru, symbol.typ));
 
@@ -278,7 +389,7 @@ impl SymbolTable {
                         output.push_str(&format!(" [смещение: {}]", offset));
                     }
 
-                    if let Some(size) = symbol.typ.size() {
+                    if let Some(size) = self.type_size(&symbol.typ) {
                         output.push_str(&format!(" [размер: {}]", size));
                     }
 
@@ -291,7 +402,7 @@ impl SymbolTable {
                         for (field_name, field_type) in sorted_fields {
                             output.push_str(&format!("    {}: {}", field_name, field_type));
                             output.push_str(&format!(" [смещение: {}]", current_offset));
-                            if let Some(size) = field_type.size() {
+                            if let Some(size) = self.type_size(field_type) {
                                 output.push_str(&format!(" [размер: {}]", size));
                                 current_offset += size;
                             }
@@ -352,4 +463,31 @@ mod tests {
         assert!(table.lookup("y").is_none());
         assert!(table.lookup("x").is_some());
     }
+
+    #[test]
+    fn test_nested_struct_layout() {
+        let pos = Position::new(1, 1);
+        let mut table = SymbolTable::new();
+
+        let inner: HashMap<String, Type> =
+            [("c".to_string(), Type::Char), ("x".to_string(), Type::Int)].into_iter().collect();
+        let order = ["c".to_string(), "x".to_string()];
+        let (offsets, size, alignment) = table.struct_layout(&inner, &order);
+        assert_eq!((offsets["x"], size, alignment), (4, 8, 4));
+        let symbol = Symbol::struct_type("P".to_string(), inner, pos.clone());
+        table.insert("P", symbol.with_layout(size, alignment));
+
+        let outer: HashMap<String, Type> = [
+            ("tag".to_string(), Type::Char),
+            ("in".to_string(), Type::Struct("P".to_string())),
+            ("all".to_string(), Type::Array(Box::new(Type::Struct("P".to_string())), 2)),
+        ]
+        .into_iter()
+        .collect();
+        let order = ["tag".to_string(), "in".to_string(), "all".to_string()];
+        let (offsets, size, _) = table.struct_layout(&outer, &order);
+        assert_eq!((offsets["in"], offsets["all"], size), (4, 12, 28));
+        assert_eq!(table.type_size(&Type::Struct("P".to_string())), Some(8));
+        assert_eq!(table.type_size(&Type::Struct("Missing".to_string())), None);
+    }
 }
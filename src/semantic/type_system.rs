@@ -6,12 +6,17 @@ use std::fmt;
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Int,
+    Long,
+    UInt,
+    ULong,
     Float,
     Bool,
     Void,
     String,
     Char,
     Struct(String),
+    /// Перечисление; значения хранятся как `int`
+    Enum(String),
     Pointer(Box<Type>),
     Function {
         return_type: Box<Type>,
@@ -24,14 +29,26 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Type::Int => write!(f, "int"),
+            Type::Long => write!(f, "long"),
+            Type::UInt => write!(f, "unsigned int"),
+            Type::ULong => write!(f, "unsigned long"),
             Type::Float => write!(f, "float"),
             Type::Bool => write!(f, "bool"),
             Type::Void => write!(f, "void"),
             Type::String => write!(f, "string"),
             Type::Char => write!(f, "char"),
             Type::Struct(name) => write!(f, "struct {}", name),
+            Type::Enum(name) => write!(f, "enum {}", name),
             Type::Pointer(inner) => write!(f, "{}*", inner),
-            Type::Array(inner, size) => write!(f, "{}[{}]", inner, size),
+            Type::Array(..) => {
+                let mut element = self;
+                let mut dims = String::new();
+                while let Type::Array(inner, size) = element {
+                    dims.push_str(&format!("[{}]", size));
+                    element = inner;
+                }
+                write!(f, "{}{}", element, dims)
+            }
             Type::Function {
                 return_type,
                 param_types,
@@ -54,12 +71,16 @@ impl Type {
     pub fn from_ast(ast_type: &crate::parser::ast::Type) -> Self {
         match ast_type {
             crate::parser::ast::Type::Int => Type::Int,
+            crate::parser::ast::Type::Long => Type::Long,
+            crate::parser::ast::Type::UInt => Type::UInt,
+            crate::parser::ast::Type::ULong => Type::ULong,
             crate::parser::ast::Type::Float => Type::Float,
             crate::parser::ast::Type::Bool => Type::Bool,
             crate::parser::ast::Type::Void => Type::Void,
             crate::parser::ast::Type::String => Type::String,
             crate::parser::ast::Type::Char => Type::Char,
             crate::parser::ast::Type::Struct(name) => Type::Struct(name.clone()),
+            crate::parser::ast::Type::Enum(name) => Type::Enum(name.clone()),
             crate::parser::ast::Type::Inferred => Type::Int,
             crate::parser::ast::Type::Pointer(inner) => {
                 Type::Pointer(Box::new(Type::from_ast(inner)))
@@ -71,11 +92,35 @@ impl Type {
     }
 
     pub fn is_numeric(&self) -> bool {
-        matches!(self, Type::Int | Type::Float)
+        matches!(self, Type::Int | Type::Long | Type::UInt | Type::ULong | Type::Float)
     }
 
     pub fn is_integer(&self) -> bool {
-        matches!(self, Type::Int | Type::Char)
+        matches!(
+            self,
+            Type::Int | Type::Long | Type::UInt | Type::ULong | Type::Char | Type::Enum(_)
+        )
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Type::UInt | Type::ULong)
+    }
+
+    /// Ранг целого типа для обычных арифметических преобразований
+    fn integer_rank(&self) -> u8 {
+        match self {
+            Type::Long | Type::ULong => 2,
+            Type::Int | Type::UInt => 1,
+            _ => 0,
+        }
+    }
+
+    /// Целочисленное повышение: `char` и перечисления вычисляются как `int`
+    pub fn promoted(&self) -> Type {
+        match self {
+            Type::Char | Type::Enum(_) => Type::Int,
+            other => other.clone(),
+        }
     }
 
     pub fn is_boolean(&self) -> bool {
@@ -98,9 +143,31 @@ impl Type {
         matches!(self, Type::Pointer(_))
     }
 
+    pub fn is_array(&self) -> bool {
+        matches!(self, Type::Array(_, _))
+    }
+
+    /// Тип литерала `null`: `void*`, присваиваемый любому указателю
+    pub fn null_pointer() -> Type {
+        Type::Pointer(Box::new(Type::Void))
+    }
+
+    /// Тип элемента указателя или массива
+    pub fn element_type(&self) -> Option<&Type> {
+        match self {
+            Type::Pointer(inner) | Type::Array(inner, _) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Размер значения в памяти по раскладке C (совпадает с `ABI::type_size`);
+    /// он же шаг индексации и адресной арифметики. Размер структуры зависит
+    /// от ее полей и берется из `SymbolTable::type_size`
     pub fn size(&self) -> Option<usize> {
         match self {
-            Type::Int | Type::Bool => Some(4),
+            Type::Int | Type::UInt | Type::Enum(_) => Some(4),
+            Type::Bool => Some(1),
+            Type::Long | Type::ULong => Some(8),
             Type::Float => Some(8),
             Type::Void => Some(0),
             Type::String => Some(8),
@@ -122,28 +189,11 @@ impl Type {
         total_size
     }
 
-    pub fn struct_offsets(
-        fields: &std::collections::HashMap<String, Type>,
-        field_order: &[String],
-    ) -> std::collections::HashMap<String, usize> {
-        let mut offsets = std::collections::HashMap::new();
-        let mut current_offset = 0;
-
-        for name in field_order {
-            if let Some(field_type) = fields.get(name) {
-                if let Some(size) = field_type.size() {
-                    offsets.insert(name.clone(), current_offset);
-                    current_offset += size;
-                }
-            }
-        }
-
-        offsets
-    }
-
     pub fn alignment(&self) -> Option<usize> {
         match self {
-            Type::Int | Type::Bool => Some(4),
+            Type::Int | Type::UInt | Type::Enum(_) => Some(4),
+            Type::Bool => Some(1),
+            Type::Long | Type::ULong => Some(8),
             Type::Float => Some(8),
             Type::Void => Some(0),
             Type::String => Some(8),
@@ -151,7 +201,7 @@ impl Type {
             Type::Pointer(_) => Some(8),
             Type::Struct(_) => None,
             Type::Function { .. } => Some(8),
-            Type::Array(inner, count) => inner.size().map(|s| s * count),
+            Type::Array(inner, _) => inner.alignment(),
         }
     }
 }
@@ -213,15 +263,45 @@ impl TypeChecker {
 
     pub fn is_assignable(&self, target: &Type, source: &Type) -> bool {
         match (target, source) {
-            (Type::Float, Type::Int) => true,
+            (Type::Float, s) if s.is_integer() => true,
+            (t, s) if t.is_integer() && s.is_integer() => true,
             (Type::Pointer(_), Type::Pointer(_)) => true,
             (Type::Pointer(_), Type::Array(_, _)) => true,
             (Type::Array(_, _), Type::Pointer(_)) => true,
+            (Type::Array(t, _), Type::Array(s, _)) => Self::same_row_shape(t, s),
             (Type::Pointer(t), Type::String) if matches!(**t, Type::Char) => true,
             (t, s) => self.is_compatible(t, s),
         }
     }
 
+    /// Строки массивов совпадают по всем внутренним размерностям:
+    /// `int[3][4]` передается в `int m[][4]`, но не в `int m[][5]`
+    fn same_row_shape(target: &Type, source: &Type) -> bool {
+        match (target, source) {
+            (Type::Array(t, n), Type::Array(s, m)) => n == m && Self::same_row_shape(t, s),
+            (Type::Array(..), _) | (_, Type::Array(..)) => false,
+            _ => true,
+        }
+    }
+
+    /// Допустимость явного приведения `(to)from`
+    ///
+    /// Разрешены преобразования между скалярами (числа, `char`, `bool`),
+    /// между указателями, между указателем и целым, приведение массива или
+    /// строки к указателю и приведение любого значения к `void`
+    pub fn is_valid_cast(&self, from: &Type, to: &Type) -> bool {
+        let scalar = |t: &Type| t.is_numeric() || t.is_integer() || t.is_boolean();
+        let address = |t: &Type| matches!(t, Type::Pointer(_) | Type::Array(_, _) | Type::String);
+        match (from, to) {
+            (_, Type::Void) => true,
+            (f, t) if scalar(f) && scalar(t) => true,
+            (f, Type::Pointer(_)) if address(f) => true,
+            (f, Type::Pointer(_)) if f.is_integer() => true,
+            (f, t) if address(f) && (t.is_integer() || t.is_boolean()) => true,
+            (f, t) => f == t,
+        }
+    }
+
     pub fn are_compatible_binary(&self, left: &Type, right: &Type) -> bool {
         match (left, right) {
             (Type::Int, Type::Int) => true,
@@ -231,23 +311,34 @@ impl TypeChecker {
             (Type::Bool, Type::Bool) => true,
             (Type::Char, Type::Char) => true,
             (Type::Char, Type::Int) | (Type::Int, Type::Char) => true,
+            (l, r) if l.is_integer() && r.is_integer() => true,
             (l, r) if l.is_numeric() && r.is_numeric() => true,
+            (l, r) if l.element_type().is_some() || r.element_type().is_some() => true,
             _ => false,
         }
     }
 
     pub fn binary_result_type(&self, left: &Type, right: &Type, op: BinaryOpType) -> Option<Type> {
         match op {
-            BinaryOpType::Arithmetic | BinaryOpType::ArithmeticAssign => match (left, right) {
-                (Type::Float, _) | (_, Type::Float) => Some(Type::Float),
-                (Type::Int, Type::Int) => Some(Type::Int),
-                (Type::Char, Type::Char) | (Type::Char, Type::Int) | (Type::Int, Type::Char) => {
-                    Some(Type::Int)
+            BinaryOpType::Arithmetic | BinaryOpType::ArithmeticAssign => {
+                self.common_numeric_type(left, right)
+            }
+            BinaryOpType::Addition => match (left, right) {
+                (p, i) | (i, p) if i.is_integer() && p.element_type().is_some() => {
+                    Self::pointer_offset_type(p)
                 }
+                _ => self.common_numeric_type(left, right),
+            },
+            BinaryOpType::Subtraction => match (left.element_type(), right.element_type()) {
+                (Some(_), None) if right.is_integer() => Self::pointer_offset_type(left),
+                (Some(l), Some(r)) if l == r && !l.is_void() => Some(Type::Long),
+                (None, None) => self.common_numeric_type(left, right),
                 _ => None,
             },
             BinaryOpType::Comparison => {
-                if left.is_numeric() && right.is_numeric() {
+                let numeric = |t: &Type| t.is_numeric() || t.is_integer();
+                let numeric = numeric(left) && numeric(right);
+                if numeric || Self::are_comparable_pointers(left, right) {
                     Some(Type::Bool)
                 } else {
                     None
@@ -260,6 +351,38 @@ impl TypeChecker {
                     None
                 }
             }
+            BinaryOpType::Bitwise => {
+                if left.is_integer() && right.is_integer() {
+                    self.common_numeric_type(left, right)
+                } else {
+                    None
+                }
+            }
+            BinaryOpType::Shift => {
+                if left.is_integer() && right.is_integer() {
+                    Some(left.promoted())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Тип `p + i`: массив распадается до указателя на элемент,
+    /// арифметика над `void*` не определена
+    fn pointer_offset_type(pointer: &Type) -> Option<Type> {
+        match pointer.element_type()? {
+            Type::Void => None,
+            element => Some(Type::Pointer(Box::new(element.clone()))),
+        }
+    }
+
+    /// Указатели сравнимы, если указывают на один тип или один из них `void*`
+    /// (в том числе `null`)
+    fn are_comparable_pointers(left: &Type, right: &Type) -> bool {
+        match (left.element_type(), right.element_type()) {
+            (Some(l), Some(r)) => l == r || l.is_void() || r.is_void(),
+            _ => false,
         }
     }
 
@@ -296,8 +419,17 @@ impl TypeChecker {
                 }
             }
             UnaryOpType::AddrOf => Some(Type::Pointer(Box::new(operand.clone()))),
+            UnaryOpType::BitNot => {
+                if operand.is_integer() {
+                    Some(operand.promoted())
+                } else {
+                    None
+                }
+            }
             UnaryOpType::Increment | UnaryOpType::Decrement => {
-                if operand.is_numeric() {
+                if operand.is_numeric()
+                    || (operand.is_pointer() && Self::pointer_offset_type(operand).is_some())
+                {
                     Some(operand.clone())
                 } else {
                     None
@@ -315,6 +447,7 @@ impl TypeChecker {
             (Type::String, Type::String) => true,
             (Type::Char, Type::Char) => true,
             (Type::Char, Type::Int) | (Type::Int, Type::Char) => true,
+            (l, r) if l.is_integer() && r.is_integer() => true,
             _ => false,
         }
     }
@@ -329,16 +462,52 @@ impl TypeChecker {
         }
     }
 
+    /// Обычные арифметические преобразования C: `float` поглощает целые,
+    /// `char` повышается до `int`, затем выбирается тип старшего ранга;
+    /// при равном ранге побеждает беззнаковый, а `long` вмещает `unsigned int`
     pub fn common_numeric_type(&self, left: &Type, right: &Type) -> Option<Type> {
         match (left, right) {
-            (Type::Float, _) | (_, Type::Float) => Some(Type::Float),
-            (Type::Int, Type::Int) => Some(Type::Int),
-            (Type::Char, Type::Char) | (Type::Char, Type::Int) | (Type::Int, Type::Char) => {
-                Some(Type::Int)
+            (Type::Float, other) | (other, Type::Float)
+                if other.is_numeric() || other.is_integer() =>
+            {
+                Some(Type::Float)
+            }
+            (l, r) if l.is_integer() && r.is_integer() => {
+                let (l, r) = (l.promoted(), r.promoted());
+                let common = if l.is_unsigned() == r.is_unsigned() {
+                    if l.integer_rank() >= r.integer_rank() { l } else { r }
+                } else {
+                    let (unsigned, signed) = if l.is_unsigned() { (l, r) } else { (r, l) };
+                    if unsigned.integer_rank() >= signed.integer_rank() {
+                        unsigned
+                    } else {
+                        signed
+                    }
+                };
+                Some(common)
             }
             _ => None,
         }
     }
+
+    /// Общий тип ветвей условного выражения `c ? a : b`
+    ///
+    /// Числовые ветви приводятся к общему арифметическому типу, массивы
+    /// низводятся до указателей, а `void*` (в том числе `null`) уступает
+    /// типизированному указателю. Остальные типы должны совпадать
+    pub fn conditional_result_type(&self, then_type: &Type, else_type: &Type) -> Option<Type> {
+        if let Some(common) = self.common_numeric_type(then_type, else_type) {
+            return Some(common);
+        }
+        match (then_type.element_type(), else_type.element_type()) {
+            (Some(t), Some(e)) if Self::are_comparable_pointers(then_type, else_type) => {
+                let pointee = if t.is_void() { e } else { t };
+                Some(Type::Pointer(Box::new(pointee.clone())))
+            }
+            (None, None) if then_type == else_type => Some(then_type.clone()),
+            _ => None,
+        }
+    }
 }
 
 /// Тип бинарной операции для проверки типов
@@ -346,17 +515,23 @@ impl TypeChecker {
 pub enum BinaryOpType {
     Arithmetic,
     ArithmeticAssign,
+    /// `+`: допускает `указатель + целое`
+    Addition,
+    /// `-`: допускает `указатель - целое` и разность указателей
+    Subtraction,
     Comparison,
     Logical,
+    Bitwise,
+    Shift,
 }
 
 impl From<&crate::parser::ast::BinaryOp> for BinaryOpType {
     fn from(op: &crate::parser::ast::BinaryOp) -> Self {
         use crate::parser::ast::BinaryOp;
         match op {
-            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
-                BinaryOpType::Arithmetic
-            }
+            BinaryOp::Add => BinaryOpType::Addition,
+            BinaryOp::Sub => BinaryOpType::Subtraction,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => BinaryOpType::Arithmetic,
             BinaryOp::Eq
             | BinaryOp::Ne
             | BinaryOp::Lt
@@ -364,6 +539,8 @@ impl From<&crate::parser::ast::BinaryOp> for BinaryOpType {
             | BinaryOp::Gt
             | BinaryOp::Ge => BinaryOpType::Comparison,
             BinaryOp::And | BinaryOp::Or => BinaryOpType::Logical,
+            BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor => BinaryOpType::Bitwise,
+            BinaryOp::Shl | BinaryOp::Shr => BinaryOpType::Shift,
         }
     }
 }
@@ -376,6 +553,7 @@ pub enum UnaryOpType {
     Plus,
     Deref,
     AddrOf,
+    BitNot,
     Increment,
     Decrement,
 }
@@ -389,6 +567,7 @@ impl From<&crate::parser::ast::UnaryOp> for UnaryOpType {
             UnaryOp::Plus => UnaryOpType::Plus,
             UnaryOp::Deref => UnaryOpType::Deref,
             UnaryOp::AddrOf => UnaryOpType::AddrOf,
+            UnaryOp::BitNot => UnaryOpType::BitNot,
             UnaryOp::PreIncrement | UnaryOp::PostIncrement => UnaryOpType::Increment,
             UnaryOp::PreDecrement | UnaryOp::PostDecrement => UnaryOpType::Decrement,
         }
@@ -425,5 +604,122 @@ mod tests {
             checker.binary_result_type(&Type::Bool, &Type::Bool, BinaryOpType::Logical),
             Some(Type::Bool)
         );
+        assert_eq!(
+            checker.binary_result_type(&Type::Char, &Type::Int, BinaryOpType::Bitwise),
+            Some(Type::Int)
+        );
+        assert_eq!(
+            checker.binary_result_type(&Type::Int, &Type::Float, BinaryOpType::Bitwise),
+            None
+        );
+        assert_eq!(
+            checker.binary_result_type(&Type::Bool, &Type::Bool, BinaryOpType::Bitwise),
+            None
+        );
+    }
+
+    #[test]
+    fn test_usual_arithmetic_conversions() {
+        let checker = TypeChecker::new();
+        let cases = [
+            (Type::Char, Type::Char, Type::Int),
+            (Type::Int, Type::UInt, Type::UInt),
+            (Type::Int, Type::Long, Type::Long),
+            (Type::Long, Type::UInt, Type::Long),
+            (Type::UInt, Type::ULong, Type::ULong),
+            (Type::Long, Type::ULong, Type::ULong),
+            (Type::ULong, Type::Float, Type::Float),
+        ];
+        for (left, right, expected) in cases {
+            assert_eq!(checker.common_numeric_type(&left, &right), Some(expected.clone()));
+            assert_eq!(checker.common_numeric_type(&right, &left), Some(expected));
+        }
+
+        assert_eq!(
+            checker.binary_result_type(&Type::UInt, &Type::Long, BinaryOpType::Shift),
+            Some(Type::UInt)
+        );
+        assert!(checker.is_assignable(&Type::Int, &Type::ULong));
+        assert!(checker.is_assignable(&Type::Float, &Type::Long));
+    }
+
+    #[test]
+    fn test_explicit_casts() {
+        let checker = TypeChecker::new();
+        let char_ptr = Type::Pointer(Box::new(Type::Char));
+        let void_ptr = Type::Pointer(Box::new(Type::Void));
+        let node = Type::Struct("Node".to_string());
+
+        assert!(checker.is_valid_cast(&Type::Float, &Type::Int));
+        assert!(checker.is_valid_cast(&Type::Int, &Type::Char));
+        assert!(checker.is_valid_cast(&Type::Bool, &Type::Float));
+        assert!(checker.is_valid_cast(&void_ptr, &char_ptr));
+        assert!(checker.is_valid_cast(&Type::Long, &char_ptr));
+        assert!(checker.is_valid_cast(&char_ptr, &Type::ULong));
+        assert!(checker.is_valid_cast(&node, &Type::Void));
+        assert!(checker.is_valid_cast(&node, &node));
+
+        assert!(!checker.is_valid_cast(&Type::Float, &char_ptr));
+        assert!(!checker.is_valid_cast(&char_ptr, &Type::Float));
+        assert!(!checker.is_valid_cast(&node, &Type::Int));
+        assert!(!checker.is_valid_cast(&Type::Int, &node));
+    }
+
+    #[test]
+    fn test_pointer_arithmetic() {
+        let checker = TypeChecker::new();
+        let int_ptr = Type::Pointer(Box::new(Type::Int));
+        let char_ptr = Type::Pointer(Box::new(Type::Char));
+        let void_ptr = Type::null_pointer();
+
+        assert_eq!(
+            checker.binary_result_type(&int_ptr, &Type::Int, BinaryOpType::Addition),
+            Some(int_ptr.clone())
+        );
+        assert_eq!(
+            checker.binary_result_type(&Type::Long, &int_ptr, BinaryOpType::Addition),
+            Some(int_ptr.clone())
+        );
+        assert_eq!(
+            checker.binary_result_type(&int_ptr, &Type::Char, BinaryOpType::Subtraction),
+            Some(int_ptr.clone())
+        );
+        assert_eq!(
+            checker.binary_result_type(&int_ptr, &int_ptr, BinaryOpType::Subtraction),
+            Some(Type::Long)
+        );
+        assert_eq!(
+            checker.binary_result_type(&int_ptr, &void_ptr, BinaryOpType::Comparison),
+            Some(Type::Bool)
+        );
+
+        assert_eq!(checker.binary_result_type(&int_ptr, &int_ptr, BinaryOpType::Addition), None);
+        assert_eq!(checker.binary_result_type(&int_ptr, &Type::Float, BinaryOpType::Addition), None);
+        assert_eq!(checker.binary_result_type(&int_ptr, &char_ptr, BinaryOpType::Subtraction), None);
+        assert_eq!(checker.binary_result_type(&void_ptr, &Type::Int, BinaryOpType::Addition), None);
+        assert_eq!(checker.binary_result_type(&int_ptr, &char_ptr, BinaryOpType::Comparison), None);
+    }
+    #[test]
+    fn test_conditional_result_type() {
+        let checker = TypeChecker::new();
+        let int_ptr = Type::Pointer(Box::new(Type::Int));
+        let int_array = Type::Array(Box::new(Type::Int), 4);
+
+        assert_eq!(checker.conditional_result_type(&Type::Int, &Type::Long), Some(Type::Long));
+        assert_eq!(checker.conditional_result_type(&Type::Char, &Type::Float), Some(Type::Float));
+        assert_eq!(checker.conditional_result_type(&Type::Bool, &Type::Bool), Some(Type::Bool));
+        assert_eq!(
+            checker.conditional_result_type(&Type::null_pointer(), &int_ptr),
+            Some(int_ptr.clone())
+        );
+        assert_eq!(
+            checker.conditional_result_type(&int_array, &int_ptr),
+            Some(int_ptr.clone())
+        );
+
+        let char_ptr = Type::Pointer(Box::new(Type::Char));
+        assert_eq!(checker.conditional_result_type(&int_ptr, &char_ptr), None);
+        assert_eq!(checker.conditional_result_type(&int_ptr, &Type::Int), None);
+        assert_eq!(checker.conditional_result_type(&Type::Bool, &Type::Int), None);
     }
 }
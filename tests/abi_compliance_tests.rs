@@ -132,4 +132,145 @@ fn test_abi_leaf_function_red_zone() {
     let result = generate_assembly(&ir, true);
 
     println!("Leaf function assembly:\n{}", result.assembly);
-}
\ No newline at end of file
+}
+
+fn assembly_for(source: &str) -> String {
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid());
+    generate_assembly(&ir_program.unwrap(), false).assembly
+}
+
+#[test]
+fn test_abi_float_parameters_in_xmm() {
+    let asm = assembly_for(
+        r#"
+        fn scale(float x, float y) -> float {
+            return x * y;
+        }
+
+        fn main() -> int {
+            float r = scale(1.5, 2.0);
+            return 0;
+        }
+    "#,
+    );
+
+    assert!(asm.contains("movsd qword [rbp+16], xmm0"));
+    assert!(asm.contains("movsd qword [rbp+24], xmm1"));
+    assert!(asm.contains("movsd xmm0, qword [rsp]"));
+    assert!(asm.contains("movsd xmm1, qword [rsp]"));
+}
+
+#[test]
+fn test_abi_mixed_int_float_ordering() {
+    let asm = assembly_for(
+        r#"
+        fn mix(int a, float b, int c, float d) -> float {
+            return a * b + c * d;
+        }
+
+        fn main() -> int {
+            float r = mix(2, 1.5, 3, 0.5);
+            return 0;
+        }
+    "#,
+    );
+
+    // Целые и вещественные аргументы нумеруются независимо
+    assert!(asm.contains("mov [rbp+16], rdi"));
+    assert!(asm.contains("movsd qword [rbp+24], xmm0"));
+    assert!(asm.contains("mov [rbp+32], rsi"));
+    assert!(asm.contains("movsd qword [rbp+40], xmm1"));
+
+    let call_site: Vec<&str> = asm
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| *l != "main:")
+        .filter(|l| l.starts_with("pop rdi") || l.starts_with("pop rsi") || l.contains("xmm"))
+        .collect();
+    assert_eq!(
+        &call_site[..4],
+        &[
+            "pop rdi",
+            "movsd xmm0, qword [rsp]",
+            "pop rsi",
+            "movsd xmm1, qword [rsp]",
+        ]
+    );
+}
+
+#[test]
+fn test_abi_float_arguments_overflow_to_stack() {
+    let asm = assembly_for(
+        r#"
+        fn many(float a, float b, float c, float d, float e,
+                float f, float g, float h, float i, int k) -> float {
+            return a + i + k;
+        }
+
+        fn main() -> int {
+            float r = many(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10);
+            return 0;
+        }
+    "#,
+    );
+
+    // Девятый вещественный аргумент идет в стек, целый - в rdi
+    assert!(asm.contains("movsd qword [rbp+80], xmm7"));
+    assert!(asm.contains("mov [rbp+88], rdi"));
+    assert!(!asm.contains("xmm8"));
+    assert!(asm.contains("mov eax, 8"));
+}
+
+#[test]
+fn test_abi_float_return_in_xmm0() {
+    let asm = assembly_for(
+        r#"
+        fn half(float x) -> float {
+            return x / 2.0;
+        }
+
+        fn main() -> int {
+            float h = half(3.0);
+            if (h > 1.0) {
+                return 1;
+            }
+            return 0;
+        }
+    "#,
+    );
+
+    let half: Vec<&str> = asm
+        .lines()
+        .map(str::trim)
+        .skip_while(|l| *l != "half:")
+        .take_while(|l| *l != "main:")
+        .collect();
+    assert!(half.iter().any(|l| l.starts_with("movsd xmm0") || l.starts_with("movq xmm0")));
+    assert!(!half.iter().any(|l| l.starts_with("mov rax")));
+    assert!(asm.contains("movq r15, xmm0"));
+}
+
+#[test]
+fn test_abi_variadic_call_sets_al() {
+    let asm = assembly_for(
+        r#"
+        extern int printf(char* format, ...);
+
+        fn main() -> int {
+            float x = 2.5;
+            printf("%f %d\n", x, 3);
+            printf("%d\n", 4);
+            return 0;
+        }
+    "#,
+    );
+
+    let calls: Vec<&str> = asm
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with("mov eax,") || l.starts_with("xor eax") || l.starts_with("call"))
+        .collect();
+    assert_eq!(calls, ["mov eax, 1", "call printf", "xor eax, eax", "call printf"]);
+    assert!(asm.contains("movsd xmm0, qword [rsp]"));
+}
@@ -534,7 +534,10 @@ fn test_short_circuit_or() {
     println!("=== Short-Circuit OR ===\n{}", result.assembly);
 
     assert!(
-        result.assembly.contains("jmp"),
+        result
+            .assembly
+            .lines()
+            .any(|l| l.trim_start().starts_with('j')),
         "Нет перехода для короткой схемы"
     );
 }
@@ -705,3 +708,442 @@ fn test_fibonacci_sequence() {
         "Нет рекурсивного вызова"
     );
 }
+
+#[test]
+fn test_struct_field_offsets_codegen() {
+    let source = r#"
+        struct Point { int x; int y; float w; }
+
+        fn main() -> int {
+            struct Point p;
+            p.x = 1;
+            p.y = 2;
+            p.w = 1.5;
+            return p.y;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+
+    let ir = ir_program.expect("IR не сгенерирован");
+    let layout = ir.get_struct("Point").expect("Нет раскладки Point");
+    assert_eq!(layout.size, 16, "Неверный размер структуры");
+    assert_eq!(layout.field("y").map(|f| f.2), Some(4));
+    assert_eq!(layout.field("w").map(|f| f.2), Some(8));
+
+    let result = generate_assembly(&ir, false);
+
+    println!("=== Struct ===\n{}", result.assembly);
+
+    assert!(
+        result.assembly.contains("mov dword [rbp-28], ecx"),
+        "Поле y должно записываться по смещению 4"
+    );
+    assert!(
+        result.assembly.contains("mov qword [rbp-24], rcx"),
+        "Поле w должно записываться по смещению 8"
+    );
+    assert!(
+        result.assembly.contains("movsxd rax, dword [rbp-28]"),
+        "Поле y должно читаться как dword"
+    );
+}
+
+#[test]
+fn test_struct_by_value_codegen() {
+    let source = r#"
+        struct P { int x; int y; }
+
+        fn make() -> struct P {
+            struct P p;
+            p.x = 1;
+            p.y = 2;
+            return p;
+        }
+
+        fn bump(struct P a) -> int {
+            a.x = 100;
+            return a.x;
+        }
+
+        fn main() -> int {
+            struct P q = make();
+            return bump(q) + q.x;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let result = generate_assembly(&ir_program.expect("IR не сгенерирован"), false);
+    let asm = &result.assembly;
+
+    println!("=== Struct by value ===\n{}", asm);
+
+    for line in asm.lines().map(str::trim) {
+        assert!(
+            !line.starts_with("mov rbp-") && !line.ends_with(", rbp-32"),
+            "Адрес стека без скобок: {}",
+            line
+        );
+    }
+    let make = &asm[asm.find("make:").expect("Нет make")..];
+    assert!(make.contains("mov [rbp+16], rdi"), "Адрес буфера приходит в rdi");
+    assert!(make.contains("mov dword [rax+4], ecx"), "Поле y копируется в буфер");
+    let main = &asm[asm.find("main:").expect("Нет main")..];
+    let calls = main.matches("lea rax, [rbp-").count();
+    assert!(calls >= 2, "Вызовы получают адреса буфера и копии");
+}
+
+#[test]
+fn test_nested_struct_and_struct_array_fields() {
+    use minic::ir::{IRInstruction, IRType, Operand};
+
+    let source = r#"
+        struct P { int x; int y; }
+        struct Q { int a; struct P in; }
+
+        fn main() -> int {
+            struct Q q;
+            q.in.y = 3;
+            struct P ps[4];
+            int i = 2;
+            ps[i].x = 5;
+            int* p = &ps[i].y;
+            return q.in.y + ps[i].x + *p;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let ir = ir_program.expect("IR не сгенерирован");
+
+    let q = ir.get_struct("Q").expect("Нет раскладки Q");
+    assert_eq!(q.size, 12, "Вложенная структура должна занимать свой размер");
+    assert_eq!(q.field("in").map(|f| f.2), Some(4));
+
+    let instructions: Vec<&IRInstruction> =
+        ir.functions[0].blocks.values().flat_map(|b| &b.instructions).collect();
+    let has = |f: &dyn Fn(&IRInstruction) -> bool| instructions.iter().any(|i| f(i));
+    let q_in_y = |i: &IRInstruction| match i {
+        IRInstruction::FieldStore(Operand::Variable(b), 8, Operand::IntLiteral(3), _) => b == "q",
+        _ => false,
+    };
+    assert!(has(&q_in_y), "q.in.y должно записываться по смещению 4 + 4");
+    assert!(has(&|i| matches!(i, IRInstruction::Alloca(_, 32))), "Массив struct P[4] - 32 байта");
+    assert!(
+        has(&|i| matches!(i, IRInstruction::Mul(_, _, Operand::IntLiteral(8)))),
+        "Индекс ps[i] должен умножаться на размер структуры"
+    );
+    assert!(
+        has(&|i| matches!(i, IRInstruction::FieldStore(_, 0, Operand::IntLiteral(5), IRType::Int))),
+        "ps[i].x должно записываться через адрес элемента"
+    );
+    assert!(
+        has(&|i| matches!(i, IRInstruction::Gep(_, _, 4))),
+        "&ps[i].y должно давать адрес элемента плюс смещение поля"
+    );
+    assert!(
+        !has(&|i| matches!(i, IRInstruction::Move(Operand::Variable(v), _) if v == "q")),
+        "Запись во вложенное поле не должна перезаписывать саму переменную"
+    );
+
+    let result = generate_assembly(&ir, false);
+    assert!(result.assembly.contains("main:"));
+}
+
+#[test]
+fn test_global_data_sections() {
+    let source = r#"
+        int table[4] = {1, 2, 3, 4};
+        float pi = 3.14;
+        int counter;
+        int partial[5] = {7, 8};
+        string msg = "hi";
+
+        fn main() -> int {
+            return table[1] + counter + partial[0];
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+
+    let ir = ir_program.expect("IR не сгенерирован");
+    assert_eq!(ir.global_inits.get("table").map(|v| v.len()), Some(4));
+    assert!(!ir.global_inits.contains_key("counter"));
+
+    let result = generate_assembly(&ir, false);
+
+    println!("=== Globals ===\n{}", result.assembly);
+
+    let asm = &result.assembly;
+    let data = asm.find("section .data").expect("Нет секции .data");
+    let bss = asm.find("section .bss").expect("Нет секции .bss");
+    let rodata = asm.find("section .rodata").expect("Нет секции .rodata");
+    assert!(asm.contains("table: dd 1, 2, 3, 4"), "Массив должен быть инициализирован");
+    assert!(asm.contains("pi: dq 3.14"), "Вещественная константа не записана");
+    assert!(
+        asm.contains("partial: dd 7, 8\n    times 4 dd 0"),
+        "Остаток массива должен быть нулевым"
+    );
+    assert!(asm.contains("counter: resq 1"), "Нулевая переменная должна быть в .bss");
+    assert!(data < bss && bss < rodata);
+    let str_pos = asm.find("db \"hi\", 0").expect("Нет строки");
+    assert!(str_pos > rodata, "Строка должна быть в .rodata");
+}
+
+#[test]
+fn test_global_constant_initializers_folded() {
+    let source = r#"
+        struct P { int x; char c; }
+
+        int wrapped = (int)4294967297L;
+        char letter = (char)(65 + 256);
+        float ratio = (float)3 / 2;
+        bool ordered = 1 < 2 && !0;
+        long bytes = sizeof(struct P) * 2;
+        char word[4] = {'o', 'k'};
+        unsigned long half = 18000000000000000000UL / 2UL;
+        int overflow = 2147483647 + 1;
+        float mixed[3] = {1.0, 2};
+        char msg[6] = "hi\n";
+        long picked = 1 > 0 ? 7 : 8;
+        bool above = 0U - 1U > 5U;
+
+        fn main() -> int {
+            return wrapped + letter;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let result = generate_assembly(&ir_program.expect("IR не сгенерирован"), false);
+    println!("{}", result.assembly);
+
+    let asm = &result.assembly;
+    assert!(asm.contains("wrapped: dq 1"));
+    assert!(asm.contains("letter: dq 65"));
+    assert!(asm.contains("ratio: dq 1.5"));
+    assert!(asm.contains("ordered: dq 1"));
+    assert!(asm.contains("bytes: dq 16"));
+    assert!(asm.contains("word: db 111, 107\n    times 6 db 0"));
+    assert!(asm.contains("half: dq 9000000000000000000"));
+    assert!(asm.contains("overflow: dq -2147483648"));
+    assert!(asm.contains("mixed: dq 1.0, 2.0\n    times 1 dq 0"));
+    assert!(asm.contains("msg: db 104, 105, 10, 0\n    times 4 db 0"));
+    assert!(asm.contains("picked: dq 7"));
+    assert!(asm.contains("above: dq 1"));
+}
+
+#[test]
+fn test_block_layout_reverse_postorder() {
+    let source = r#"
+        fn main() -> int {
+            int i = 0;
+            int s = 0;
+            while (i < 10) {
+                if (i > 3 && i < 8) {
+                    s = s + i;
+                } else {
+                    s = s - 1;
+                }
+                i = i + 1;
+            }
+            return s;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+
+    let result = generate_assembly(&ir_program.expect("IR не сгенерирован"), false);
+    let asm = &result.assembly;
+
+    println!("=== Layout ===\n{}", asm);
+
+    let lines: Vec<&str> = asm.lines().map(|l| l.trim()).collect();
+    let labels: Vec<&str> = lines
+        .iter()
+        .filter(|l| l.starts_with(".L") && l.ends_with(':'))
+        .map(|l| l.trim_end_matches(':'))
+        .collect();
+    assert!(labels[0].ends_with("_entry"), "Первым должен идти входной блок");
+
+    let pos = |suffix: &str| labels.iter().position(|l| l.ends_with(suffix)).unwrap();
+    assert_eq!(pos("while_body"), pos("while_cond") + 1, "Тело цикла должно следовать за условием");
+    assert!(pos("while_end") > pos("while_body"));
+
+    // Ни один `jmp` не ведет на метку, стоящую сразу за ним
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(target) = line.strip_prefix("jmp ") {
+            let next = lines.get(i + 1).copied().unwrap_or("");
+            assert_ne!(format!("{}:", target), next, "Лишний переход на следующий блок");
+        }
+    }
+}
+
+#[test]
+fn test_debug_line_directives() {
+    let source = r#"fn main() -> int {
+    int x = 3;
+    if (x > 1) {
+        x = x * 2;
+    }
+    return x;
+}
+"#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let ir = ir_program.expect("IR не сгенерирован");
+
+    let plain = generate_assembly(&ir, false);
+    assert!(!plain.assembly.contains("%line"));
+
+    let debug = minic::codegen::generate_debug_assembly(&ir, false, "prog.src");
+    println!("{}", debug.assembly);
+
+    let lines: Vec<&str> = debug
+        .assembly
+        .lines()
+        .filter(|l| l.starts_with("%line"))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "%line 2+0 prog.src",
+            "%line 3+0 prog.src",
+            "%line 4+0 prog.src",
+            "%line 6+0 prog.src",
+        ]
+    );
+
+    let body_start = debug.assembly.find("%line 4+0").unwrap();
+    assert!(debug.assembly[body_start..].contains("imul"));
+}
+
+#[test]
+fn test_debug_lines_follow_source_map() {
+    let dir = std::env::temp_dir().join(format!("minic_debug_lines_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("util.h"),
+        "fn twice(int v) -> int {\n    return v * 2;\n}\n",
+    )
+    .unwrap();
+    let main = dir.join("main.src");
+    let source = "#include \"util.h\"\n#define LIMIT 10\n\n\
+                  fn main() -> int {\n    int x = LIMIT;\n    return twice(x);\n}\n";
+
+    let includes = minic::preprocessor::IncludeOptions {
+        source_file: Some(main),
+        search_paths: vec![],
+    };
+    let (parse_output, ir_program) =
+        minic::compiler::compile_with_ir_includes(source, vec![], &includes);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let ir = ir_program.expect("IR не сгенерирован");
+
+    let debug = minic::codegen::generate_debug_assembly(&ir, false, "main.src");
+    println!("{}", debug.assembly);
+    let lines: Vec<&str> = debug
+        .assembly
+        .lines()
+        .filter(|l| l.starts_with("%line"))
+        .collect();
+    let util = std::fs::canonicalize(dir.join("util.h")).unwrap();
+    assert_eq!(
+        lines,
+        [
+            "%line 5+0 main.src".to_string(),
+            "%line 6+0 main.src".to_string(),
+            format!("%line 2+0 {}", util.display()),
+        ]
+    );
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_char_pointer_byte_access() {
+    let source = r#"
+        fn first(char* p) -> char {
+            return *p;
+        }
+
+        fn put(char* p, int i, char c) -> void {
+            p[i] = c;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let result = generate_assembly(&ir_program.expect("IR не сгенерирован"), false);
+    println!("{}", result.assembly);
+
+    assert!(result.assembly.contains("movsx rax, byte [rax]"));
+    assert!(result.assembly.contains("mov byte [rax], cl"));
+    assert!(!result.assembly.contains("imul rax, 8"));
+}
+
+#[test]
+fn test_char_array_byte_stride() {
+    let source = r#"
+        char tag[3] = {'a', 'b'};
+
+        fn main() -> int {
+            char buf[10];
+            buf[7] = 'z';
+            return buf[7] + tag[1];
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let ir = ir_program.expect("IR не сгенерирован");
+    let allocas: Vec<u32> = ir.functions[0]
+        .blocks
+        .values()
+        .flat_map(|b| &b.instructions)
+        .filter_map(|i| match i {
+            minic::ir::IRInstruction::Alloca(_, size) => Some(*size),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(allocas, vec![16], "Массив char[10] должен занимать 16 байт");
+    let result = generate_assembly(&ir, false);
+    println!("{}", result.assembly);
+
+    assert!(result.assembly.contains("tag: db 97, 98"));
+    assert!(result.assembly.contains("mov byte [rax], cl"));
+    assert!(result.assembly.contains("movsx rax, byte [rax]"));
+    assert!(!result.assembly.contains("imul rax, 8"));
+}
+
+#[test]
+fn test_unsigned_division_and_int_truncation() {
+    let source = r#"
+        fn udiv(unsigned int a, unsigned int b) -> unsigned int {
+            return a / b;
+        }
+
+        fn sdiv(long a, long b) -> long {
+            return a / b;
+        }
+
+        fn wrap(int a) -> int {
+            return a + 1;
+        }
+    "#;
+
+    let (parse_output, ir_program) = compile_with_ir(source, vec![]);
+    assert!(parse_output.is_valid(), "Ошибки: {:?}", parse_output.errors);
+    let result = generate_assembly(&ir_program.expect("IR не сгенерирован"), false);
+    println!("{}", result.assembly);
+
+    assert!(result.assembly.contains("xor edx, edx\n    div rcx"));
+    assert!(result.assembly.contains("cqo\n    idiv rcx"));
+    assert!(!result.assembly.contains("cdq"));
+    assert!(result.assembly.contains("movsxd rax, eax"));
+}
@@ -279,7 +279,7 @@ fn test_nested_break() {
             return sum;
         }
     "#;
-    assert_eq!(compile_and_run(source), 20);
+    assert_eq!(compile_and_run(source), 22);
 }
 
 #[test]
@@ -1,5 +1,8 @@
+use minic::codegen::{BuildError, BuildOptions};
 use std::fs;
-use std::process::Command;
+use std::path::Path;
+use std::io::Write;
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 use std::time::Duration;
@@ -21,68 +24,15 @@ fn compile_and_run(source: &str, expected_exit_code: i32) -> bool {
     }
 
     let test_id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
-    let asm_file = format!("test_output_{}.asm", test_id);
-    let obj_file = format!("test_output_{}.o", test_id);
     let exe_file = if cfg!(windows) {
         format!("test_program_{}.exe", test_id)
     } else {
         format!("test_program_{}", test_id)
     };
 
-    let (parse_output, ir_program) = minic::compiler::compile_with_ir(source, vec![]);
-    if !parse_output.is_valid() {
-        eprintln!("Parse errors: {:?}", parse_output.errors);
-        return false;
-    }
-
-    let ir = ir_program.expect("IR не сгенерирован");
-    let result = minic::codegen::generate_assembly(&ir, false);
-
-    println!("\n=== GENERATED ASM ===\n{}", result.assembly);
-    println!("=== END ASM ===\n");
-
-    fs::write(&asm_file, &result.assembly).expect("Failed to write ASM");
-
-    let nasm_status = Command::new("nasm")
-        .args(&["-f", "elf64", &asm_file, "-o", &obj_file])
-        .output();
-
-    let nasm_output = match nasm_status {
-        Ok(out) => out,
-        Err(e) => {
-            eprintln!("NASM error: {}", e);
-            return false;
-        }
-    };
-
-    if !nasm_output.status.success() {
-        eprintln!(
-            "NASM stderr:\n{}",
-            String::from_utf8_lossy(&nasm_output.stderr)
-        );
-        return false;
-    }
-
-    let linker = "gcc";
-
-    let ld_status = Command::new(linker)
-        .args(&["-no-pie", &obj_file, "-o", &exe_file])
-        .output();
-
-    let ld_output = match ld_status {
-        Ok(out) => out,
-        Err(e) => {
-            eprintln!("{} error: {}", linker, e);
-            return false;
-        }
-    };
-
-    if !ld_output.status.success() {
-        eprintln!(
-            "{} stderr:\n{}",
-            linker,
-            String::from_utf8_lossy(&ld_output.stderr)
-        );
+    let options = BuildOptions::default();
+    if let Err(e) = minic::compiler::build(source, vec![], Path::new(&exe_file), &options) {
+        eprintln!("Build error: {}", e);
         return false;
     }
 
@@ -103,8 +53,6 @@ fn compile_and_run(source: &str, expected_exit_code: i32) -> bool {
     let exit_code = run_result.status.code().unwrap_or(-1);
     println!("Exit code: {}, expected: {}", exit_code, expected_exit_code);
 
-    let _ = fs::remove_file(&asm_file);
-    let _ = fs::remove_file(&obj_file);
     let _ = fs::remove_file(&exe_file);
 
     thread::sleep(Duration::from_millis(100));
@@ -368,6 +316,45 @@ fn test_integration_short_circuit_and_false() {
     assert!(compile_and_run(source, 0));
 }
 
+#[test]
+fn test_integration_short_circuit_and_right_false() {
+    let source = r#"
+        fn main() -> int {
+            int x = 7;
+            int result = 0;
+            if (x > 1 && x < 5) {
+                result = 1;
+            }
+            return result;
+        }
+    "#;
+    assert!(compile_and_run(source, 0));
+}
+
+#[test]
+fn test_integration_nested_loops_liveness() {
+    let source = r#"
+        fn main() -> int {
+            int n = 4;
+            int total = 0;
+            int i = 0;
+            while (i < n) {
+                int j = 0;
+                while (j < n) {
+                    bool both = i > 0 && j > 0;
+                    if (both) {
+                        total = total + i * j;
+                    }
+                    j = j + 1;
+                }
+                i = i + 1;
+            }
+            return total;
+        }
+    "#;
+    assert!(compile_and_run(source, 36));
+}
+
 #[test]
 fn test_integration_short_circuit_or() {
     let source = r#"
@@ -617,3 +604,919 @@ fn test_integration_relational_operators_ge() {
     "#;
     assert!(compile_and_run(source, 1));
 }
+
+#[test]
+fn test_integration_struct_fields() {
+    let source = r#"
+        struct Point { int x; int y; }
+
+        fn main() -> int {
+            struct Point p;
+            p.x = 10;
+            p.y = p.x + 5;
+            p.x += 2;
+            p.y++;
+            return p.x + p.y;
+        }
+    "#;
+    assert!(compile_and_run(source, 28));
+}
+
+#[test]
+fn test_integration_struct_global_and_pointer_param() {
+    let source = r#"
+        struct Pair { int a; int b; }
+        struct Pair g;
+
+        fn sum(struct Pair p) -> int {
+            return p.a + p.b;
+        }
+
+        fn main() -> int {
+            struct Pair p;
+            p.a = 4;
+            p.b = 5;
+            g.a = 30;
+            struct Pair q = p;
+            q.b = 6;
+            return sum(q) + p.b + g.a;
+        }
+    "#;
+    assert!(compile_and_run(source, 45));
+}
+
+#[test]
+fn test_integration_initialized_globals() {
+    let source = r#"
+        int table[4] = {1, 2, 3, 4};
+        float pi = 3.14;
+        int counter;
+        int neg = -5 * 2;
+        int partial[5] = {7, 8};
+
+        fn main() -> int {
+            counter = counter + 1;
+            int s = table[0] + table[3] + partial[1] + partial[4];
+            if (pi > 3.0) {
+                s = s + 100;
+            }
+            table[2] = 20;
+            return s + table[2] + neg + counter;
+        }
+    "#;
+    assert!(compile_and_run(source, 124));
+}
+
+#[test]
+fn test_integration_float_arithmetic_by_type() {
+    let source = r#"
+        fn main() -> int {
+            float a = 7.0;
+            float b = -a;
+            float c = a / 4.0;
+            int n = 3;
+            float d = c * n;
+            float e = d - b;
+            if (b < 0.0 && e > 12.0) {
+                return 1;
+            }
+            return 0;
+        }
+    "#;
+    assert!(compile_and_run(source, 1));
+}
+
+#[test]
+fn test_integration_float_call_mixed_args() {
+    let source = r#"
+        fn mix(int a, float b, int c, float d) -> float {
+            return a * b + c * d;
+        }
+
+        fn main() -> int {
+            float r = mix(2, 1.5, 3, 0.5);
+            if (r > 4.4 && r < 4.6) {
+                return 1;
+            }
+            return 0;
+        }
+    "#;
+    assert!(compile_and_run(source, 1));
+}
+
+#[test]
+fn test_build_keeps_temps_on_request() {
+    if !is_nasm_installed() {
+        eprintln!("NASM not installed, skipping test");
+        return;
+    }
+
+    let exe = std::env::temp_dir().join("minic_build_keep_temps");
+    let options = BuildOptions {
+        keep_temps: true,
+        ..Default::default()
+    };
+    minic::compiler::build("fn main() -> int { return 3; }", vec![], &exe, &options)
+        .expect("сборка должна пройти");
+
+    assert!(exe.exists());
+    assert!(exe.with_extension("asm").exists());
+    assert!(exe.with_extension("o").exists());
+
+    let code = Command::new(&exe).status().unwrap().code();
+    assert_eq!(code, Some(3));
+
+    let _ = fs::remove_file(exe.with_extension("asm"));
+    let _ = fs::remove_file(exe.with_extension("o"));
+    let _ = fs::remove_file(&exe);
+}
+
+#[test]
+fn test_build_leaves_user_files_alone() {
+    if !is_nasm_installed() {
+        eprintln!("NASM not installed, skipping test");
+        return;
+    }
+
+    let exe = std::env::temp_dir().join("minic_build_user_files");
+    fs::write(exe.with_extension("asm"), "; написано вручную\n").unwrap();
+    fs::write(exe.with_extension("o"), "не объектный файл").unwrap();
+
+    let options = BuildOptions {
+        optimize: true,
+        ..Default::default()
+    };
+    minic::compiler::build("fn main() -> int { return 4; }", vec![], &exe, &options)
+        .expect("сборка должна пройти");
+
+    let code = Command::new(&exe).status().unwrap().code();
+    assert_eq!(code, Some(4));
+    assert_eq!(
+        fs::read_to_string(exe.with_extension("asm")).unwrap(),
+        "; написано вручную\n"
+    );
+    assert_eq!(
+        fs::read_to_string(exe.with_extension("o")).unwrap(),
+        "не объектный файл"
+    );
+
+    let _ = fs::remove_file(exe.with_extension("asm"));
+    let _ = fs::remove_file(exe.with_extension("o"));
+    let _ = fs::remove_file(&exe);
+}
+
+#[test]
+fn test_build_reports_missing_assembler() {
+    let exe = std::env::temp_dir().join("minic_build_missing_nasm");
+    let options = BuildOptions {
+        assembler: "minic-no-such-nasm".to_string(),
+        ..Default::default()
+    };
+    let err = minic::compiler::build("fn main() -> int { return 0; }", vec![], &exe, &options)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        BuildError::ToolNotFound { ref tool, .. } if tool == "minic-no-such-nasm"
+    ));
+    assert!(err.to_string().contains("minic-no-such-nasm"));
+    assert!(!exe.with_extension("asm").exists());
+}
+
+#[test]
+fn test_build_reports_compile_errors() {
+    let exe = std::env::temp_dir().join("minic_build_bad_source");
+    let options = BuildOptions::default();
+    let err = minic::compiler::build("fn main() -> int { return 0 }", vec![], &exe, &options)
+        .unwrap_err();
+
+    assert!(matches!(err, BuildError::Compile(_)));
+}
+
+#[test]
+fn test_run_command_exit_code_and_expected_stdout() {
+    if !is_nasm_installed() {
+        eprintln!("NASM not installed, skipping test");
+        return;
+    }
+
+    let dir = std::env::temp_dir().join(format!("minic_run_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("prog.src");
+    fs::write(
+        &source,
+        r#"
+        extern int printf(char* format, ...);
+
+        fn main() -> int {
+            printf("sum=%d\n", 2 + 3);
+            return 7;
+        }
+    "#,
+    )
+    .unwrap();
+    fs::write(dir.join("ok.out"), "sum=5\n").unwrap();
+    fs::write(dir.join("bad.out"), "sum=6\n").unwrap();
+
+    let run = |expected: &str| {
+        Command::new(env!("CARGO_BIN_EXE_minic"))
+            .arg("run")
+            .arg("-i")
+            .arg(&source)
+            .arg("--expect-stdout")
+            .arg(dir.join(expected))
+            .output()
+            .unwrap()
+    };
+
+    let ok = run("ok.out");
+    assert_eq!(ok.status.code(), Some(7));
+    assert_eq!(String::from_utf8_lossy(&ok.stdout), "sum=5\n");
+
+    let bad = run("bad.out");
+    assert_eq!(bad.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("sum=6"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_run_command_forwards_stdin_and_stderr() {
+    if !is_nasm_installed() {
+        eprintln!("NASM not installed, skipping test");
+        return;
+    }
+
+    let dir = std::env::temp_dir().join(format!("minic_run_stdin_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("echo.src");
+    fs::write(
+        &source,
+        r#"
+        extern int getchar();
+        extern long write(int fd, char* buf, long count);
+
+        fn main() -> int {
+            write(2, "warn\n", 5);
+            return getchar();
+        }
+    "#,
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_minic"))
+        .arg("run")
+        .arg("-i")
+        .arg(&source)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"A").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(output.status.code(), Some(65));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warn"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_integration_bitwise_and_shifts() {
+    let source = r#"
+        fn main() -> int {
+            int a = 12;
+            int b = 10;
+            int r = 0;
+            if ((a & b) == 8) { r = r + 1; }
+            if ((a | b) == 14) { r = r + 2; }
+            if ((a ^ b) == 6) { r = r + 4; }
+            if (~a == -13) { r = r + 8; }
+            int s = 3;
+            if ((1 << s) == 8 && (-64 >> s) == -8 && (a >> 2) == 3) { r = r + 16; }
+            int x = 5;
+            x <<= 2;
+            x |= 1;
+            x ^= 3;
+            x &= 30;
+            x >>= 1;
+            x %= 5;
+            if (x == 1) { r = r + 32; }
+            int arr[2];
+            arr[1] = 6;
+            arr[1] ^= 3;
+            if (arr[1] == 5) { r = r + 64; }
+            return r;
+        }
+    "#;
+    assert!(compile_and_run(source, 127));
+}
+
+#[test]
+fn test_integration_char_literals_and_strings() {
+    let source = r#"
+        extern char* malloc(int n);
+
+        fn length(char* p) -> int {
+            int n = 0;
+            while (p[n] != '\0') {
+                n = n + 1;
+            }
+            return n;
+        }
+
+        fn main() -> int {
+            char* buf = malloc(8);
+            buf[0] = 'a';
+            buf[1] = 'b';
+            buf[2] = '\n';
+            buf[3] = '\0';
+            *buf = *buf - 32;
+            char c = buf[0];
+            if (c != 'A' || buf[2] != 10) {
+                return 1;
+            }
+            return length(buf) * 10 + length("x\ty");
+        }
+    "#;
+    assert!(compile_and_run(source, 33));
+}
+
+#[test]
+fn test_integration_char_arrays_byte_stride() {
+    let source = r#"
+        char tag[5] = {'x', 'y'};
+        bool flags[3];
+
+        fn main() -> int {
+            char buf[4];
+            buf[0] = 'a';
+            buf[1] = 'b';
+            buf[2] = 'c';
+            buf[3] = '\0';
+            char* q = buf;
+            char* t = &tag[0];
+            flags[1] = true;
+            if (q[2] != 'c' || *(t + 1) != 'y' || tag[4] != '\0' || !flags[1] || flags[2]) {
+                return 1;
+            }
+            char m[2][3] = {{'a', 'b', 'c'}, {'d', 'e', 'f'}};
+            char* r = &m[0][0];
+            if (r[4] != 'e' || m[1][2] != 'f' || sizeof buf != 4 || sizeof m != 6) {
+                return 2;
+            }
+            char* p = &buf[0];
+            p = p + 1;
+            return *p;
+        }
+    "#;
+    assert!(compile_and_run(source, 98));
+}
+
+#[test]
+fn test_integration_long_and_unsigned() {
+    let source = r#"
+        fn widen(int x) -> long {
+            return x * 2L;
+        }
+
+        fn main() -> int {
+            long big = 3000000000L * 4;
+            if (big / 1000000 != 12000) {
+                return 1;
+            }
+            int x = 2147483647;
+            x = x + 1;
+            if (x >= 0) {
+                return 2;
+            }
+            unsigned int u = 4000000000U;
+            if (u / 3 != 1333333333 || u >> 28 != 14 || !(u > 3000000000U)) {
+                return 3;
+            }
+            u += 500000000;
+            if (u != 205032704) {
+                return 4;
+            }
+            int neg = -16;
+            if (neg >> 2 != -4 || neg / 3 != -5 || neg % 3 != -1) {
+                return 5;
+            }
+            int t = big;
+            if (t != -884901888 || widen(1500000000) != 3000000000L) {
+                return 6;
+            }
+            unsigned long ul = 18446744073709551615UL;
+            if (ul / 10 != 1844674407370955161UL || ul % 10 != 5) {
+                return 7;
+            }
+            unsigned int m = -1;
+            return m >> 26;
+        }
+    "#;
+    assert!(compile_and_run(source, 63));
+}
+
+#[test]
+fn test_integration_casts_and_sizeof() {
+    let source = r#"
+        extern void* malloc(long size);
+
+        struct Node {
+            int value;
+            long next;
+            char tag;
+        }
+
+        long node_bytes = sizeof(struct Node) * 2;
+
+        fn main() -> int {
+            float f = 3.75;
+            int i = (int)f;
+            if (i != 3 || (int)((float)i + 0.5) != 3) {
+                return 1;
+            }
+            if ((char)300 != 44 || (unsigned int)-1 != 4294967295U || !(bool)f) {
+                return 2;
+            }
+            if ((long)2147483647 + 1 != 2147483648L) {
+                return 3;
+            }
+            if (sizeof(struct Node) != 24 || sizeof(char*) != 8 || sizeof i != 4) {
+                return 4;
+            }
+            int n = 5;
+            int* xs = (int*)malloc(n * sizeof(int));
+            for (int k = 0; k < n; k++) {
+                xs[k] = k * 3;
+            }
+            if (xs[0] != 0 || xs[n - 1] != 12 || (long)&xs[n] - (long)xs != n * sizeof(int)) {
+                return 5;
+            }
+            char* buf = (char*)malloc(node_bytes);
+            buf[0] = (char)65;
+            buf[1] = (char)(buf[0] + 1);
+            return buf[1];
+        }
+    "#;
+    assert!(compile_and_run(source, 66));
+}
+
+#[test]
+fn test_integration_pointer_arithmetic() {
+    let source = r#"
+        extern void* malloc(long size);
+
+        struct Pair {
+            int a;
+            long b;
+        }
+
+        int table[4] = {10, 20, 30, 40};
+
+        fn main() -> int {
+            int arr[5];
+            arr[0] = 1; arr[1] = 2; arr[2] = 3; arr[3] = 4; arr[4] = 5;
+            int* p = arr;
+            int* q = p + 3;
+            if (*(p + 1) != 2 || *q != 4 || q - p != 3 || !(p < q)) {
+                return 1;
+            }
+            char* s = "hello";
+            char* e = s;
+            while (*e != (char)0) {
+                e++;
+            }
+            if (e - s != 5 || *(s + 1) != 101) {
+                return 2;
+            }
+            int* g = table + 2;
+            if (*g != 30 || *(1 + g) != 40) {
+                return 3;
+            }
+            q -= 2;
+            q++;
+            if (*q != 3) {
+                return 4;
+            }
+            int* n = null;
+            if (n != null) {
+                return 5;
+            }
+            struct Pair* ps = (struct Pair*)malloc(sizeof(struct Pair) * 3);
+            struct Pair* pe = ps + 3;
+            if (pe - ps != 3 || (long)pe - (long)ps != 48) {
+                return 6;
+            }
+            return *(&arr[2]) + *(&s[2]);
+        }
+    "#;
+    assert!(compile_and_run(source, 111));
+}
+
+#[test]
+fn test_integration_linked_list() {
+    let source = r#"
+        extern void* malloc(long size);
+
+        struct Node {
+            int value;
+            char tag;
+            struct Node* next;
+        }
+
+        struct Node* head;
+
+        fn push(struct Node* list, int value) -> struct Node* {
+            struct Node* n = (struct Node*)malloc(sizeof(struct Node));
+            n->value = value;
+            n->tag = (char)(65 + value);
+            n->next = list;
+            return n;
+        }
+
+        fn sum(struct Node* list) -> int {
+            int total = 0;
+            while (list != null) {
+                total += list->value;
+                list = list->next;
+            }
+            return total;
+        }
+
+        fn main() -> int {
+            head = null;
+            for (int i = 1; i <= 4; i++) {
+                head = push(head, i);
+            }
+            if (sum(head) != 10 || head->next->value != 3 || head->next->next->tag != 67) {
+                return 1;
+            }
+            head->value += 10;
+            head->next->value++;
+            (*head).value = (*head).value * 2;
+            struct Node** link = &head->next->next->next;
+            *link = null;
+            if (head->value != 28 || head->next->value != 4 || push(null, 9)->value != 9) {
+                return 2;
+            }
+            struct Node local;
+            local.next = head;
+            return sum(local.next) + local.next->next->next->value;
+        }
+    "#;
+    assert!(compile_and_run(source, 36));
+}
+
+#[test]
+fn test_integration_nested_structs_and_struct_arrays() {
+    let source = r#"
+        extern void* malloc(long size);
+
+        struct P { int x; char c; int y; }
+        struct Q { int a; struct P in; struct P arr[2]; }
+
+        struct Q gq;
+
+        fn sum(struct P p) -> int {
+            return p.x + p.y;
+        }
+
+        fn main() -> int {
+            struct Q q;
+            q.a = 1;
+            q.in.x = 3;
+            q.in.y = 4;
+            q.arr[1].y = 9;
+            if (q.a != 1 || q.in.x != 3 || q.in.y != 4 || q.arr[1].y != 9) {
+                return 1;
+            }
+            struct P ps[3];
+            for (int i = 0; i < 3; i++) {
+                ps[i].x = i * 10;
+                ps[i].y = i;
+            }
+            int* px = &ps[2].x;
+            *px = *px + 1;
+            if (ps[1].x != 10 || ps[2].x != 21 || ps[0].y != 0 || sum(ps[2]) != 23) {
+                return 2;
+            }
+            struct Q r = q;
+            r.in = ps[1];
+            ps[0] = q.arr[1];
+            if (r.in.x != 10 || r.a != 1 || r.arr[1].y != 9 || ps[0].y != 9 || sum(q.in) != 7) {
+                return 3;
+            }
+            gq.in.y = 5;
+            struct Q* pq = &gq;
+            pq->arr[0].x = 6;
+            struct P* pp = &pq->in;
+            pp->x = 2;
+            if (gq.in.y + gq.arr[0].x + gq.in.x != 13 || (*pq).in.y != 5) {
+                return 4;
+            }
+            struct P* heap = (struct P*)malloc(sizeof(struct P) * 4);
+            heap[3].y = 40;
+            (heap + 3)->x = 2;
+            q.in.x += 5;
+            q.in.y++;
+            return heap[3].y + heap[3].x + q.in.x + q.in.y;
+        }
+    "#;
+    assert!(compile_and_run(source, 55));
+}
+
+#[test]
+fn test_integration_struct_by_value() {
+    let source = r#"
+        struct P { int x; char c; long y; }
+        struct P g;
+
+        fn make(int a, long b) -> struct P {
+            struct P p;
+            p.x = a;
+            p.c = 'z';
+            p.y = b;
+            return p;
+        }
+
+        fn twice(struct P a) -> struct P {
+            a.x = a.x * 2;
+            return a;
+        }
+
+        fn sum(struct P a) -> long {
+            return a.x + a.y;
+        }
+
+        fn main() -> int {
+            struct P q = make(3, 4);
+            g = twice(q);
+            q = twice(twice(q));
+            if (q.x != 12 || g.x != 6 || q.c != 'z' || sum(make(1, 2)) != 3) {
+                return 1;
+            }
+            return (int)(q.y + g.y + sum(q));
+        }
+    "#;
+    assert!(compile_and_run(source, 24));
+}
+
+#[test]
+fn test_integration_field_pointers_keep_neighbours() {
+    let source = r#"
+        struct S { int x; int y; char c; char d; long z; }
+
+        fn main() -> int {
+            struct S s;
+            s.x = 1;
+            s.y = 2;
+            s.c = 'c';
+            s.d = 'd';
+            s.z = 3;
+            int* p = &s.x;
+            *p = -5;
+            char* q = &s.c;
+            *q = 'C';
+            if (s.x != -5 || s.y != 2 || s.c != 'C' || s.d != 'd' || s.z != 3) {
+                return 1;
+            }
+            *(p + 1) = 7;
+            return s.y + (int)s.z;
+        }
+    "#;
+    assert!(compile_and_run(source, 10));
+}
+
+#[test]
+fn test_integration_int_pointers_step_by_four() {
+    let source = r#"
+        extern void* malloc(long size);
+
+        int gv = 3;
+
+        fn set(int* p) -> void {
+            *p = -7;
+        }
+
+        fn main() -> int {
+            int x = 5;
+            set(&x);
+            int* q = &gv;
+            *q = -2;
+            int gl = gv;
+            if (x != -7 || gl != -2) {
+                return 1;
+            }
+            int a[4] = {1, 2, 3, 4};
+            int* p = &a[0];
+            p = p + 2;
+            if (*p != 3 || sizeof a != 16 || (long)&a[1] - (long)&a[0] != 4) {
+                return 2;
+            }
+            *p = -1;
+            if (a[2] != -1 || a[3] != 4 || a[1] != 2) {
+                return 3;
+            }
+            int* buf = (int*)malloc(3 * sizeof(int));
+            buf[0] = -5;
+            buf[1] = 6;
+            buf[2] = 7;
+            unsigned int u[2] = {4000000000U, 1U};
+            if (buf[0] + buf[1] + buf[2] != 8 || u[0] != 4000000000U) {
+                return 4;
+            }
+            return 42;
+        }
+    "#;
+    assert!(compile_and_run(source, 42));
+}
+
+#[test]
+fn test_integration_multidimensional_arrays() {
+    let source = r#"
+        int grid[2][3] = {{1, 2, 3}, {4, 5}};
+
+        fn trace(int m[][4], int n) -> int {
+            int s = 0;
+            for (int i = 0; i < n; i++) {
+                s += m[i][i];
+            }
+            return s;
+        }
+
+        fn row_sum(int* row, int n) -> int {
+            int s = 0;
+            for (int i = 0; i < n; i++) {
+                s += row[i];
+            }
+            return s;
+        }
+
+        fn main() -> int {
+            int m[3][4];
+            for (int i = 0; i < 3; i++) {
+                for (int j = 0; j < 4; j++) {
+                    m[i][j] = i * 10 + j;
+                }
+            }
+            if (m[1][2] != 12 || m[2][3] != 23 || trace(m, 3) != 33) {
+                return 1;
+            }
+            m[2][1] += 100;
+            if (m[2][1] != 121 || row_sum(m[1], 4) != 46) {
+                return 2;
+            }
+            int* p = &m[1][3];
+            if (*p != 13 || *(p + 1) != 20) {
+                return 3;
+            }
+            int k[2][2] = {{7, 8}, {9, 10}};
+            if (k[0][1] != 8 || k[1][0] != 9 || grid[1][1] + grid[0][2] + grid[1][2] != 8) {
+                return 4;
+            }
+            int cube[2][2][2];
+            cube[1][0][1] = 42;
+            return cube[1][0][1] + (int)sizeof m;
+        }
+    "#;
+    assert!(compile_and_run(source, 90));
+}
+
+#[test]
+fn test_integration_array_initializers() {
+    let source = r#"
+        int g[5] = {1, 2};
+        long h[] = {7, 8, 9};
+
+        fn dirty_stack() -> int {
+            int junk[8];
+            for (int i = 0; i < 8; i++) {
+                junk[i] = 99;
+            }
+            return junk[3];
+        }
+
+        fn check() -> int {
+            int a[5] = {1, 2, 3};
+            int b[] = {4, 5, 6, 7};
+            int m[2][3] = {{1}, {2, 3}};
+            float f[3] = {1.5};
+            var v = {10, 20};
+            if (a[2] != 3 || a[3] != 0 || a[4] != 0 || sizeof b != 16 || b[3] != 7) {
+                return 1;
+            }
+            if (m[0][1] != 0 || m[0][2] != 0 || m[1][1] != 3 || m[1][2] != 0) {
+                return 2;
+            }
+            if (f[0] != 1.5 || f[2] != 0.0 || v[1] != 20 || sizeof v != 8) {
+                return 3;
+            }
+            if (g[1] != 2 || g[4] != 0 || h[2] != 9 || sizeof h != 24) {
+                return 4;
+            }
+            return 0;
+        }
+
+        fn main() -> int {
+            dirty_stack();
+            return check() + 5;
+        }
+    "#;
+    assert!(compile_and_run(source, 5));
+}
+
+#[test]
+fn test_integration_array_initializer_conversions() {
+    let source = r#"
+        fn main() -> int {
+            float f[3] = {1.5, 2};
+            char m[6] = "hi\n";
+            int k[2] = {(int)2.5, 3};
+            if (f[1] != 2.0 || f[2] != 0.0 || m[1] != 'i' || m[2] != '\n' || m[5] != 0) {
+                return 1;
+            }
+            return k[0] + k[1] + m[3] + (int)(f[0] + f[1]);
+        }
+    "#;
+    assert!(compile_and_run(source, 8));
+}
+
+#[test]
+fn test_integration_do_while_conditional_comma() {
+    let source = r#"
+        fn max(int a, int b) -> int {
+            return a > b ? a : b;
+        }
+
+        fn main() -> int {
+            int i = 0;
+            int sum = 0;
+            do {
+                i = i + 1;
+                if (i == 3) continue;
+                if (i > 6) break;
+                sum += i;
+            } while (i < 10);
+            int once = 0;
+            do once += 1; while (false);
+            int j;
+            int k;
+            for (j = 0, k = 5; j < k; j += 1, k -= 1) {
+                sum += 1;
+            }
+            int calls = 0;
+            int z = sum > 100 ? (calls = calls + 1) : (calls = calls + 10);
+            float f = i > 0 ? 2 : 0.5;
+            int q = (i = 40, i + 2);
+            int arr[3] = {1, 2, 3};
+            int* p = null;
+            int* r = p == null ? arr : p;
+            int nested = i < 0 ? 1 : i < 50 ? 2 : 3;
+            return max(sum, 3) + z + once + (f > 1.5 ? 100 : 0) + q + r[2] + nested;
+        }
+    "#;
+    assert!(compile_and_run(source, 179));
+}
+
+#[test]
+fn test_integration_enum_switch() {
+    let source = r#"
+        enum Color { Red, Green = 5, Blue }
+        enum State { Idle, Running, Done }
+
+        fn score(enum Color c) -> int {
+            switch (c) {
+                case Red: return 1;
+                case Green: return 10;
+                case Blue: return 100;
+            }
+            return 0;
+        }
+
+        fn step(enum State s) -> enum State {
+            switch (s) {
+                case Idle: return Running;
+                default: return Done;
+            }
+            return Done;
+        }
+
+        fn main() -> int {
+            enum Color c = Blue;
+            int total = score(Red) + score(Green) + score(c);
+            enum State s = Idle;
+            int steps = 0;
+            while (s != Done) {
+                s = step(s);
+                steps += 1;
+            }
+            if (Blue == 6) {
+                total += steps;
+            }
+            return total;
+        }
+    "#;
+    assert!(compile_and_run(source, 113));
+}
@@ -136,3 +136,33 @@ fn test_dead_code_elimination() {
         "Должны быть удалены инструкции"
     );
 }
+
+/// Деление с беззнаковым результатом сворачивается как беззнаковое
+#[test]
+fn test_constant_folding_unsigned_division() {
+    let source = r#"
+        fn main() -> int {
+            unsigned long x = 18000000000000000000UL / 2UL;
+            long y = -8L / 2L;
+            return (int)(x / 1000000000000000000UL) + (int)y;
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let mut program = ir_program.unwrap();
+    PeepholeOptimizer::optimize(&mut program);
+
+    let main = program.get_function("main").unwrap();
+    let moved: Vec<i64> = main
+        .blocks
+        .values()
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|i| match i {
+            IRInstruction::Move(_, Operand::IntLiteral(v)) => Some(*v),
+            _ => None,
+        })
+        .collect();
+    assert!(moved.contains(&9_000_000_000_000_000_000), "Свернуто: {:?}", moved);
+    assert!(moved.contains(&-4), "Знаковое деление: {:?}", moved);
+    assert!(moved.iter().all(|v| *v >= -4), "Отрицательная свертка: {:?}", moved);
+}
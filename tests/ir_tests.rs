@@ -246,10 +246,10 @@ fn test_ir_type_information() {
 
     for (name, typ) in &main.locals {
         println!("DEBUG: local var {}: {}", name, typ);
-        match typ.as_str() {
-            "int" => has_int = true,
-            "float" => has_float = true,
-            "bool" => has_bool = true,
+        match typ {
+            IRType::Int => has_int = true,
+            IRType::Float => has_float = true,
+            IRType::Bool => has_bool = true,
             _ => {}
         }
     }
@@ -258,3 +258,400 @@ fn test_ir_type_information() {
     assert!(has_float, "Должна быть float переменная");
     assert!(has_bool, "Должна быть bool переменная");
 }
+
+/// Тест анализа живых переменных во вложенных циклах
+#[test]
+fn test_ir_liveness_nested_loops() {
+    let source = r#"
+        fn main() -> int {
+            int n = 3;
+            int sum = 0;
+            int i = 0;
+            while (i < n) {
+                int j = 0;
+                while (j < n) {
+                    sum = sum + j;
+                    j = j + 1;
+                }
+                i = i + 1;
+            }
+            return sum;
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let main = program.get_function("main").unwrap();
+    let liveness = compute_liveness(main);
+
+    let labels = |part: &str| -> Vec<String> {
+        main.blocks
+            .keys()
+            .filter(|l| l.ends_with(part))
+            .cloned()
+            .collect()
+    };
+
+    // `n` используется только в заголовках циклов, но должна оставаться
+    // живой во всех блоках тел, иначе регистр освободится до обратного ребра
+    for label in labels("while_body") {
+        let live_out = liveness.live_out(&label).unwrap();
+        assert!(live_out.contains("n"), "n должна быть живой на выходе из {}", label);
+        assert!(live_out.contains("sum"), "sum должна быть живой на выходе из {}", label);
+    }
+
+    let end_labels = labels("while_end");
+    assert!(end_labels
+        .iter()
+        .any(|l| liveness.live_in(l).unwrap().contains("sum")));
+
+    let entry_in = liveness.live_in(&main.entry_block).unwrap();
+    assert!(!entry_in.contains("n"), "n определяется во входном блоке");
+
+    for block in main.blocks.values() {
+        for succ in &block.successors {
+            let pred_list = &main.get_block(succ).unwrap().predecessors;
+            assert!(pred_list.contains(&block.label));
+        }
+    }
+}
+
+/// Тест типов временных значений
+#[test]
+fn test_ir_temporary_types() {
+    let source = r#"
+        fn scale(float x, int k) -> float {
+            return x * k;
+        }
+
+        fn main() -> int {
+            float a = 1.5;
+            float b = a + a;
+            bool big = b > 2.0;
+            int n = 2 + 3;
+            return n;
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+
+    let scale = program.get_function("scale").unwrap();
+    assert_eq!(scale.return_type, IRType::Float);
+    assert_eq!(
+        scale.parameters,
+        vec![("x".to_string(), IRType::Float), ("k".to_string(), IRType::Int)]
+    );
+
+    let main = program.get_function("main").unwrap();
+    let temp_type = |instr_matches: &dyn Fn(&IRInstruction) -> bool| -> IRType {
+        let dest = main
+            .blocks
+            .values()
+            .flat_map(|b| b.instructions.iter())
+            .find(|i| instr_matches(i))
+            .and_then(|i| i.dest())
+            .cloned()
+            .expect("Инструкция не найдена");
+        let name = match dest {
+            Operand::Temporary(name) => name,
+            other => panic!("Ожидалось временное значение, получено {}", other),
+        };
+        main.value_type(&name).cloned().expect("Тип не назначен")
+    };
+
+    let float_add = temp_type(&|i| matches!(i, IRInstruction::Add(_, Operand::Variable(_), _)));
+    let compare = temp_type(&|i| matches!(i, IRInstruction::CmpGtF(_, _, _)));
+    let int_add = temp_type(&|i| matches!(i, IRInstruction::Add(_, Operand::IntLiteral(_), _)));
+    assert_eq!(float_add, IRType::Float);
+    assert_eq!(compare, IRType::Bool);
+    assert_eq!(int_add, IRType::Int);
+}
+
+#[test]
+fn test_ir_block_source_lines() {
+    let source = r#"fn main() -> int {
+    int x = 1;
+    while (x < 10) {
+        x = x * 2;
+    }
+    return x;
+}
+"#;
+    let (_, ir) = minic::compiler::compile_with_ir(source, vec![]);
+    let ir = ir.expect("IR не сгенерирован");
+    let func = ir.get_function("main").expect("main");
+
+    let entry = &func.blocks[&func.entry_block];
+    assert_eq!(entry.line_at(0), Some(2));
+
+    let body = func
+        .blocks
+        .values()
+        .find(|b| b.label.ends_with("while_body"))
+        .expect("тело цикла");
+    assert_eq!(body.line_at(0), Some(4));
+
+    let end = func
+        .blocks
+        .values()
+        .find(|b| b.label.ends_with("while_end"))
+        .expect("выход из цикла");
+    assert_eq!(end.line_at(end.instructions.len() - 1), Some(6));
+}
+
+#[test]
+fn test_ir_multidimensional_array_layout() {
+    let source = r#"
+        int grid[2][3] = {{1, 2}, {4, 5, 6}};
+
+        fn main() -> int {
+            int m[3][4] = {{1, 2}, {3}};
+            m[2][1] = 7;
+            return m[1][0] + grid[1][2];
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let slot = |v: i64| Operand::IntLiteral(v);
+    assert_eq!(
+        program.global_inits.get("grid").unwrap(),
+        &vec![slot(1), slot(2), slot(0), slot(4), slot(5), slot(6)]
+    );
+
+    let main = program.get_function("main").unwrap();
+    let instructions: Vec<&IRInstruction> =
+        main.blocks.values().flat_map(|b| b.instructions.iter()).collect();
+    assert!(instructions.iter().any(|i| matches!(i, IRInstruction::Alloca(_, 48))));
+    let stores: Vec<i64> = instructions
+        .iter()
+        .filter_map(|i| match i {
+            IRInstruction::ArrayStore(_, Operand::IntLiteral(idx), v) if *v != slot(0) => {
+                Some(*idx)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(stores, vec![0, 1, 4, 9]);
+    assert!(instructions
+        .iter()
+        .any(|i| matches!(i, IRInstruction::ArrayLoad(_, _, Operand::IntLiteral(4)))));
+}
+
+#[test]
+fn test_ir_array_initializer_zero_fill() {
+    let source = r#"
+        fn main() -> int {
+            int a[4] = {5, 6};
+            int b[] = {1, 2, 3};
+            return a[3] + b[2];
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let main = program.get_function("main").unwrap();
+    let instructions: Vec<&IRInstruction> =
+        main.blocks.values().flat_map(|b| b.instructions.iter()).collect();
+    let stores = |name: &str| -> Vec<(i64, Operand)> {
+        instructions
+            .iter()
+            .filter_map(|i| match i {
+                IRInstruction::ArrayStore(Operand::Variable(n), Operand::IntLiteral(idx), v)
+                    if n == name =>
+                {
+                    Some((*idx, v.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let lit = |v: i64| Operand::IntLiteral(v);
+    assert_eq!(stores("a"), vec![(0, lit(5)), (1, lit(6)), (2, lit(0)), (3, lit(0))]);
+    assert_eq!(stores("b"), vec![(0, lit(1)), (1, lit(2)), (2, lit(3))]);
+    assert!(main
+        .locals
+        .contains(&("b".to_string(), IRType::Array(Box::new(IRType::Int), 3))));
+    assert!(instructions
+        .iter()
+        .any(|i| matches!(i, IRInstruction::Alloca(Operand::Variable(n), 16) if n == "b")));
+}
+
+/// Ветви `?:` попадают в разные блоки: вызов из невыбранной ветви не выполняется
+#[test]
+fn test_ir_array_initializer_element_conversion() {
+    let source = r#"
+        fn main() -> int {
+            float f[2] = {1.0, 2};
+            char m[6] = "hi\n";
+            return m[0];
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let main = program.get_function("main").unwrap();
+    let instructions: Vec<&IRInstruction> =
+        main.blocks.values().flat_map(|b| b.instructions.iter()).collect();
+    let converted = instructions.iter().find_map(|i| match i {
+        IRInstruction::IntToFloat(d, Operand::IntLiteral(2)) => Some(d.clone()),
+        _ => None,
+    });
+    let converted = converted.expect("Целый элемент должен приводиться к float");
+    assert!(instructions.contains(&&IRInstruction::ArrayStore(
+        Operand::Variable("f".to_string()),
+        Operand::IntLiteral(1),
+        converted,
+    )));
+
+    let bytes: Vec<(i64, Operand)> = instructions
+        .iter()
+        .filter_map(|i| match i {
+            IRInstruction::ArrayStore(Operand::Variable(n), Operand::IntLiteral(idx), v)
+                if n == "m" =>
+            {
+                Some((*idx, v.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    let lit = |v: i64| Operand::IntLiteral(v);
+    assert_eq!(
+        bytes,
+        vec![(0, lit(104)), (1, lit(105)), (2, lit(10)), (3, lit(0)), (4, lit(0)), (5, lit(0))]
+    );
+    assert!(!instructions
+        .iter()
+        .any(|i| matches!(i, IRInstruction::Move(_, Operand::StringLiteral(_)))));
+}
+
+#[test]
+fn test_ir_conditional_branches_in_separate_blocks() {
+    let source = r#"
+        fn left() -> int { return 1; }
+        fn right() -> int { return 2; }
+        fn main() -> int {
+            bool c = true;
+            return c ? left() : right();
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let main = program.get_function("main").unwrap();
+    let calls_in = |block: &BasicBlock| -> Vec<String> {
+        block
+            .instructions
+            .iter()
+            .filter_map(|i| match i {
+                IRInstruction::Call(_, Operand::Label(f), _) => Some(f.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+    let blocks: Vec<Vec<String>> = main
+        .blocks
+        .values()
+        .map(calls_in)
+        .filter(|calls| !calls.is_empty())
+        .collect();
+    assert_eq!(blocks.len(), 2, "Вызовы ветвей должны быть в разных блоках");
+    assert!(blocks.iter().all(|calls| calls.len() == 1));
+    assert!(main
+        .blocks
+        .values()
+        .flat_map(|b| &b.instructions)
+        .any(|i| matches!(i, IRInstruction::JumpIfNot(..))));
+}
+
+#[test]
+fn test_ir_struct_by_value_is_copied() {
+    use minic::ir::ir_generator::SRET_PARAM;
+
+    let source = r#"
+        struct P { int x; int y; }
+
+        fn make() -> struct P {
+            struct P p;
+            p.x = 1;
+            p.y = 2;
+            return p;
+        }
+
+        fn bump(struct P a) -> int {
+            a.x = 100;
+            return a.x;
+        }
+
+        fn main() -> int {
+            struct P q = make();
+            return bump(q) + q.x;
+        }
+    "#;
+
+    let (_, ir_program) = compiler::compile_with_ir(source, vec![]);
+    let program = ir_program.unwrap();
+    let make = program.get_function("make").unwrap();
+    assert_eq!(make.parameters.len(), 1, "Результат передается скрытым указателем");
+    assert_eq!(make.parameters[0].0, SRET_PARAM);
+    let make_instrs: Vec<&IRInstruction> =
+        make.blocks.values().flat_map(|b| b.instructions.iter()).collect();
+    let sret = Operand::Variable(SRET_PARAM.to_string());
+    assert!(make_instrs
+        .iter()
+        .any(|i| matches!(i, IRInstruction::FieldStore(b, 4, _, IRType::Int) if *b == sret)));
+    assert!(make_instrs.contains(&&IRInstruction::Return(Some(sret.clone()))));
+
+    let main = program.get_function("main").unwrap();
+    let instrs: Vec<&IRInstruction> =
+        main.blocks.values().flat_map(|b| b.instructions.iter()).collect();
+    let q = Operand::Variable("q".to_string());
+    assert!(
+        !instrs.iter().any(|i| matches!(i, IRInstruction::AddrOf(_, v) if *v == q)),
+        "Аргумент-структура не должен передаваться своим адресом"
+    );
+    assert!(!instrs.iter().any(|i| matches!(i, IRInstruction::Move(d, _) if *d == q)));
+    let buffers: Vec<&Operand> = instrs
+        .iter()
+        .filter_map(|i| match i {
+            IRInstruction::Alloca(t @ Operand::Temporary(_), 8) => Some(t),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(buffers.len(), 2, "Буфер результата и копия аргумента");
+    for buffer in buffers {
+        assert!(instrs
+            .iter()
+            .any(|i| matches!(i, IRInstruction::AddrOf(_, b) if b == buffer)));
+    }
+    assert!(instrs
+        .iter()
+        .any(|i| matches!(i, IRInstruction::FieldStore(b, 0, _, _) if *b == q)));
+}
+
+#[test]
+fn test_peephole_keeps_block_source_lines() {
+    let source = r#"fn main() -> int {
+    int a = 2 + 3;
+    int unused = a * 1;
+    int b = a;
+    return b;
+}
+"#;
+    let (_, ir) = minic::compiler::compile_with_ir(source, vec![]);
+    let mut ir = ir.expect("IR не сгенерирован");
+    minic::ir::PeepholeOptimizer::optimize(&mut ir);
+
+    let func = ir.get_function("main").expect("main");
+    let entry = &func.blocks[&func.entry_block];
+    assert!(entry
+        .lines
+        .iter()
+        .all(|(start, _)| *start < entry.instructions.len()));
+    for (i, instr) in entry.instructions.iter().enumerate() {
+        if matches!(instr, minic::ir::IRInstruction::Return(_)) {
+            assert_eq!(entry.line_at(i), Some(5));
+        }
+    }
+}
@@ -181,6 +181,147 @@ fn test_number_edge_cases() {
     }
 }
 
+/// Тест символьных литералов и escape-последовательностей.
+#[test]
+fn test_char_literals() {
+    use minic::common::token::TokenKind;
+
+    let test_cases = vec![
+        ("'a'", 'a'),
+        ("' '", ' '),
+        ("'\\n'", '\n'),
+        ("'\\t'", '\t'),
+        ("'\\0'", '\0'),
+        ("'\\''", '\''),
+        ("'\\\\'", '\\'),
+        ("'\"'", '"'),
+    ];
+
+    for (source, expected) in test_cases {
+        let mut scanner = Scanner::new(source);
+        let (tokens, errors) = scanner.scan_all();
+
+        assert!(errors.is_empty(), "Литерал {}: {:?}", source, errors);
+        assert_eq!(tokens[0].kind, TokenKind::CharLiteral(expected));
+        assert_eq!(tokens[0].lexeme, source);
+    }
+
+    for invalid in ["''", "'ab'", "'a", "'\\q'", "'\n'"] {
+        let mut scanner = Scanner::new(invalid);
+        let (_, errors) = scanner.scan_all();
+        assert!(!errors.is_empty(), "Литерал {} должен быть ошибкой", invalid);
+    }
+}
+
+/// Тест суффиксов целых литералов `L`, `U`, `UL`.
+#[test]
+fn test_integer_suffixes() {
+    use minic::common::token::TokenKind;
+
+    let test_cases = vec![
+        ("42", TokenKind::IntLiteral(42)),
+        ("42L", TokenKind::LongLiteral(42)),
+        ("3000000000l", TokenKind::LongLiteral(3_000_000_000)),
+        ("7U", TokenKind::UIntLiteral(7)),
+        ("4294967295u", TokenKind::UIntLiteral(u32::MAX)),
+        ("18446744073709551615UL", TokenKind::ULongLiteral(u64::MAX)),
+        ("5LU", TokenKind::ULongLiteral(5)),
+    ];
+
+    for (source, expected) in test_cases {
+        let mut scanner = Scanner::new(source);
+        let (tokens, errors) = scanner.scan_all();
+
+        assert!(errors.is_empty(), "Литерал {}: {:?}", source, errors);
+        assert_eq!(tokens[0].kind, expected);
+        assert_eq!(tokens[0].lexeme, source);
+    }
+
+    for invalid in ["4294967296U", "9223372036854775808L", "1LL", "1UU", "1ULU"] {
+        let mut scanner = Scanner::new(invalid);
+        let (_, errors) = scanner.scan_all();
+        assert!(!errors.is_empty(), "Литерал {} должен быть ошибкой", invalid);
+    }
+}
+
+/// Тест шестнадцатеричных, восьмеричных, двоичных литералов,
+/// разделителей разрядов и порядка вещественных чисел.
+#[test]
+fn test_radix_and_separator_literals() {
+    use minic::common::token::TokenKind;
+
+    let test_cases = vec![
+        ("0xFF", TokenKind::IntLiteral(255)),
+        ("0Xff", TokenKind::IntLiteral(255)),
+        ("0o17", TokenKind::IntLiteral(15)),
+        ("017", TokenKind::IntLiteral(15)),
+        ("0b1010", TokenKind::IntLiteral(10)),
+        ("1_000_000", TokenKind::IntLiteral(1_000_000)),
+        ("0xFFFF_FFFF", TokenKind::UIntLiteral(u32::MAX)),
+        ("0x1_0000_0000L", TokenKind::LongLiteral(1 << 32)),
+        ("0b1UL", TokenKind::ULongLiteral(1)),
+        ("-0x80000000", TokenKind::IntLiteral(i32::MIN)),
+        ("1e-9", TokenKind::FloatLiteral(1e-9)),
+        ("2.5E+3", TokenKind::FloatLiteral(2500.0)),
+        ("1_000.000_5", TokenKind::FloatLiteral(1000.0005)),
+    ];
+
+    for (source, expected) in test_cases {
+        let mut scanner = Scanner::new(source);
+        let (tokens, errors) = scanner.scan_all();
+
+        assert!(errors.is_empty(), "Литерал {}: {:?}", source, errors);
+        assert_eq!(tokens[0].kind, expected, "Литерал {}", source);
+        assert_eq!(tokens[0].lexeme, source);
+    }
+
+    for invalid in ["0x", "0b102", "09", "0o8", "1__0", "1_", "0x_1", "1.5f", "1e", "10px"] {
+        let mut scanner = Scanner::new(invalid);
+        let (_, errors) = scanner.scan_all();
+        assert!(
+            matches!(errors.first(), Some(minic::LexerError::InvalidNumber { .. })),
+            "Литерал {}: {:?}",
+            invalid,
+            errors
+        );
+    }
+}
+
+/// Тест ошибок переполнения целых литералов с диапазоном позиций.
+#[test]
+fn test_integer_literal_overflow() {
+    use minic::common::position::Position;
+    use minic::LexerError;
+
+    let cases = [
+        ("x = 2147483648;", "2147483648", 5, 14, "int"),
+        ("0x1_0000_0000", "0x1_0000_0000", 1, 13, "unsigned int"),
+        ("4294967296U", "4294967296U", 1, 11, "unsigned int"),
+        ("-1UL", "-1UL", 1, 4, "unsigned long"),
+        ("0xFFFF_FFFF_FFFF_FFFF_F", "0xFFFF_FFFF_FFFF_FFFF_F", 1, 23, "unsigned long"),
+        ("0x1_0000_0000_0000_0000", "0x1_0000_0000_0000_0000", 1, 23, "unsigned long"),
+        ("99999999999999999999", "99999999999999999999", 1, 20, "unsigned long"),
+    ];
+
+    for (source, lexeme, start, end, target) in cases {
+        let mut scanner = Scanner::new(source);
+        let (_, errors) = scanner.scan_all();
+        let expected = LexerError::IntegerOverflow {
+            position: Position::new(1, start),
+            end: Position::new(1, end),
+            lexeme: lexeme.to_string(),
+            target: target.to_string(),
+        };
+        assert_eq!(errors.first(), Some(&expected), "Литерал {}", source);
+    }
+
+    let error = Scanner::new("2147483648").scan_all().1.remove(0);
+    assert_eq!(
+        error.to_string(),
+        "1:1-1:10: Целая константа '2147483648' не помещается в тип int"
+    );
+}
+
 /// Тест всех ключевых слов языка.
 #[test]
 fn test_all_keywords() {
@@ -214,7 +355,7 @@ fn test_all_keywords() {
 fn test_all_operators() {
     let operators = vec![
         "+", "-", "*", "/", "%", "==", "!=", "<", "<=", ">", ">=", "&&", "||", "!", "=", "+=",
-        "-=", "*=", "/=",
+        "-=", "*=", "/=", "&", "|", "^", "~", "<<", ">>", "%=", "&=", "|=", "^=", "<<=", ">>=",
     ];
 
     for operator in operators {
@@ -473,6 +473,9 @@ mod tests {
                                 self.node_count += 1;
                             }
                         }
+                        minic::parser::Declaration::Enum(e) => {
+                            self.node_count += 1 + e.variants.len();
+                        }
                         minic::parser::Declaration::Variable(_v) => {
                             self.node_count += 1;
                         }
@@ -507,10 +510,12 @@ mod tests {
 
             fn visit_function_decl(&mut self, _func: &minic::parser::FunctionDecl) {}
             fn visit_struct_decl(&mut self, _struct_decl: &minic::parser::StructDecl) {}
+            fn visit_enum_decl(&mut self, _enum_decl: &minic::parser::EnumDecl) {}
             fn visit_var_decl(&mut self, _var_decl: &minic::parser::VarDecl) {}
             fn visit_param(&mut self, _param: &minic::parser::Param) {}
             fn visit_if_stmt(&mut self, _if_stmt: &minic::parser::IfStmt) {}
             fn visit_while_stmt(&mut self, _while_stmt: &minic::parser::WhileStmt) {}
+            fn visit_do_while_stmt(&mut self, _do_while: &minic::parser::DoWhileStmt) {}
             fn visit_for_stmt(&mut self, _for_stmt: &minic::parser::ForStmt) {}
             fn visit_return_stmt(&mut self, _return_stmt: &minic::parser::ReturnStmt) {}
             fn visit_expr_stmt(&mut self, _expr_stmt: &minic::parser::ExprStmt) {}
@@ -528,6 +533,10 @@ mod tests {
             fn visit_switch_stmt(&mut self, _switch_stmt: &minic::parser::SwitchStmt) {}
             fn visit_case_stmt(&mut self, _case_stmt: &minic::parser::CaseStmt) {}
             fn visit_array_access(&mut self, _access: &minic::parser::ArrayAccessExpr) {}
+            fn visit_cast(&mut self, _cast: &minic::parser::CastExpr) {}
+            fn visit_sizeof(&mut self, _sizeof: &minic::parser::SizeofExpr) {}
+            fn visit_conditional(&mut self, _cond: &minic::parser::ConditionalExpr) {}
+            fn visit_comma(&mut self, _comma: &minic::parser::CommaExpr) {}
         }
 
         let mut visitor = CountingVisitor::new();
@@ -545,4 +554,289 @@ mod tests {
             visitor.node_count
         );
     }
+
+    /// Расставляет скобки вокруг бинарных операций по дереву разбора
+    fn shape(expr: &minic::parser::Expression) -> String {
+        use minic::parser::Expression;
+        use minic::parser::SizeofOperand;
+        match expr {
+            Expression::Binary(b) => {
+                format!("({} {} {})", shape(&b.left), b.operator, shape(&b.right))
+            }
+            Expression::Unary(u) => format!("{}{}", u.operator, shape(&u.operand)),
+            Expression::Identifier(i) => i.name.clone(),
+            Expression::Literal(l) => l.value.to_string(),
+            Expression::Grouped(g) => shape(&g.expr),
+            Expression::Assignment(a) => {
+                format!("({} {} {})", shape(&a.target), a.operator, shape(&a.value))
+            }
+            Expression::Call(c) => {
+                let args: Vec<String> = c.arguments.iter().map(shape).collect();
+                format!("{}({})", shape(&c.callee), args.join(", "))
+            }
+            Expression::Cast(c) => format!("(({}){})", c.target_type, shape(&c.expr)),
+            Expression::StructAccess(sa) => {
+                format!("({}{}{})", shape(&sa.object), sa.operator(), sa.field)
+            }
+            Expression::ArrayAccess(aa) => format!("{}[{}]", shape(&aa.array), shape(&aa.index)),
+            Expression::Sizeof(s) => match &s.operand {
+                SizeofOperand::Type(t) => format!("sizeof({})", t),
+                SizeofOperand::Expr(e) => format!("sizeof {}", shape(e)),
+            },
+            Expression::Conditional(c) => format!(
+                "({} ? {} : {})",
+                shape(&c.condition),
+                shape(&c.then_expr),
+                shape(&c.else_expr)
+            ),
+            Expression::Comma(c) => format!("({}, {})", shape(&c.left), shape(&c.right)),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn parse_expressions(source: &str) -> Vec<String> {
+        use minic::parser::{Declaration, Statement};
+        let output = parse_string(source);
+        assert!(!output.has_errors(), "Ошибки: {:?}", output.errors.errors);
+        let ast = output.ast.expect("AST должен быть построен");
+        let body = match &ast.declarations[0] {
+            Declaration::Function(f) => &f.body.statements,
+            other => panic!("ожидалась функция, получено {:?}", other),
+        };
+        body.iter()
+            .map(|stmt| match stmt {
+                Statement::Expression(e) => shape(&e.expr),
+                other => panic!("ожидалось выражение, получено {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bitwise_precedence() {
+        let shapes = parse_expressions(
+            r#"
+            fn main() {
+                a | b ^ c & d == e;
+                1 + 2 << 3 < 4;
+                x && y | z;
+                ~a & b >> 1;
+                x <<= y |= 3;
+            }
+        "#,
+        );
+
+        assert_eq!(
+            shapes,
+            [
+                "(a | (b ^ (c & (d == e))))",
+                "(((1 + 2) << 3) < 4)",
+                "(x && (y | z))",
+                "(~a & (b >> 1))",
+                "(x <<= (y |= 3))",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cast_and_sizeof() {
+        let shapes = parse_expressions(
+            r#"
+            fn main() {
+                (int)f + 1;
+                (char*)malloc(n * sizeof(struct Node));
+                -(float)x * y;
+                sizeof x + 1;
+                sizeof(unsigned long) << 2;
+                (a) + b;
+            }
+        "#,
+        );
+
+        assert_eq!(
+            shapes,
+            [
+                "(((int)f) + 1)",
+                "((char*)malloc((n * sizeof(struct Node))))",
+                "(-((float)x) * y)",
+                "(sizeof x + 1)",
+                "(sizeof(unsigned long) << 2)",
+                "(a + b)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conditional_and_comma() {
+        let shapes = parse_expressions(
+            r#"
+            fn main() {
+                x = a < b ? a : b;
+                a ? b : c ? d : e;
+                a || b ? 1 : 2;
+                i = 0, j = n;
+                (i += 1, j -= 1);
+            }
+        "#,
+        );
+
+        assert_eq!(
+            shapes,
+            [
+                "(x = ((a < b) ? a : b))",
+                "(a ? b : (c ? d : e))",
+                "((a || b) ? 1 : 2)",
+                "((i = 0), (j = n))",
+                "((i += 1), (j -= 1))",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_do_while_and_comma_in_for() {
+        use minic::parser::{Declaration, Statement};
+        let output = parse_string(
+            r#"
+            fn main() {
+                do { i = i + 1; } while (i < 10);
+                for (i = 0, j = 9; i < j; i += 1, j -= 1) {}
+            }
+        "#,
+        );
+        assert!(!output.has_errors(), "Ошибки: {:?}", output.errors.errors);
+        let ast = output.ast.unwrap();
+        let body = match &ast.declarations[0] {
+            Declaration::Function(f) => &f.body.statements,
+            other => panic!("ожидалась функция, получено {:?}", other),
+        };
+        match &body[0] {
+            Statement::DoWhile(d) => {
+                assert!(matches!(d.body.as_ref(), Statement::Block(_)));
+                assert_eq!(shape(&d.condition), "(i < 10)");
+            }
+            other => panic!("ожидался do-while, получено {:?}", other),
+        }
+        match &body[1] {
+            Statement::For(f) => {
+                match f.init.as_deref() {
+                    Some(Statement::Expression(e)) => {
+                        assert_eq!(shape(&e.expr), "((i = 0), (j = 9))")
+                    }
+                    other => panic!("ожидалась инициализация, получено {:?}", other),
+                }
+                assert_eq!(shape(f.update.as_ref().unwrap()), "((i += 1), (j -= 1))");
+            }
+            other => panic!("ожидался for, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_multidimensional_arrays() {
+        use minic::parser::{Declaration, Expression, Statement};
+        let output = parse_string(
+            r#"
+            fn trace(int m[][4], int n) -> int {
+                int grid[2][3] = {{1, 2, 3}, {4, 5}};
+                return m[1][2] + grid[n][0];
+            }
+        "#,
+        );
+        assert!(!output.has_errors(), "Ошибки: {:?}", output.errors.errors);
+        let ast = output.ast.unwrap();
+        let func = match &ast.declarations[0] {
+            Declaration::Function(f) => f,
+            other => panic!("ожидалась функция, получено {:?}", other),
+        };
+        assert_eq!(func.parameters[0].param_type.to_string(), "int[][4]");
+        let grid = match &func.body.statements[0] {
+            Statement::VariableDecl(v) => v,
+            other => panic!("ожидалось объявление, получено {:?}", other),
+        };
+        assert_eq!(grid.var_type.to_string(), "int[2][3]");
+        let rows = match grid.initializer.as_deref() {
+            Some(Expression::ArrayInitializer(init)) => &init.elements,
+            other => panic!("ожидался инициализатор, получено {:?}", other),
+        };
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(&rows[1], Expression::ArrayInitializer(row) if row.elements.len() == 2));
+        match &func.body.statements[1] {
+            Statement::Return(r) => {
+                assert_eq!(shape(r.value.as_ref().unwrap()), "(m[1][2] + grid[n][0])")
+            }
+            other => panic!("ожидался return, получено {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_arrow_access() {
+        let shapes = parse_expressions(
+            r#"
+            fn main() {
+                n->next->value;
+                list.head->next = null;
+                -p->value * 2;
+                &nodes[i]->tag;
+                (*n).value;
+                make()->value;
+            }
+        "#,
+        );
+
+        assert_eq!(
+            shapes,
+            [
+                "((n->next)->value)",
+                "(((list.head)->next) = null)",
+                "(-(p->value) * 2)",
+                "&(nodes[i]->tag)",
+                "(*n.value)",
+                "(make()->value)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_declaration_and_case_constants() {
+        use minic::parser::{Declaration, Expression, Statement, Type};
+        let output = parse_string(
+            r#"
+            enum Color { Red, Green = 5, Blue, }
+            enum Sign { Minus = -1, Plus = 1 };
+            fn paint(enum Color c) -> int {
+                switch (c) {
+                    case Red: return 1;
+                    case 2: return 2;
+                    default: return 0;
+                }
+            }
+        "#,
+        );
+        assert!(!output.has_errors(), "Ошибки: {:?}", output.errors.errors);
+        let ast = output.ast.unwrap();
+        match &ast.declarations[0] {
+            Declaration::Enum(e) => {
+                assert_eq!(e.name, "Color");
+                let variants: Vec<_> =
+                    e.variants.iter().map(|v| (v.name.as_str(), v.value)).collect();
+                assert_eq!(variants, [("Red", None), ("Green", Some(5)), ("Blue", None)]);
+            }
+            other => panic!("ожидалось перечисление, получено {:?}", other),
+        }
+        match &ast.declarations[1] {
+            Declaration::Enum(e) => assert_eq!(e.variants[0].value, Some(-1)),
+            other => panic!("ожидалось перечисление, получено {:?}", other),
+        }
+        let func = match &ast.declarations[2] {
+            Declaration::Function(f) => f,
+            other => panic!("ожидалась функция, получено {:?}", other),
+        };
+        assert_eq!(func.parameters[0].param_type, Type::Enum("Color".to_string()));
+        match &func.body.statements[0] {
+            Statement::Switch(s) => {
+                let first = &s.cases[0].value;
+                assert!(matches!(first, Expression::Identifier(id) if id.name == "Red"));
+                assert!(matches!(s.cases[1].value, Expression::Literal(_)));
+            }
+            other => panic!("ожидался switch, получено {:?}", other),
+        }
+    }
 }
@@ -1,6 +1,9 @@
 //! Тесты для препроцессора.
 
-use minic::preprocessor::Preprocessor;
+use minic::Position;
+use minic::compiler::compile_with_ir_includes;
+use minic::preprocessor::{IncludeOptions, Preprocessor, PreprocessorError};
+use std::path::{Path, PathBuf};
 
 #[test]
 fn test_comment_removal() {
@@ -84,22 +87,23 @@ fn test_macro_recursion_detection() {
 #define A B
 #define B A
 int x = A;
+int y = B;
 "#;
 
     let mut preprocessor = Preprocessor::new(source);
-    let result = preprocessor.process();
+    let result = preprocessor.process().unwrap();
 
-    assert!(result.is_err());
-    let err = result.unwrap_err();
-    let err_str = err.to_string();
-    println!("Ошибка: {}", err_str);
-
-    assert!(
-        err_str.contains("рекурсивное")
-            || err_str.contains("Рекурсивное")
-            || err_str.contains("recursive")
-            || err_str.contains("Recursive")
-    );
+    assert!(result.contains("int x = A;"), "{}", result);
+    assert!(result.contains("int y = B;"), "{}", result);
+
+    // Рекурсией считается только превышение предела подстановок
+    let mut source = String::from("#define M0 x x\n");
+    for i in 1..=16 {
+        source.push_str(&format!("#define M{} M{} M{}\n", i, i - 1, i - 1));
+    }
+    source.push_str("int z = M16;\n");
+    let err = Preprocessor::new(&source).process().unwrap_err().to_string();
+    assert!(err.contains("Рекурсивное"), "{}", err);
 }
 
 #[test]
@@ -148,3 +152,379 @@ fn test_comment_removal_edge_cases() {
     let result = pp.process().unwrap();
     assert!(result.trim().ends_with(";"));
 }
+
+#[test]
+fn test_function_like_macros() {
+    let source = r#"
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define N 7
+#define STR(x) #x
+#define XSTR(x) STR(x)
+#define CAT(a, b) a ## b
+#define LOG(fmt, ...) printf(fmt, __VA_ARGS__)
+int v = MAX(MAX(1, N), 3);
+s = STR(a  "q"   b);
+t = XSTR(N);
+int CAT(var, 1) = CAT(N, 0);
+LOG("%d %d", 1, 2);
+MAX;
+"#;
+
+    let mut preprocessor = Preprocessor::new(source);
+    let result = preprocessor.process().unwrap();
+
+    let max_1_n = "((1) > (7) ? (1) : (7))";
+    assert!(result.contains(&format!(
+        "int v = (({0}) > (3) ? ({0}) : (3));",
+        max_1_n
+    )));
+    assert!(result.contains(r#"s = "a \"q\" b";"#));
+    assert!(result.contains(r#"t = "7";"#));
+    assert!(result.contains("int var1 = N0;"));
+    assert!(result.contains(r#"printf("%d %d", 1, 2);"#));
+    assert!(result.contains("MAX;"));
+}
+
+#[test]
+fn test_multiline_macro_calls() {
+    let source = "#define MAX(a, b) ((a) > (b) ? (a) : (b))
+int m = MAX(3,
+    4) + 1;
+int n = MAX(
+  MAX(1,
+      2),
+  0);
+int after = 5;
+";
+
+    let mut preprocessor = Preprocessor::new(source);
+    let result = preprocessor.process().unwrap();
+    println!("{}", result);
+
+    let lines: Vec<&str> = result.lines().collect();
+    let max_1_2 = "((1) > (2) ? (1) : (2))";
+    assert_eq!(lines[0], "int m = ((3) > (4) ? (3) : (4)) + 1;");
+    assert_eq!(lines[1], "");
+    assert_eq!(
+        lines[2],
+        format!("int n = (({0}) > (0) ? ({0}) : (0));", max_1_2)
+    );
+    assert!(lines[3..6].iter().all(|line| line.is_empty()));
+    assert_eq!(lines[6], "int after = 5;");
+}
+
+#[test]
+fn test_painted_macro_names() {
+    let source = r#"
+#define h(x) x h
+#define ID(x) x
+int a = h(1)(2);
+int b = ID(ID)(7);
+int c = ID(ID(ID))(8);
+#define f(x) x + f(x)
+#define A(x) B(x)
+#define B(x) A(x)
+int d = f(1);
+int e = A(1) + B(2);
+"#;
+
+    let mut preprocessor = Preprocessor::new(source);
+    let result = preprocessor.process().unwrap();
+
+    assert!(result.contains("int a = 1 h(2);"), "{}", result);
+    assert!(result.contains("int b = ID(7);"), "{}", result);
+    assert!(result.contains("int c = ID(8);"), "{}", result);
+    assert!(result.contains("int d = 1 + f(1);"), "{}", result);
+    assert!(result.contains("int e = A(1) + B(2);"), "{}", result);
+}
+
+#[test]
+fn test_function_like_macro_errors() {
+    let cases = [
+        ("#define ID(x) x\nID(1, 2)\n", "ожидает аргументов: 1, передано: 2"),
+        ("#define ID(x) x\nID(1\n", "незавершенный вызов"),
+        ("#define BAD(x #x\n", "незавершенный список параметров"),
+        ("#define BAD(x) #y\n", "за '#' должен следовать параметр"),
+        ("#define BAD(x) ## x\n", "'##' не может стоять"),
+    ];
+
+    for (source, expected) in cases {
+        let mut preprocessor = Preprocessor::new(source);
+        let err = preprocessor.process().unwrap_err().to_string();
+        assert!(err.contains(expected), "{}: {}", source, err);
+    }
+}
+
+#[test]
+fn test_if_elif_expressions() {
+    let source = r#"
+#define LEVEL 2
+#define DEBUG
+#if LEVEL >= 2 && defined(DEBUG)
+int a = 1;
+#elif 1
+int a = 2;
+#endif
+#if defined UNDEF || LEVEL * 3 == 5
+int b = 1;
+#elif LEVEL == 2 ? 0x10 : 0
+int b = 2;
+#else
+int b = 3;
+#endif
+#if 0
+#if 1 / 0
+#endif
+#elif !defined(DEBUG) || UNKNOWN
+int c = 1;
+#else
+int c = 2;
+#endif
+#ifdef DEBUG
+int d = 1;
+#elif 1
+int d = 2;
+#endif
+"#;
+
+    let mut preprocessor = Preprocessor::new(source);
+    let result = preprocessor.process().unwrap();
+
+    assert!(result.contains("int a = 1;"));
+    assert!(!result.contains("int a = 2;"));
+    assert!(result.contains("int b = 2;"));
+    assert!(!result.contains("int b = 1;") && !result.contains("int b = 3;"));
+    assert!(result.contains("int c = 2;"));
+    assert!(!result.contains("int c = 1;"));
+    assert!(result.contains("int d = 1;"));
+    assert!(!result.contains("int d = 2;"));
+}
+
+#[test]
+fn test_if_expression_errors() {
+    let cases = [
+        ("#if 1 +\n#endif\n", 1, "ожидалось выражение"),
+        ("\n#if (1\n#endif\n", 2, "ожидалось ')'"),
+        ("#if 1 / 0\n#endif\n", 1, "деление на ноль"),
+        ("#if defined(\n#endif\n", 1, "после 'defined'"),
+        ("#if 1\n#else\n#elif 1\n#endif\n", 3, "#elif после #else"),
+    ];
+
+    for (source, line, expected) in cases {
+        let mut preprocessor = Preprocessor::new(source);
+        match preprocessor.process() {
+            Err(PreprocessorError::InvalidDirective {
+                position, reason, ..
+            }) => {
+                assert_eq!(position.line, line, "{}", source);
+                assert!(reason.contains(expected), "{}: {}", source, reason);
+            }
+            other => panic!("{}: ожидалась ошибка директивы, получено {:?}", source, other),
+        }
+    }
+}
+
+#[test]
+fn test_macros_not_expanded_in_literals() {
+    let source = r#"
+#define MAX 100
+#define STR(x) #x
+#define ID(x) x
+s = "MAX value is MAX";
+c = 'M';
+w = "esc \" MAX" MAX;
+t = STR("MAX" MAX);
+u = ID("a, b");
+#if 'A' == 65
+int a = MAX;
+#endif
+"#;
+
+    let mut preprocessor = Preprocessor::new(source);
+    let result = preprocessor.process().unwrap();
+
+    assert!(result.contains(r#"s = "MAX value is MAX";"#));
+    assert!(result.contains("c = 'M';"));
+    assert!(result.contains(r#"w = "esc \" MAX" 100;"#));
+    assert!(result.contains(r#"t = "\"MAX\" MAX";"#));
+    assert!(result.contains(r#"u = "a, b";"#));
+    assert!(result.contains("int a = 100;"));
+}
+
+#[test]
+fn test_macro_names_follow_lexer_identifiers() {
+    let mut preprocessor = Preprocessor::new("#define ЧИСЛО 5\n");
+    match preprocessor.process() {
+        Err(PreprocessorError::InvalidMacroName { name }) => assert_eq!(name, "ЧИСЛО"),
+        other => panic!("ожидалась ошибка имени макроса, получено {:?}", other),
+    }
+
+    // Имя не продолжается не-ASCII буквой, как и идентификатор в лексере
+    let mut preprocessor = Preprocessor::new("#define N 5\nx = Nб;\n");
+    let result = preprocessor.process().unwrap();
+    assert!(result.contains("x = 5б;"));
+}
+
+/// Создает временный каталог с файлами для тестов `#include`.
+fn include_fixture(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("minic-include-{}-{}", name, std::process::id()));
+    for (file, text) in files {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, text).unwrap();
+    }
+    dir
+}
+
+fn preprocess_file(main: &Path, search_paths: Vec<PathBuf>) -> Result<String, PreprocessorError> {
+    let source = std::fs::read_to_string(main).unwrap();
+    let mut pp = Preprocessor::new(&source);
+    pp.set_include_options(&IncludeOptions {
+        source_file: Some(main.to_path_buf()),
+        search_paths,
+    });
+    pp.process()
+}
+
+#[test]
+fn test_include_search_paths_and_once() {
+    let dir = include_fixture(
+        "search",
+        &[
+            ("lib/util.h", "#pragma once\nint util_marker;\n"),
+            (
+                "src/guard.h",
+                "#ifndef GUARD_H\n#define GUARD_H\n#define BASE 20\n#include <util.h>\n#endif\n",
+            ),
+            (
+                "src/main.src",
+                "#include \"guard.h\"\n#include \"guard.h\"\n#include <util.h>\nint x = BASE;\n",
+            ),
+        ],
+    );
+
+    let result = preprocess_file(&dir.join("src/main.src"), vec![dir.join("lib")]).unwrap();
+    assert_eq!(result.matches("util_marker").count(), 1);
+    assert!(result.contains("int x = 20;"));
+
+    // Без -I угловые скобки не находят файл, даже если он рядом
+    let err = preprocess_file(&dir.join("src/main.src"), vec![]).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Файл 'util.h' не найден"), "{}", message);
+    assert!(message.contains("включен из"), "{}", message);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_include_cycle_and_nested_errors() {
+    let dir = include_fixture(
+        "cycle",
+        &[
+            ("a.h", "#include \"b.h\"\n"),
+            ("b.h", "\n#include \"a.h\"\n"),
+            ("cycle.src", "#include \"a.h\"\n"),
+            ("bad.h", "int y;\n#endif\n"),
+            ("bad.src", "\n#include \"bad.h\"\n"),
+        ],
+    );
+
+    let err = preprocess_file(&dir.join("cycle.src"), vec![]).unwrap_err();
+    assert!(matches!(err, PreprocessorError::IncludeCycle { .. }));
+    let message = err.to_string();
+    assert!(message.contains("Циклическое включение"), "{}", message);
+    assert!(message.contains("b.h:2"), "{}", message);
+    assert!(message.contains("cycle.src:1"), "{}", message);
+
+    let err = preprocess_file(&dir.join("bad.src"), vec![]).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("bad.h: 2:1"), "{}", message);
+    assert!(message.contains("bad.src:2"), "{}", message);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_source_map_locations() {
+    let dir = include_fixture(
+        "source-map",
+        &[
+            ("util.h", "#pragma once\nint u = 1;\n"),
+            (
+                "main.src",
+                concat!(
+                    "#include \"util.h\"\n",
+                    "#define TWICE(x) ((x) + (x))\n",
+                    "/* a\n b */ int v = TWICE(u) + w;\n",
+                ),
+            ),
+        ],
+    );
+    let main = dir.join("main.src");
+    let source = std::fs::read_to_string(&main).unwrap();
+    let mut pp = Preprocessor::new(&source);
+    pp.preserve_line_numbers(false);
+    pp.set_include_options(&IncludeOptions {
+        source_file: Some(main.clone()),
+        search_paths: vec![],
+    });
+    let result = pp.process().unwrap();
+    let map = pp.source_map();
+    assert_eq!(map.len(), result.lines().count());
+
+    // Строка включенного файла
+    let location = map.locate(Position::new(1, 5)).unwrap();
+    assert!(location.file.unwrap().ends_with("util.h"));
+    assert_eq!(location.position, Position::new(2, 5));
+
+    // Текст после комментария и раскрытия макроса сохраняет исходные колонки
+    let line = result.lines().nth(1).unwrap();
+    assert_eq!(line, " int v = ((u) + (u)) + w;");
+    let column = line.find('w').unwrap() + 1;
+    let location = map.locate(Position::new(2, column)).unwrap();
+    assert_eq!(location.position, Position::new(4, 26));
+    assert!(location.macros.is_empty());
+
+    let column = line.find('(').unwrap() + 1;
+    let location = map.locate(Position::new(2, column)).unwrap();
+    assert_eq!(location.position, Position::new(4, 15));
+    assert_eq!(location.macros, vec!["TWICE".to_string()]);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_diagnostics_use_source_map() {
+    let dir = include_fixture(
+        "diagnostics",
+        &[
+            ("util.h", "int helper(int x) {\n    return x +;\n}\n"),
+            ("inc.src", "#include \"util.h\"\nfn main() -> int { return 0; }\n"),
+            (
+                "macro.src",
+                "#define USE(v) (v + missing)\n\nfn main() -> int {\n    return USE(1);\n}\n",
+            ),
+        ],
+    );
+    let compile = |name: &str| {
+        let main = dir.join(name);
+        let source = std::fs::read_to_string(&main).unwrap();
+        let includes = IncludeOptions {
+            source_file: Some(main),
+            search_paths: vec![],
+        };
+        let (output, _) = compile_with_ir_includes(&source, vec![], &includes);
+        output.errors.errors[0].to_string()
+    };
+
+    let message = compile("inc.src");
+    assert!(message.starts_with("2:15:"), "{}", message);
+    assert!(message.contains("в файле"), "{}", message);
+    assert!(message.contains("util.h"), "{}", message);
+
+    let message = compile("macro.src");
+    assert!(message.starts_with("4:12: семантическая ошибка"), "{}", message);
+    assert!(message.contains("в раскрытии макроса 'USE'"), "{}", message);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
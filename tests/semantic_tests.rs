@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use minic::compiler;
-    use minic::semantic::{SemanticAnalyzer, SemanticErrorKind};
+    use minic::semantic::{SemanticAnalyzer, SemanticErrorKind, SymbolKind};
 
     /// Вспомогательная функция для тестирования семантики
     fn analyze(source: &str) -> (bool, Vec<SemanticErrorKind>) {
@@ -217,6 +217,441 @@ mod tests {
         assert!(valid, "Ошибки: {:?}", errors);
     }
 
+    #[test]
+    fn test_bitwise_operators() {
+        let source = r#"
+            fn main() {
+                int a = 12;
+                int b = (a & 10) | (a ^ 3) << 2 >> 1;
+                int c = ~b;
+                a <<= 2;
+                a %= 5;
+                a |= b;
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+    }
+
+    #[test]
+    fn test_char_literals() {
+        let source = r#"
+            fn main() {
+                char c = 'a';
+                char nl = '\n';
+                int code = c;
+                c = code + 1;
+                bool lower = c >= 'a' && c <= 'z';
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        let (valid, _) = analyze("fn main() { char c = 1.5; }");
+        assert!(!valid);
+    }
+
+    #[test]
+    fn test_long_and_unsigned_types() {
+        let source = r#"
+            fn widen(int x) -> long {
+                return x * 2L;
+            }
+            fn main() {
+                long big = 3000000000L;
+                unsigned int u = 4000000000U;
+                unsigned long ul = 18446744073709551615UL;
+                unsigned m = 7;
+                long int li = widen(1) + u;
+                int narrow = big;
+                bool less = u < ul;
+                u >>= 3;
+                ul = ul / u % 10;
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        let (valid, _) = analyze("fn main() { long x = 1.5; }");
+        assert!(!valid);
+        let (valid, _) = analyze("fn main() { unsigned int u = 1; bool b = u; }");
+        assert!(!valid);
+    }
+
+    #[test]
+    fn test_casts_and_sizeof() {
+        let source = r#"
+            extern void* malloc(long size);
+            struct Node { int value; struct Node* next; }
+            fn main() {
+                float f = 2.5;
+                int i = (int)f;
+                char c = (char)(i + 300);
+                bool b = (bool)f;
+                struct Node* n = (struct Node*)malloc(sizeof(struct Node));
+                long addr = (long)n;
+                char* p = (char*)addr;
+                unsigned long size = sizeof i + sizeof(int[4]);
+                (void)size;
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for source in [
+            "fn main() { float f = 1.0; char* p = (char*)f; }",
+            "fn main() { int* p; float f = (float)p; }",
+            "struct P { int x; } fn main() { struct P s; int n = (int)s; }",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&SemanticErrorKind::TypeMismatch), "{}: {:?}", source, errors);
+        }
+        let (valid, errors) = analyze("fn main() { long n = sizeof(struct Missing); }");
+        assert!(!valid);
+        assert!(errors.contains(&SemanticErrorKind::UndeclaredIdentifier));
+    }
+
+    #[test]
+    fn test_pointer_arithmetic_and_null() {
+        let source = r#"
+            fn main() {
+                int arr[4];
+                int* p = arr;
+                int* q = p + 2;
+                long d = q - p;
+                bool before = p < q;
+                char* s = null;
+                void* v = null;
+                q -= 1;
+                p++;
+                if (s == null || v != q) {
+                    d = d + 1;
+                }
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        let (valid, errors) = analyze("fn main() { int x = null; }");
+        assert!(!valid);
+        assert!(errors.contains(&SemanticErrorKind::AssignmentTypeMismatch), "{:?}", errors);
+
+        for source in [
+            "fn main() { int* p; float f = 1.5; int* q = p + f; }",
+            "fn main() { int* p; int* q; int* r = p + q; }",
+            "fn main() { int* p; char* c; long d = p - c; }",
+            "fn main() { int* p; char* c; bool b = p < c; }",
+            "fn main() { void* v; void* w = v + 1; }",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&SemanticErrorKind::TypeMismatch), "{}: {:?}", source, errors);
+        }
+    }
+
+    #[test]
+    fn test_arrow_access_and_self_referential_structs() {
+        let source = r#"
+            extern void* malloc(long size);
+            struct Node { int value; struct Node* next; }
+            struct List { struct Node* head; int size; }
+            fn main() {
+                struct List list;
+                list.head = (struct Node*)malloc(sizeof(struct Node));
+                list.head->next = null;
+                list.head->value = 1;
+                struct Node* n = list.head;
+                int v = n->value + (*n).value;
+                struct Node** link = &n->next;
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for source in [
+            "struct Node { int v; struct Node inner; } fn main() {}",
+            "struct Node { int v; struct Node items[2]; } fn main() {}",
+            "struct P { int x; } fn main() { struct P p; int x = p->x; }",
+            "struct P { int x; } fn main() { struct P* p; int x = p.x; }",
+            "fn main() { int* p; int x = p->x; }",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&SemanticErrorKind::TypeMismatch), "{}: {:?}", source, errors);
+        }
+        let (valid, errors) = analyze("struct A { struct B b; } struct B { int x; } fn main() {}");
+        assert!(!valid);
+        assert!(errors.contains(&SemanticErrorKind::UndeclaredIdentifier));
+        let (valid, errors) = analyze("struct P { int x; } fn main() { struct P* p; p->y = 1; }");
+        assert!(!valid);
+        assert!(errors.contains(&SemanticErrorKind::UndeclaredField));
+    }
+
+    #[test]
+    fn test_global_initializers_must_be_constant() {
+        let source = r#"
+            enum Color { RED, GREEN }
+            int a = (int)2.5 + GREEN * 4;
+            bool b = !(1 > 2);
+            long sizes[2] = {sizeof(int), (long)-1};
+            int pick = 2 > 1 ? GREEN : 4;
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for source in [
+            "int a = 1; int b = a + 1;",
+            "fn f() -> int { return 1; } int x = f();",
+            "int a = 1; int c = a > 0 ? 1 : 2;",
+            "int t[2] = {1, 2}; int* p = &t[0];",
+            "struct P { int x; } struct P p; struct P q = p;",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(
+                errors.contains(&SemanticErrorKind::InvalidExpression),
+                "{}: {:?}",
+                source,
+                errors
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_array_string_initializer_fits() {
+        let (valid, errors) = analyze(
+            "char msg[6] = \"hello\"; char exact[5] = \"hello\"; \
+             fn main() { char m[3] = \"a\\n\"; }",
+        );
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for source in [
+            "char big[3] = \"hello\";",
+            "fn main() { char m[2] = \"ab\\n\"; }",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&SemanticErrorKind::TypeMismatch), "{:?}", errors);
+        }
+    }
+
+    #[test]
+    fn test_nested_struct_fields_and_struct_arrays() {
+        let source = r#"
+            struct P { int x; int y; }
+            struct Q { int a; struct P in; struct P arr[2]; }
+            fn main() {
+                struct Q q;
+                q.in.x = 3;
+                q.arr[1].y = q.in.x;
+                struct P ps[3];
+                ps[1] = q.in;
+                int* px = &ps[2].x;
+                long n = sizeof(struct Q);
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        let (valid, errors) = analyze(
+            "struct P { int x; } fn make() -> struct P { struct P p; return p; } \
+             fn main() { int* px = &make().x; }",
+        );
+        assert!(!valid);
+        assert!(errors.contains(&SemanticErrorKind::InvalidExpression), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_multidimensional_arrays() {
+        let source = r#"
+            int grid[2][3] = {{1, 2, 3}, {4}};
+            fn trace(int m[][4], int n) -> int {
+                return m[n][n];
+            }
+            fn main() {
+                int m[3][4] = {{1, 2}, {3, 4, 5, 6}};
+                int t = trace(m, 2) + m[1][3] + grid[1][0];
+                int* row = m[2];
+                m[0][1] += t;
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for source in [
+            "fn f(int m[][]) {} fn main() {}",
+            "fn main() { int m[2][] ; }",
+            "fn f(int m[][4]) {} fn main() { int a[3][5]; f(a); }",
+            "fn main() { int m[2][2] = {{1, 2}, {3, 4}, {5, 6}}; }",
+            "fn main() { int m[2][2] = {{1, 2, 3}}; }",
+            "fn main() { int m[2][2] = {1, 2}; }",
+            "fn main() { int a[2] = {{1}, {2}}; }",
+            "int g[2][2] = {{1, 2, 3}}; fn main() {}",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            let expected =
+                [SemanticErrorKind::TypeMismatch, SemanticErrorKind::ArgumentTypeMismatch];
+            assert!(errors.iter().any(|e| expected.contains(e)), "{}: {:?}", source, errors);
+        }
+    }
+
+    #[test]
+    fn test_array_initializers() {
+        let source = r#"
+            long table[] = {1, 2, 3};
+            fn main() {
+                int a[5] = {1, 2};
+                int b[] = {4, 5, 6, 7};
+                float f[2] = {1.5, 2};
+                var v = {10, 20};
+                int n = b[3] + v[1];
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for (source, kind) in [
+            ("fn main() { int a[2] = {1, 2, 3}; }", SemanticErrorKind::TypeMismatch),
+            ("int g[1] = {1, 2}; fn main() {}", SemanticErrorKind::TypeMismatch),
+            ("fn main() { int a[]; }", SemanticErrorKind::TypeMismatch),
+            ("fn main() { int a[2] = {1.5, 2}; }", SemanticErrorKind::AssignmentTypeMismatch),
+            ("fn main() { int x = {1}; }", SemanticErrorKind::AssignmentTypeMismatch),
+            ("fn main() { int a[2] = {y, 1}; }", SemanticErrorKind::UndeclaredIdentifier),
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&kind), "{}: {:?}", source, errors);
+        }
+    }
+
+    #[test]
+    fn test_do_while_conditional_and_comma() {
+        let source = r#"
+            fn pick(int* p, int* q, bool first) -> int* {
+                return first ? p : null;
+            }
+            fn main() -> int {
+                int i = 0;
+                int j = 10;
+                do {
+                    i++;
+                    if (i == 2) continue;
+                    if (i > j) break;
+                } while (i < 5);
+                for (i = 0, j = 3; i < j; i++, j--) {}
+                float f = i > 0 ? 1 : 0.5;
+                long l = true ? i : 2L;
+                int arr[2];
+                int* p = i < 0 ? arr : null;
+                return (i = 3, i + 1);
+            }
+        "#;
+        let (valid, errors) = analyze(source);
+        assert!(valid, "Ошибки: {:?}", errors);
+
+        for (source, kind) in [
+            ("fn main() { int x = 1 ? 2 : 3; }", SemanticErrorKind::InvalidConditionType),
+            ("fn main() { int x = true ? 2 : false; }", SemanticErrorKind::TypeMismatch),
+            (
+                "fn main() { int a = 1; int* p = &a; int x = true ? p : 1; }",
+                SemanticErrorKind::TypeMismatch,
+            ),
+            (
+                "fn main() { int* p = null; char* c = null; var x = true ? p : c; }",
+                SemanticErrorKind::TypeMismatch,
+            ),
+            ("fn main() { do {} while (1); }", SemanticErrorKind::InvalidConditionType),
+            ("fn main() { int x = (y, 1); }", SemanticErrorKind::UndeclaredIdentifier),
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&kind), "{}: {:?}", source, errors);
+        }
+    }
+
+    #[test]
+    fn test_enum_constants_and_switch_checks() {
+        let source = r#"
+            enum Color { Red, Green = 5, Blue }
+            fn main() -> int {
+                enum Color c = Blue;
+                int x = c + 1;
+                switch (c) {
+                    case Red: x = 1;
+                    case Green: x = 2;
+                    case Blue: x = 3;
+                }
+                switch (x) { case 1: x = 0; case 'a': x = 2; }
+                return x;
+            }
+        "#;
+        let parse_output = compiler::syntactic_analysis(source);
+        let output = SemanticAnalyzer::new().analyze(parse_output.ast.unwrap());
+        assert!(output.is_valid(), "Ошибки: {:?}", output.errors.errors);
+        assert!(!output.errors.has_warnings());
+        let blue = output.symbol_table.lookup("Blue").unwrap();
+        assert_eq!(blue.kind, SymbolKind::EnumConstant);
+        assert_eq!(blue.const_value, Some(6));
+
+        let source = r#"
+            enum Color { Red, Green = 5, Blue }
+            fn paint(enum Color c) -> int {
+                switch (c) { case Green: return 1; }
+                switch (c) { case Red: return 2; default: return 3; }
+                return 0;
+            }
+        "#;
+        let parse_output = compiler::syntactic_analysis(source);
+        let output = SemanticAnalyzer::new().analyze(parse_output.ast.unwrap());
+        assert!(output.is_valid(), "Ошибки: {:?}", output.errors.errors);
+        let warnings = &output.errors.warnings;
+        assert_eq!(warnings.len(), 1, "Предупреждения: {:?}", warnings);
+        assert_eq!(warnings[0].kind, SemanticErrorKind::NonExhaustiveSwitch);
+        assert!(warnings[0].message.ends_with("Red, Blue"), "{}", warnings[0].message);
+
+        for (source, kind) in [
+            (
+                "enum E { A, B = 0 } fn f(enum E e) { switch (e) { case A: e = B; case B: {} } }",
+                SemanticErrorKind::DuplicateCase,
+            ),
+            (
+                "fn f(int x) { switch (x) { case 1: x = 0; case 1: x = 2; } }",
+                SemanticErrorKind::DuplicateCase,
+            ),
+            (
+                "enum E { A } enum F { B } fn f(enum E e) { switch (e) { case B: {} } }",
+                SemanticErrorKind::TypeMismatch,
+            ),
+            (
+                "fn f(int x) { int y = 1; switch (x) { case y: {} } }",
+                SemanticErrorKind::InvalidExpression,
+            ),
+            ("enum E { A, B, A }", SemanticErrorKind::DuplicateDeclaration),
+            ("enum E { A } fn f() { A = 1; }", SemanticErrorKind::InvalidAssignmentTarget),
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&kind), "{}: {:?}", source, errors);
+        }
+    }
+
+    #[test]
+    fn test_bitwise_operators_require_integers() {
+        for source in [
+            "fn main() { int x = 1.5 & 2; }",
+            "fn main() { int x = 1 << 2.0; }",
+            "fn main() { bool b = true | false; }",
+            "fn main() { int x = ~2.5; }",
+            "fn main() { float f = 1.0; f ^= 3; }",
+        ] {
+            let (valid, errors) = analyze(source);
+            assert!(!valid, "Ожидалась ошибка для: {}", source);
+            assert!(errors.contains(&SemanticErrorKind::TypeMismatch), "{}: {:?}", source, errors);
+        }
+    }
+
     #[test]
     fn test_var_type_inference() {
         let source = r#"